@@ -10,6 +10,7 @@ use byteorder::{ByteOrder, LittleEndian};
 use candle_core::{D, Device, IndexOp, Tensor};
 use candle_nn::{VarBuilder, ops::softmax};
 use rodio::{Decoder, Source};
+use signal_processing::{SignalBuffer, resample_to_rate};
 
 use crate::{
 	error::DeepLearningError,
@@ -734,13 +735,28 @@ fn load_audio_as_mono_16k(path: &Path) -> Result<Vec<f32>, DeepLearningError> {
 		return Ok(mono_samples);
 	}
 
-	Ok(linear_resample(
-		&mono_samples,
-		sample_rate as usize,
-		whisper_modeling::SAMPLE_RATE,
+	Ok(resample_mono(
+		mono_samples,
+		sample_rate,
+		whisper_modeling::SAMPLE_RATE as u32,
 	))
 }
 
+/// 重采样单声道 PCM。
+///
+/// 优先使用多相抗混叠重采样；像 22051 Hz 这类含大素因子的采样率无法构造多相滤波器，
+/// 此时退回线性插值。
+fn resample_mono(samples: Vec<f32>, input_rate: u32, target_rate: u32) -> Vec<f32> {
+	let input = SignalBuffer {
+		sample_rate: input_rate as f32,
+		samples,
+	};
+	match resample_to_rate(&input, target_rate as f32) {
+		Ok(resampled) => resampled.samples,
+		Err(_) => linear_resample(&input.samples, input_rate as usize, target_rate as usize),
+	}
+}
+
 /// 对 PCM 数据执行线性重采样。
 fn linear_resample(samples: &[f32], input_rate: usize, target_rate: usize) -> Vec<f32> {
 	if samples.is_empty() || input_rate == target_rate {
@@ -1122,3 +1138,40 @@ fn map_candle_error(action: &str, error: candle_core::Error) -> DeepLearningErro
 		message: format!("{action}失败: {error}"),
 	}
 }
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn resample_mono_should_fall_back_to_linear_for_large_ratios() {
+		let samples = vec![0.5; 22_051];
+
+		let resampled = resample_mono(samples.clone(), 22_051, 16_000);
+
+		assert!(
+			resample_to_rate(
+				&SignalBuffer {
+					sample_rate: 22_051.0,
+					samples,
+				},
+				16_000.0,
+			)
+			.is_err()
+		);
+		assert_eq!(resampled.len(), 16_000);
+		assert!(resampled.iter().all(|sample| (sample - 0.5).abs() < 1e-6));
+	}
+
+	#[test]
+	fn resample_mono_should_use_polyphase_for_common_rates() {
+		let resampled = resample_mono(vec![0.5; 44_100], 44_100, 16_000);
+
+		assert_eq!(resampled.len(), 16_000);
+		assert!(
+			resampled[1_000..15_000]
+				.iter()
+				.all(|sample| (sample - 0.5).abs() < 1e-3)
+		);
+	}
+}
@@ -0,0 +1,258 @@
+//! EDF+/BDF+ 注释 (Time-stamped Annotation Lists)
+//!
+//! 注释通道按字节存放 TAL：`+起始[\x15时长]\x14文本\x14…\x14\x00`，
+//! 每个数据记录的第一个 TAL 为只含起始时刻的计时注释
+
+use crate::loader::EdfLoaderError;
+
+/// TAL 中时刻与时长的分隔符
+const DURATION_SEPARATOR: u8 = 0x15;
+/// TAL 中时刻与文本、文本与文本之间的分隔符
+const TEXT_SEPARATOR: u8 = 0x14;
+/// TAL 结束符，也是注释通道的填充字节
+const TAL_TERMINATOR: u8 = 0x00;
+/// TAL 时刻与时长保留的小数位数（微秒精度），避免浮点误差写出超长数字
+const TAL_DECIMALS: usize = 6;
+/// 注释槽的最小字节数，足够容纳较长记录的计时注释
+const MIN_ANNOTATION_SLOT_BYTES: usize = 16;
+
+/// 单条注释，例如事件标记、睡眠分期或刺激代码
+#[derive(Debug, Clone, PartialEq)]
+pub struct Annotation {
+	/// 相对文件开始时刻的起始时间（秒）
+	pub onset_secs: f64,
+	/// 持续时间（秒），瞬时事件为 None
+	pub duration_secs: Option<f64>,
+	/// 注释文本
+	pub text: String,
+}
+
+impl Annotation {
+	/// 创建瞬时注释
+	///
+	/// # Arguments
+	/// * `onset_secs` - 起始时间（秒）
+	/// * `text` - 注释文本
+	pub fn new(onset_secs: f64, text: impl Into<String>) -> Self {
+		Self {
+			onset_secs,
+			duration_secs: None,
+			text: text.into(),
+		}
+	}
+
+	/// 设置持续时间
+	pub fn with_duration(mut self, duration_secs: f64) -> Self {
+		self.duration_secs = Some(duration_secs);
+		self
+	}
+}
+
+/// 解析一个数据记录中注释通道的字节，跳过计时注释与填充
+///
+/// # Arguments
+/// * `bytes` - 单个数据记录里注释信号的全部字节
+pub fn parse_annotation_record(bytes: &[u8]) -> Result<Vec<Annotation>, EdfLoaderError> {
+	let mut annotations = Vec::new();
+	for tal in bytes
+		.split(|byte| *byte == TAL_TERMINATOR)
+		.filter(|tal| !tal.is_empty())
+	{
+		let mut parts = tal.split(|byte| *byte == TEXT_SEPARATOR);
+		let timing = parts.next().unwrap_or_default();
+		let (onset, duration) = match timing.iter().position(|byte| *byte == DURATION_SEPARATOR) {
+			Some(index) => (&timing[..index], Some(&timing[index + 1..])),
+			None => (timing, None),
+		};
+		if !matches!(onset.first(), Some(b'+' | b'-')) {
+			return Err(EdfLoaderError::InvalidFormat(format!(
+				"注释起始时刻缺少符号: {}",
+				String::from_utf8_lossy(onset)
+			)));
+		}
+
+		let onset_secs = parse_tal_number(onset, "注释起始时刻")?;
+		let duration_secs = duration
+			.filter(|duration| !duration.is_empty())
+			.map(|duration| parse_tal_number(duration, "注释时长"))
+			.transpose()?;
+		annotations.extend(
+			parts
+				.filter(|text| !text.is_empty())
+				.map(|text| Annotation {
+					onset_secs,
+					duration_secs,
+					text: String::from_utf8_lossy(text).into_owned(),
+				}),
+		);
+	}
+	Ok(annotations)
+}
+
+/// 解析一个数据记录中注释通道开头计时注释的起始时刻
+///
+/// 注释通道为空或首个 TAL 含有文本时返回 None
+///
+/// # Arguments
+/// * `bytes` - 单个数据记录里注释信号的全部字节
+pub(crate) fn parse_record_onset(bytes: &[u8]) -> Result<Option<f64>, EdfLoaderError> {
+	let Some(tal) = bytes
+		.split(|byte| *byte == TAL_TERMINATOR)
+		.next()
+		.filter(|tal| !tal.is_empty())
+	else {
+		return Ok(None);
+	};
+	let mut parts = tal.split(|byte| *byte == TEXT_SEPARATOR);
+	let onset = parts.next().unwrap_or_default();
+	if parts.any(|text| !text.is_empty()) || !matches!(onset.first(), Some(b'+' | b'-')) {
+		return Ok(None);
+	}
+	parse_tal_number(onset, "记录起始时刻").map(Some)
+}
+
+/// 把注释编码为 TAL 字节
+pub fn encode_annotation(annotation: &Annotation) -> Vec<u8> {
+	let mut bytes = format_onset(annotation.onset_secs).into_bytes();
+	if let Some(duration) = annotation.duration_secs {
+		bytes.push(DURATION_SEPARATOR);
+		bytes.extend_from_slice(format_tal_number(duration).as_bytes());
+	}
+	bytes.push(TEXT_SEPARATOR);
+	bytes.extend_from_slice(annotation.text.as_bytes());
+	bytes.push(TEXT_SEPARATOR);
+	bytes.push(TAL_TERMINATOR);
+	bytes
+}
+
+/// 计算每个记录的注释槽字节数：至少容纳计时注释，并向上取整到整数个样本
+///
+/// # Arguments
+/// * `requested_bytes` - 调用方请求的槽字节数
+/// * `bytes_per_sample` - 每个样本的字节数，EDF 为 2，BDF 为 3
+pub(crate) fn annotation_slot_bytes(requested_bytes: usize, bytes_per_sample: usize) -> usize {
+	requested_bytes
+		.max(MIN_ANNOTATION_SLOT_BYTES)
+		.div_ceil(bytes_per_sample)
+		* bytes_per_sample
+}
+
+/// 把注释分配到各数据记录的注释槽，每个槽以计时注释开头并用 0 填充到 `slot_bytes`
+///
+/// 注释优先放入其起始时刻所在的记录，放不下时顺延到后续记录；仍放不下时返回 None
+///
+/// # Arguments
+/// * `record_onsets` - 各记录的起始时刻（秒），EDF+D 中记录之间可以有间隔
+/// * `slot_bytes` - 每个注释槽的字节数
+pub(crate) fn pack_annotation_records(
+	annotations: &[Annotation],
+	record_onsets: &[f64],
+	slot_bytes: usize,
+) -> Option<Vec<Vec<u8>>> {
+	let mut slots: Vec<Vec<u8>> = record_onsets
+		.iter()
+		.map(|onset| {
+			let mut slot = format_onset(*onset).into_bytes();
+			slot.extend_from_slice(&[TEXT_SEPARATOR, TEXT_SEPARATOR, TAL_TERMINATOR]);
+			slot
+		})
+		.collect();
+
+	let mut ordered: Vec<&Annotation> = annotations.iter().collect();
+	ordered.sort_by(|left, right| left.onset_secs.total_cmp(&right.onset_secs));
+	for annotation in ordered {
+		let encoded = encode_annotation(annotation);
+		let first_record = record_onsets
+			.iter()
+			.rposition(|onset| *onset <= annotation.onset_secs)
+			.unwrap_or(0);
+		let slot = slots
+			.iter_mut()
+			.skip(first_record)
+			.find(|slot| slot.len() + encoded.len() <= slot_bytes)?;
+		slot.extend_from_slice(&encoded);
+	}
+
+	for slot in &mut slots {
+		if slot.len() > slot_bytes {
+			return None;
+		}
+		slot.resize(slot_bytes, TAL_TERMINATOR);
+	}
+	Some(slots)
+}
+
+fn format_onset(onset_secs: f64) -> String {
+	let text = format_tal_number(onset_secs);
+	if text.starts_with('-') {
+		text
+	} else {
+		format!("+{text}")
+	}
+}
+
+/// 按固定小数位格式化并去掉末尾的 0，例如 0.30000000000000004 写为 "0.3"
+fn format_tal_number(value: f64) -> String {
+	let text = format!("{value:.TAL_DECIMALS$}");
+	let text = text.trim_end_matches('0').trim_end_matches('.');
+	if text == "-0" {
+		"0".to_string()
+	} else {
+		text.to_string()
+	}
+}
+
+fn parse_tal_number(bytes: &[u8], field_name: &str) -> Result<f64, EdfLoaderError> {
+	let text = String::from_utf8_lossy(bytes);
+	text.parse::<f64>()
+		.map_err(|error| EdfLoaderError::InvalidFormat(format!("{field_name} 解析失败: {error}")))
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn parses_time_keeping_and_multi_text_tals() {
+		let bytes = b"+0\x14\x14\x00+1.5\x1530\x14Sleep stage W\x14Lights off\x14\x00-0.25\x14Start\x14\x00\x00\x00";
+
+		let annotations = parse_annotation_record(bytes).unwrap();
+
+		assert_eq!(
+			annotations,
+			[
+				Annotation::new(1.5, "Sleep stage W").with_duration(30.0),
+				Annotation::new(1.5, "Lights off").with_duration(30.0),
+				Annotation::new(-0.25, "Start"),
+			]
+		);
+		assert!(parse_annotation_record(b"1.5\x14Bad\x14\x00").is_err());
+		assert_eq!(parse_record_onset(bytes).unwrap(), Some(0.0));
+		assert_eq!(
+			parse_record_onset(b"+0.25\x14\x14\x00\x00").unwrap(),
+			Some(0.25)
+		);
+		assert_eq!(parse_record_onset(b"\x00\x00").unwrap(), None);
+	}
+
+	#[test]
+	fn packs_annotations_into_record_slots() {
+		let annotations = [
+			Annotation::new(2.5, "stim 2"),
+			Annotation::new(0.5, "stim 1").with_duration(0.1),
+		];
+
+		let slots = pack_annotation_records(&annotations, &[0.0, 1.0, 2.0], 30).unwrap();
+		let parsed: Vec<Vec<Annotation>> = slots
+			.iter()
+			.map(|slot| parse_annotation_record(slot).unwrap())
+			.collect();
+
+		assert!(slots.iter().all(|slot| slot.len() == 30));
+		assert!(slots[1].starts_with(b"+1\x14\x14\x00"));
+		assert_eq!(parsed[0], [annotations[1].clone()]);
+		assert!(parsed[1].is_empty());
+		assert_eq!(parsed[2], [annotations[0].clone()]);
+		assert!(pack_annotation_records(&annotations, &[0.0, 1.0, 2.0], 12).is_none());
+	}
+}
@@ -2,12 +2,17 @@
 //!
 //! BDF 是 24 位版本的 EDF 格式，用于 BioSemi 设备采集的 EEG 数据
 
-use rand::Rng;
+use crate::annotation::{Annotation, annotation_slot_bytes, pack_annotation_records};
+use crate::header::{write_ascii_field, write_signal_field};
+use eeg_simulator::{EegSimulationConfig, EegSimulator};
 use std::fs::File;
-use std::io::{BufWriter, Seek, Write};
+use std::io::{BufWriter, Seek, SeekFrom, Write};
 use std::path::Path;
 use thiserror::Error;
 
+/// BDF+ 注释通道标签
+const ANNOTATION_LABEL: &str = "BDF Annotations";
+
 /// BDF 文件写入错误
 #[derive(Error, Debug)]
 pub enum BdfWriterError {
@@ -19,6 +24,15 @@ pub enum BdfWriterError {
 
 	#[error("数据大小不匹配: 期望 {expected}, 实际 {actual}")]
 	DataSizeMismatch { expected: usize, actual: usize },
+
+	#[error("仿真信号生成失败: {0}")]
+	SimulationError(String),
+}
+
+impl From<eeg_simulator::SimulationError> for BdfWriterError {
+	fn from(err: eeg_simulator::SimulationError) -> Self {
+		BdfWriterError::SimulationError(err.to_string())
+	}
 }
 
 /// BDF 信号参数
@@ -54,6 +68,10 @@ pub struct BdfWriter {
 	record_size: usize,
 	/// 数据记录数量
 	num_records: usize,
+	/// 每个记录中注释通道的字节数，0 表示不写注释通道
+	annotation_slot_bytes: usize,
+	/// 待写入的注释
+	annotations: Vec<Annotation>,
 	/// 文件
 	file: Option<BufWriter<File>>,
 }
@@ -64,20 +82,69 @@ impl BdfWriter {
 	/// # Arguments
 	/// * `path` - 输出文件路径
 	/// * `signals` - 信号参数列表
-	/// * `sample_rate` - 采样率 (Hz)
-	/// * `total_samples` - 总采样点数
+	/// * `sample_rate` - 主采样率 (Hz)，与 `total_samples` 一起决定记录数
+	/// * `total_samples` - 主采样率下的总采样点数
+	///
+	/// 每个数据记录为 1 秒，各信号按自身的 `sample_rate` 写入每记录采样数，可以互不相同
 	pub fn create(
 		path: &Path,
 		signals: Vec<BdfSignalParam>,
 		sample_rate: i32,
 		total_samples: usize,
+	) -> Result<Self, BdfWriterError> {
+		Self::create_with_annotation_slot(path, signals, sample_rate, total_samples, 0)
+	}
+
+	/// 创建带 "BDF Annotations" 通道的 BDF+ 文件
+	///
+	/// 注释通过 `add_annotation` 添加，在 `finalize` 时写入各记录的注释槽；
+	/// 此时每次 `write_samples` 必须恰好写入一个数据记录
+	///
+	/// # Arguments
+	/// * `annotation_bytes_per_record` - 每个记录的注释槽字节数，向上取整到 3 的倍数
+	pub fn create_with_annotations(
+		path: &Path,
+		signals: Vec<BdfSignalParam>,
+		sample_rate: i32,
+		total_samples: usize,
+		annotation_bytes_per_record: usize,
+	) -> Result<Self, BdfWriterError> {
+		Self::create_with_annotation_slot(
+			path,
+			signals,
+			sample_rate,
+			total_samples,
+			annotation_slot_bytes(annotation_bytes_per_record, 3),
+		)
+	}
+
+	fn create_with_annotation_slot(
+		path: &Path,
+		mut signals: Vec<BdfSignalParam>,
+		sample_rate: i32,
+		total_samples: usize,
+		annotation_slot_bytes: usize,
 	) -> Result<Self, BdfWriterError> {
 		let channel_count = signals.len();
-		let header_size = 256 + (256 * channel_count);
+		if annotation_slot_bytes > 0 {
+			signals.push(BdfSignalParam {
+				label: ANNOTATION_LABEL.to_string(),
+				physical_max: 1.0,
+				physical_min: -1.0,
+				digital_max: 8_388_607,
+				digital_min: -8_388_608,
+				sample_rate: (annotation_slot_bytes / 3) as i32,
+				physical_dimension: String::new(),
+			});
+		}
+		let header_size = 256 + (256 * signals.len());
 		let num_records = total_samples.div_ceil(sample_rate as usize);
 
 		// BDF 每个数据记录内，按通道顺序连续存放每个通道的一整段样本。
-		let record_size = channel_count * sample_rate as usize * 3;
+		let record_size = signals
+			.iter()
+			.map(|signal| signal.sample_rate.max(0) as usize * 3)
+			.sum();
 
 		let file =
 			File::create(path).map_err(|e| BdfWriterError::FileCreateError(e.to_string()))?;
@@ -89,6 +156,8 @@ impl BdfWriter {
 			header_size,
 			record_size,
 			num_records,
+			annotation_slot_bytes,
+			annotations: Vec::new(),
 			file: Some(BufWriter::new(file)),
 		};
 
@@ -126,8 +195,13 @@ impl BdfWriter {
 		// 184-192 字节: 头部长度 (8 字节 ASCII)
 		write_ascii_field(&mut header[184..192], &format!("{:<8}", self.header_size));
 
-		// 192-236 字节: 保留 (44 字节)
-		write_ascii_field(&mut header[192..236], "24BIT");
+		// 192-236 字节: 保留 (44 字节)，带注释通道时标记为连续的 BDF+
+		let reserved = if self.annotation_slot_bytes > 0 {
+			"BDF+C"
+		} else {
+			"24BIT"
+		};
+		write_ascii_field(&mut header[192..236], reserved);
 
 		// 236-244 字节: 数据记录数量 (8 字节 ASCII)
 		write_ascii_field(&mut header[236..244], &format!("{:<8}", self.num_records));
@@ -136,17 +210,15 @@ impl BdfWriter {
 		write_ascii_field(&mut header[244..252], &format!("{:<8}", 1));
 
 		// 252-256 字节: 信号数量 (4 字节 ASCII)
-		write_ascii_field(&mut header[252..256], &format!("{:<4}", self.channel_count));
+		write_ascii_field(&mut header[252..256], &format!("{:<4}", self.signals.len()));
 
 		// ===== 信号头 =====
-		// EDF/BDF 头部中的各字段需要“按字段分组”为所有通道依次写入，
-		// 不是每个通道一个独立的 256 字节块。
 		let mut offset = 256;
 		offset = write_signal_field(&mut header, offset, 16, &self.signals, |signal| {
 			signal.label.clone()
 		});
-		offset = write_signal_field(&mut header, offset, 80, &self.signals, |_| {
-			"AgAgCl electrodes".to_string()
+		offset = write_signal_field(&mut header, offset, 80, &self.signals, |signal| {
+			annotation_blank(signal, "AgAgCl electrodes")
 		});
 		offset = write_signal_field(&mut header, offset, 8, &self.signals, |signal| {
 			signal.physical_dimension.clone()
@@ -163,8 +235,8 @@ impl BdfWriter {
 		offset = write_signal_field(&mut header, offset, 8, &self.signals, |signal| {
 			format!("{:<8}", signal.digital_max)
 		});
-		offset = write_signal_field(&mut header, offset, 80, &self.signals, |_| {
-			"HP:0.1Hz LP:70Hz".to_string()
+		offset = write_signal_field(&mut header, offset, 80, &self.signals, |signal| {
+			annotation_blank(signal, "HP:0.1Hz LP:70Hz")
 		});
 		offset = write_signal_field(&mut header, offset, 8, &self.signals, |signal| {
 			format!("{:<8}", signal.sample_rate)
@@ -180,7 +252,8 @@ impl BdfWriter {
 	/// 写入多通道数据
 	///
 	/// # Arguments
-	/// * `data` - 数据，格式为 [channel][samples]，每样本点 24-bit signed
+	/// * `data` - 数据，格式为 [channel][samples]，每样本点 24-bit signed；
+	///   每次写入一个数据记录，即每个通道各写入其 `sample_rate` 个样本
 	pub fn write_samples(&mut self, data: &[Vec<f64>]) -> Result<(), BdfWriterError> {
 		if data.len() != self.channel_count {
 			return Err(BdfWriterError::DataSizeMismatch {
@@ -189,6 +262,18 @@ impl BdfWriter {
 			});
 		}
 
+		// 数据记录内按通道连续存放，每次必须恰好写入一个完整记录，
+		// 否则各通道样本会错位到相邻通道
+		for (channel_samples, signal) in data.iter().zip(&self.signals) {
+			let expected = signal.sample_rate.max(0) as usize;
+			if channel_samples.len() != expected {
+				return Err(BdfWriterError::DataSizeMismatch {
+					expected,
+					actual: channel_samples.len(),
+				});
+			}
+		}
+
 		let samples_to_write = data[0].len();
 		let file = self
 			.file
@@ -197,7 +282,7 @@ impl BdfWriter {
 
 		// 转换并写入每通道数据
 		for (ch, channel_samples) in data.iter().enumerate().take(self.channel_count) {
-			for value in channel_samples.iter().take(samples_to_write) {
+			for value in channel_samples {
 				let value = *value;
 
 				// 转换为 24-bit signed integer
@@ -219,10 +304,28 @@ impl BdfWriter {
 			}
 		}
 
+		if self.annotation_slot_bytes > 0 {
+			file.write_all(&vec![0u8; self.annotation_slot_bytes])
+				.map_err(|e| BdfWriterError::WriteError(e.to_string()))?;
+		}
+
 		self.samples_written += samples_to_write;
 		Ok(())
 	}
 
+	/// 添加一条注释，在 `finalize` 时写入
+	///
+	/// 仅对 `create_with_annotations` 创建的写入器有效
+	pub fn add_annotation(&mut self, annotation: Annotation) -> Result<(), BdfWriterError> {
+		if self.annotation_slot_bytes == 0 {
+			return Err(BdfWriterError::WriteError(
+				"未创建注释通道，无法写入注释".to_string(),
+			));
+		}
+		self.annotations.push(annotation);
+		Ok(())
+	}
+
 	/// 完成写入并关闭文件
 	pub fn finalize(mut self) -> Result<(), BdfWriterError> {
 		if let Some(mut file) = self.file.take() {
@@ -243,6 +346,10 @@ impl BdfWriter {
 					.map_err(|e| BdfWriterError::WriteError(e.to_string()))?;
 			}
 
+			if self.annotation_slot_bytes > 0 {
+				self.write_annotation_slots(&mut file)?;
+			}
+
 			file.flush()
 				.map_err(|e| BdfWriterError::WriteError(e.to_string()))?;
 		}
@@ -250,31 +357,35 @@ impl BdfWriter {
 	}
 }
 
-fn write_ascii_field(buf: &mut [u8], value: &str) {
-	buf.fill(b' ');
-	let bytes = value.as_bytes();
-	let len = bytes.len().min(buf.len());
-	buf[..len].copy_from_slice(&bytes[..len]);
+impl BdfWriter {
+	/// 回填每个记录末尾的注释槽
+	fn write_annotation_slots(&self, file: &mut BufWriter<File>) -> Result<(), BdfWriterError> {
+		let record_onsets: Vec<f64> = (0..self.num_records).map(|record| record as f64).collect();
+		let slots = pack_annotation_records(
+			&self.annotations,
+			&record_onsets,
+			self.annotation_slot_bytes,
+		)
+		.ok_or_else(|| BdfWriterError::WriteError("注释超出注释通道容量".to_string()))?;
+
+		for (record, slot) in slots.iter().enumerate() {
+			let offset = self.header_size + (record + 1) * self.record_size - slot.len();
+			file.seek(SeekFrom::Start(offset as u64))
+				.map_err(|e| BdfWriterError::WriteError(e.to_string()))?;
+			file.write_all(slot)
+				.map_err(|e| BdfWriterError::WriteError(e.to_string()))?;
+		}
+		Ok(())
+	}
 }
 
-fn write_signal_field<F>(
-	header: &mut [u8],
-	mut offset: usize,
-	field_width: usize,
-	signals: &[BdfSignalParam],
-	formatter: F,
-) -> usize
-where
-	F: Fn(&BdfSignalParam) -> String,
-{
-	for signal in signals {
-		write_ascii_field(
-			&mut header[offset..offset + field_width],
-			&formatter(signal),
-		);
-		offset += field_width;
+/// 注释通道的传感器与预滤波字段留空
+fn annotation_blank(signal: &BdfSignalParam, value: &str) -> String {
+	if signal.label == ANNOTATION_LABEL {
+		String::new()
+	} else {
+		value.to_string()
 	}
-	offset
 }
 
 /// 生成测试 BDF 文件
@@ -318,24 +429,17 @@ pub fn generate_test_bdf(
 
 	eprintln!("写入 {} 个数据记录...", record_count);
 
-	let mut rng = rand::rng();
-	let base_freq = 10.0; // 基础频率 10 Hz
+	let mut simulator = EegSimulator::new(EegSimulationConfig::for_recording(
+		sample_rate as f32,
+		channel_count,
+	))?;
 
 	for record in 0..record_count {
-		let mut channel_data: Vec<Vec<f64>> =
-			vec![Vec::with_capacity(samples_per_record); channel_count];
-
-		for sample_idx in 0..samples_per_record {
-			let t = (record * samples_per_record + sample_idx) as f64 / sample_rate as f64;
-
-			for channel_samples in channel_data.iter_mut().take(channel_count) {
-				// 生成模拟 EEG 信号：正弦波 + 噪声
-				let signal_val = (t * base_freq * 2.0 * std::f64::consts::PI).sin() * 50.0;
-				let noise: f64 = (rng.random::<f64>() - 0.5) * 20.0;
-				let value = signal_val + noise;
-				channel_samples.push(value);
-			}
-		}
+		let channel_data: Vec<Vec<f64>> = simulator
+			.generate(samples_per_record)
+			.into_iter()
+			.map(|samples| samples.into_iter().map(f64::from).collect())
+			.collect();
 
 		writer.write_samples(&channel_data)?;
 
@@ -400,6 +504,70 @@ mod tests {
 		assert!(data[305..368].iter().all(|byte| *byte == b' '));
 	}
 
+	#[test]
+	fn annotations_round_trip_through_bdf_plus() {
+		let path = std::env::temp_dir().join("codex_bdf_annotation_test.bdf");
+		let mut writer =
+			BdfWriter::create_with_annotations(&path, test_signals(), 4, 8, 60).unwrap();
+		writer
+			.add_annotation(Annotation::new(1.25, "stimulus 7"))
+			.unwrap();
+		writer
+			.add_annotation(Annotation::new(0.0, "Sleep stage W").with_duration(30.0))
+			.unwrap();
+		for record in 0..2 {
+			let value = record as f64 * 10.0;
+			writer
+				.write_samples(&[vec![value; 4], vec![-value; 4]])
+				.unwrap();
+		}
+		assert!(writer.write_samples(&[vec![0.0; 3], vec![0.0; 4]]).is_err());
+		writer.finalize().unwrap();
+
+		let path_text = path.to_string_lossy().to_string();
+		let loader = crate::EdfLoader::from_file(&path_text).unwrap();
+		std::fs::remove_file(&path).ok();
+
+		assert_eq!(loader.channel_count(), 2);
+		assert_eq!(loader.labels(), ["EEG CH0", "EEG CH1"]);
+		assert!((loader.channels()[1][4] + 10.0).abs() < 1e-3);
+		assert_eq!(
+			loader.header().continuity,
+			Some(crate::DataContinuity::Continuous)
+		);
+		assert_eq!(
+			loader.annotations(),
+			[
+				Annotation::new(0.0, "Sleep stage W").with_duration(30.0),
+				Annotation::new(1.25, "stimulus 7"),
+			]
+		);
+	}
+
+	#[test]
+	fn rejects_channel_length_not_matching_its_sample_rate() {
+		let path = std::env::temp_dir().join("codex_bdf_mixed_rate_length_test.bdf");
+		let mut signals = test_signals();
+		signals[1].sample_rate = 2;
+		let mut writer = BdfWriter::create(&path, signals, 4, 8).unwrap();
+
+		let result = writer.write_samples(&[vec![0.0; 4], vec![0.0; 4]]);
+		writer.write_samples(&[vec![0.0; 4], vec![0.0; 2]]).unwrap();
+		writer.finalize().unwrap();
+		let metadata = fs::metadata(&path).unwrap();
+		fs::remove_file(&path).ok();
+
+		assert!(matches!(
+			result,
+			Err(BdfWriterError::DataSizeMismatch {
+				expected: 2,
+				actual: 4
+			})
+		));
+		// 2 个记录，每记录 (4 + 2) 个 24-bit 样本 => 768 + 2 * 18
+		assert_eq!(metadata.len(), 804);
+	}
+
 	#[test]
 	fn pads_file_to_full_record_size() {
 		let path = std::env::temp_dir().join("codex_bdf_size_test.bdf");
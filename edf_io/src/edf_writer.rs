@@ -0,0 +1,741 @@
+//! 原生 EDF+ (16 位) 文件写入器
+//!
+//! 按数据记录流式写入，记录数在 `finalize` 时回写到文件头；
+//! 未调用 `finalize` 时文件头中的记录数保持 -1，读取端按文件长度推算
+
+use crate::annotation::{Annotation, annotation_slot_bytes, pack_annotation_records};
+use crate::header::{
+	DataContinuity, EdfStartTime, SignalHeader, write_ascii_field, write_signal_field,
+};
+use std::fs::File;
+use std::io::{BufWriter, Seek, SeekFrom, Write};
+use std::path::Path;
+use thiserror::Error;
+
+/// EDF+ 注释通道标签
+const ANNOTATION_LABEL: &str = "EDF Annotations";
+/// 头部数值字段的宽度
+const NUMBER_FIELD_WIDTH: usize = 8;
+/// 判断记录时刻是否重叠时允许的浮点误差（秒）
+const ONSET_TOLERANCE_SECS: f64 = 1e-9;
+/// 每个样本的字节数
+const BYTES_PER_SAMPLE: usize = 2;
+/// 文件头中记录数字段的偏移
+const RECORD_COUNT_OFFSET: u64 = 236;
+/// EDF+ 日期中的月份缩写
+const MONTHS: [&str; 12] = [
+	"JAN", "FEB", "MAR", "APR", "MAY", "JUN", "JUL", "AUG", "SEP", "OCT", "NOV", "DEC",
+];
+
+/// EDF 文件写入错误
+#[derive(Error, Debug)]
+pub enum EdfWriterError {
+	#[error("文件创建失败: {0}")]
+	FileCreateError(String),
+
+	#[error("写入失败: {0}")]
+	WriteError(String),
+
+	#[error("参数无效: {0}")]
+	InvalidParameter(String),
+
+	#[error("数据大小不匹配: 期望 {expected}, 实际 {actual}")]
+	DataSizeMismatch { expected: usize, actual: usize },
+}
+
+/// EDF 信号参数
+#[derive(Debug, Clone)]
+pub struct EdfSignalParam {
+	/// 信号标签 (如 "EEG Fp1")
+	pub label: String,
+	/// 传感器类型 (如 "AgAgCl electrode")
+	pub transducer: String,
+	/// 物理单位
+	pub physical_dimension: String,
+	/// 物理最小值
+	pub physical_min: f64,
+	/// 物理最大值
+	pub physical_max: f64,
+	/// 数字最小值 (16-bit: -32768)
+	pub digital_min: i32,
+	/// 数字最大值 (16-bit: 32767)
+	pub digital_max: i32,
+	/// 预滤波描述 (如 "HP:0.1Hz LP:75Hz")
+	pub prefilter: String,
+	/// 采样率 (Hz)，乘以记录时长必须为正整数
+	pub sample_rate: f64,
+}
+
+/// EDF+ 文件级参数
+#[derive(Debug, Clone)]
+pub struct EdfWriterOptions {
+	/// 患者标识 "代码 性别 出生日期 姓名"，未知子字段写 "X"
+	pub patient: String,
+	/// 记录标识中 "Startdate 日期" 之后的部分 "检查号 技师 设备"
+	pub recording: String,
+	/// 记录开始时刻
+	pub start: EdfStartTime,
+	/// 单个数据记录的时长（秒）
+	pub record_duration_secs: f64,
+	/// 写入 `EDF+C` 还是 `EDF+D`
+	pub continuity: DataContinuity,
+	/// 每个记录的注释槽字节数，向上取整到 2 的倍数
+	pub annotation_bytes_per_record: usize,
+}
+
+impl Default for EdfWriterOptions {
+	fn default() -> Self {
+		Self {
+			patient: "X X X X".to_string(),
+			recording: "X X X".to_string(),
+			start: EdfStartTime {
+				year: 2000,
+				month: 1,
+				day: 1,
+				hour: 0,
+				minute: 0,
+				second: 0,
+			},
+			record_duration_secs: 1.0,
+			continuity: DataContinuity::Continuous,
+			annotation_bytes_per_record: 60,
+		}
+	}
+}
+
+/// `finalize` 的写入结果
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EdfWriteSummary {
+	/// 写入的数据记录数量
+	pub record_count: usize,
+	/// 各通道超出物理范围而被截断的样本数
+	pub clipped_samples: Vec<usize>,
+}
+
+impl EdfWriteSummary {
+	/// 全部通道被截断的样本总数
+	pub fn total_clipped(&self) -> usize {
+		self.clipped_samples.iter().sum()
+	}
+}
+
+/// EDF+ 文件写入器
+pub struct EdfWriter {
+	/// 全部信号头，最后一个为注释通道
+	signals: Vec<SignalHeader>,
+	/// 数据通道数量
+	channel_count: usize,
+	/// 文件级参数
+	options: EdfWriterOptions,
+	/// 文件头大小
+	header_size: usize,
+	/// 数据记录大小 (字节)
+	record_size: usize,
+	/// 每个记录中注释通道的字节数
+	annotation_slot_bytes: usize,
+	/// 已写入记录的起始时刻（秒）
+	record_onsets: Vec<f64>,
+	/// 当前连续段的起始时刻（秒），EDF+C 中始终为 0
+	segment_onset_secs: f64,
+	/// 当前连续段中已写入的记录数
+	segment_records: usize,
+	/// 待写入的注释
+	annotations: Vec<Annotation>,
+	/// 各通道被截断的样本数
+	clipped_samples: Vec<usize>,
+	/// 文件
+	file: Option<BufWriter<File>>,
+}
+
+impl EdfWriter {
+	/// 创建新的 EDF+ 文件并写入文件头
+	///
+	/// # Arguments
+	/// * `path` - 输出文件路径
+	/// * `signals` - 信号参数列表，各信号的采样率可以互不相同
+	/// * `options` - 患者、记录、开始时刻与记录时长等文件级参数
+	pub fn create(
+		path: &Path,
+		signals: Vec<EdfSignalParam>,
+		options: EdfWriterOptions,
+	) -> Result<Self, EdfWriterError> {
+		let record_duration = options.record_duration_secs;
+		if !record_duration.is_finite() || record_duration <= 0.0 {
+			return Err(EdfWriterError::InvalidParameter(format!(
+				"记录时长必须为正数: {record_duration}"
+			)));
+		}
+		check_number_field(record_duration, "记录时长")?;
+		if !(1..=12).contains(&options.start.month) {
+			return Err(EdfWriterError::InvalidParameter(format!(
+				"开始月份无效: {}",
+				options.start.month
+			)));
+		}
+
+		let channel_count = signals.len();
+		let mut headers = signals
+			.into_iter()
+			.map(|signal| signal_header(signal, record_duration))
+			.collect::<Result<Vec<_>, _>>()?;
+
+		let annotation_slot_bytes =
+			annotation_slot_bytes(options.annotation_bytes_per_record, BYTES_PER_SAMPLE);
+		headers.push(SignalHeader {
+			label: ANNOTATION_LABEL.to_string(),
+			transducer: String::new(),
+			physical_dimension: String::new(),
+			physical_min: -1.0,
+			physical_max: 1.0,
+			digital_min: i16::MIN as i32,
+			digital_max: i16::MAX as i32,
+			prefilter: String::new(),
+			samples_per_record: annotation_slot_bytes / BYTES_PER_SAMPLE,
+			reserved: String::new(),
+		});
+
+		let header_size = 256 + (256 * headers.len());
+		let record_size = headers
+			.iter()
+			.map(|signal| signal.samples_per_record * BYTES_PER_SAMPLE)
+			.sum();
+
+		let file =
+			File::create(path).map_err(|e| EdfWriterError::FileCreateError(e.to_string()))?;
+
+		let mut writer = Self {
+			signals: headers,
+			channel_count,
+			options,
+			header_size,
+			record_size,
+			annotation_slot_bytes,
+			record_onsets: Vec::new(),
+			segment_onset_secs: 0.0,
+			segment_records: 0,
+			annotations: Vec::new(),
+			clipped_samples: vec![0; channel_count],
+			file: Some(BufWriter::new(file)),
+		};
+
+		writer.write_header()?;
+
+		Ok(writer)
+	}
+
+	/// 写入 EDF+ 头信息，记录数先写为 -1
+	fn write_header(&mut self) -> Result<(), EdfWriterError> {
+		let file = self
+			.file
+			.as_mut()
+			.ok_or_else(|| EdfWriterError::WriteError("File not opened".to_string()))?;
+
+		let start = self.options.start;
+		let mut header = vec![0u8; self.header_size];
+		// ===== 全局头 (256 字节) =====
+		// 0-8 字节: 版本 "0"
+		write_ascii_field(&mut header[0..8], "0");
+
+		// 8-88 字节: 患者信息 (80 字节)
+		write_ascii_field(&mut header[8..88], &self.options.patient);
+
+		// 88-168 字节: 记录信息 (80 字节)，EDF+ 以 "Startdate dd-MMM-yyyy" 开头
+		let recording = format!(
+			"Startdate {:02}-{}-{:04} {}",
+			start.day,
+			MONTHS[usize::from(start.month - 1)],
+			start.year,
+			self.options.recording
+		);
+		write_ascii_field(&mut header[88..168], &recording);
+
+		// 168-176 字节: 开始日期 "dd.mm.yy"
+		let start_date = format!(
+			"{:02}.{:02}.{:02}",
+			start.day,
+			start.month,
+			start.year % 100
+		);
+		write_ascii_field(&mut header[168..176], &start_date);
+
+		// 176-184 字节: 开始时间 "hh.mm.ss"
+		let start_time = format!("{:02}.{:02}.{:02}", start.hour, start.minute, start.second);
+		write_ascii_field(&mut header[176..184], &start_time);
+
+		// 184-192 字节: 头部长度
+		write_ascii_field(&mut header[184..192], &self.header_size.to_string());
+
+		// 192-236 字节: 保留字段，标记 EDF+ 的连续性
+		let reserved = match self.options.continuity {
+			DataContinuity::Continuous => "EDF+C",
+			DataContinuity::Discontinuous => "EDF+D",
+		};
+		write_ascii_field(&mut header[192..236], reserved);
+
+		// 236-244 字节: 数据记录数量，finalize 时回写
+		write_ascii_field(&mut header[236..244], "-1");
+
+		// 244-252 字节: 每条记录的持续时间（秒）
+		write_ascii_field(
+			&mut header[244..252],
+			&self.options.record_duration_secs.to_string(),
+		);
+
+		// 252-256 字节: 信号数量
+		write_ascii_field(&mut header[252..256], &self.signals.len().to_string());
+
+		// ===== 信号头，按字段分组依次写入所有通道 =====
+		let mut offset = 256;
+		offset = write_signal_field(&mut header, offset, 16, &self.signals, |signal| {
+			signal.label.clone()
+		});
+		offset = write_signal_field(&mut header, offset, 80, &self.signals, |signal| {
+			signal.transducer.clone()
+		});
+		offset = write_signal_field(&mut header, offset, 8, &self.signals, |signal| {
+			signal.physical_dimension.clone()
+		});
+		offset = write_signal_field(&mut header, offset, 8, &self.signals, |signal| {
+			signal.physical_min.to_string()
+		});
+		offset = write_signal_field(&mut header, offset, 8, &self.signals, |signal| {
+			signal.physical_max.to_string()
+		});
+		offset = write_signal_field(&mut header, offset, 8, &self.signals, |signal| {
+			signal.digital_min.to_string()
+		});
+		offset = write_signal_field(&mut header, offset, 8, &self.signals, |signal| {
+			signal.digital_max.to_string()
+		});
+		offset = write_signal_field(&mut header, offset, 80, &self.signals, |signal| {
+			signal.prefilter.clone()
+		});
+		offset = write_signal_field(&mut header, offset, 8, &self.signals, |signal| {
+			signal.samples_per_record.to_string()
+		});
+		let _ = write_signal_field(&mut header, offset, 32, &self.signals, |signal| {
+			signal.reserved.clone()
+		});
+
+		file.write_all(&header)
+			.map_err(|e| EdfWriterError::WriteError(e.to_string()))?;
+
+		Ok(())
+	}
+
+	/// 紧接上一个记录写入一个数据记录
+	///
+	/// # Arguments
+	/// * `data` - 数据，格式为 [channel][samples]，每个通道恰好包含一个记录的样本，
+	///   即采样率乘以记录时长个物理值
+	pub fn write_samples(&mut self, data: &[Vec<f64>]) -> Result<(), EdfWriterError> {
+		self.write_record(self.next_contiguous_onset(), data)?;
+		self.segment_records += 1;
+		Ok(())
+	}
+
+	/// 在指定时刻写入一个数据记录
+	///
+	/// EDF+D 的任意记录都可指定起始时刻；EDF+C 只允许第一个记录这样写入，
+	/// 用于表示文件头无法记录的亚秒级开始时刻
+	///
+	/// # Arguments
+	/// * `onset_secs` - 记录相对文件开始时刻的起始时间（秒），不得早于上一个记录的结束
+	/// * `data` - 数据，格式同 `write_samples`
+	pub fn write_record_at(
+		&mut self,
+		onset_secs: f64,
+		data: &[Vec<f64>],
+	) -> Result<(), EdfWriterError> {
+		if self.options.continuity != DataContinuity::Discontinuous
+			&& !self.record_onsets.is_empty()
+		{
+			return Err(EdfWriterError::InvalidParameter(
+				"EDF+C 的记录必须连续，只有第一个记录可以指定起始时刻".to_string(),
+			));
+		}
+		let earliest = self.next_contiguous_onset();
+		if !onset_secs.is_finite() || onset_secs < earliest - ONSET_TOLERANCE_SECS {
+			return Err(EdfWriterError::InvalidParameter(format!(
+				"记录起始时刻 {onset_secs} 早于上一个记录的结束 {earliest}"
+			)));
+		}
+		self.write_record(onset_secs, data)?;
+		self.segment_onset_secs = onset_secs;
+		self.segment_records = 1;
+		Ok(())
+	}
+
+	/// 紧接上一个记录的起始时刻，按段内记录序号相乘计算，避免逐条累加的浮点误差
+	fn next_contiguous_onset(&self) -> f64 {
+		self.segment_onset_secs + self.segment_records as f64 * self.options.record_duration_secs
+	}
+
+	fn write_record(&mut self, onset_secs: f64, data: &[Vec<f64>]) -> Result<(), EdfWriterError> {
+		if data.len() != self.channel_count {
+			return Err(EdfWriterError::DataSizeMismatch {
+				expected: self.channel_count,
+				actual: data.len(),
+			});
+		}
+		for (channel_samples, signal) in data.iter().zip(&self.signals) {
+			if channel_samples.len() != signal.samples_per_record {
+				return Err(EdfWriterError::DataSizeMismatch {
+					expected: signal.samples_per_record,
+					actual: channel_samples.len(),
+				});
+			}
+		}
+
+		let file = self
+			.file
+			.as_mut()
+			.ok_or_else(|| EdfWriterError::WriteError("File not opened".to_string()))?;
+
+		let mut record = Vec::with_capacity(self.record_size);
+		for ((channel_samples, signal), clipped) in data
+			.iter()
+			.zip(&self.signals)
+			.zip(self.clipped_samples.iter_mut())
+		{
+			for value in channel_samples {
+				let (digital_value, was_clipped) = to_digital(signal, *value);
+				if was_clipped {
+					*clipped += 1;
+				}
+				record.extend_from_slice(&digital_value.to_le_bytes());
+			}
+		}
+
+		// 先写入只含计时注释的槽，使未 finalize 的文件也是合法的 EDF+
+		let slots = pack_annotation_records(&[], &[onset_secs], self.annotation_slot_bytes)
+			.ok_or_else(|| EdfWriterError::WriteError("计时注释超出注释通道容量".to_string()))?;
+		for slot in &slots {
+			record.extend_from_slice(slot);
+		}
+
+		file.write_all(&record)
+			.map_err(|e| EdfWriterError::WriteError(e.to_string()))?;
+
+		self.record_onsets.push(onset_secs);
+		Ok(())
+	}
+
+	/// 添加一条注释，在 `finalize` 时写入其起始时刻所在记录的注释槽
+	pub fn add_annotation(&mut self, annotation: Annotation) {
+		self.annotations.push(annotation);
+	}
+
+	/// 获取已写入的数据记录数量
+	pub fn record_count(&self) -> usize {
+		self.record_onsets.len()
+	}
+
+	/// 获取各通道目前为止被截断的样本数
+	pub fn clipped_samples(&self) -> &[usize] {
+		&self.clipped_samples
+	}
+
+	/// 回填注释槽与记录数并关闭文件
+	pub fn finalize(mut self) -> Result<EdfWriteSummary, EdfWriterError> {
+		if let Some(mut file) = self.file.take() {
+			if !self.annotations.is_empty() {
+				self.write_annotation_slots(&mut file)?;
+			}
+
+			file.seek(SeekFrom::Start(RECORD_COUNT_OFFSET))
+				.map_err(|e| EdfWriterError::WriteError(e.to_string()))?;
+			let mut field = [0u8; 8];
+			write_ascii_field(&mut field, &self.record_onsets.len().to_string());
+			file.write_all(&field)
+				.map_err(|e| EdfWriterError::WriteError(e.to_string()))?;
+
+			file.flush()
+				.map_err(|e| EdfWriterError::WriteError(e.to_string()))?;
+		}
+
+		Ok(EdfWriteSummary {
+			record_count: self.record_onsets.len(),
+			clipped_samples: self.clipped_samples,
+		})
+	}
+
+	/// 回填每个记录末尾的注释槽
+	fn write_annotation_slots(&self, file: &mut BufWriter<File>) -> Result<(), EdfWriterError> {
+		if self.record_onsets.is_empty() {
+			return Err(EdfWriterError::WriteError(
+				"没有数据记录，无法写入注释".to_string(),
+			));
+		}
+		let slots = pack_annotation_records(
+			&self.annotations,
+			&self.record_onsets,
+			self.annotation_slot_bytes,
+		)
+		.ok_or_else(|| EdfWriterError::WriteError("注释超出注释通道容量".to_string()))?;
+
+		for (record, slot) in slots.iter().enumerate() {
+			let offset = self.header_size + (record + 1) * self.record_size - slot.len();
+			file.seek(SeekFrom::Start(offset as u64))
+				.map_err(|e| EdfWriterError::WriteError(e.to_string()))?;
+			file.write_all(slot)
+				.map_err(|e| EdfWriterError::WriteError(e.to_string()))?;
+		}
+		Ok(())
+	}
+}
+
+/// 校验信号参数并换算为每记录采样数
+fn signal_header(
+	signal: EdfSignalParam,
+	record_duration_secs: f64,
+) -> Result<SignalHeader, EdfWriterError> {
+	let digital_range = i16::MIN as i32..=i16::MAX as i32;
+	if !digital_range.contains(&signal.digital_min)
+		|| !digital_range.contains(&signal.digital_max)
+		|| signal.digital_min >= signal.digital_max
+	{
+		return Err(EdfWriterError::InvalidParameter(format!(
+			"{} 的数字范围无效: {}..{}",
+			signal.label, signal.digital_min, signal.digital_max
+		)));
+	}
+	if !signal.physical_min.is_finite()
+		|| !signal.physical_max.is_finite()
+		|| signal.physical_min == signal.physical_max
+	{
+		return Err(EdfWriterError::InvalidParameter(format!(
+			"{} 的物理范围无效: {}..{}",
+			signal.label, signal.physical_min, signal.physical_max
+		)));
+	}
+
+	check_number_field(signal.physical_min, "物理最小值")?;
+	check_number_field(signal.physical_max, "物理最大值")?;
+
+	let samples = signal.sample_rate * record_duration_secs;
+	let samples_per_record = samples.round();
+	if !samples.is_finite()
+		|| samples_per_record < 1.0
+		|| (samples - samples_per_record).abs() > 1e-6
+	{
+		return Err(EdfWriterError::InvalidParameter(format!(
+			"{} 的采样率 {} Hz 乘以记录时长 {} 秒不是正整数",
+			signal.label, signal.sample_rate, record_duration_secs
+		)));
+	}
+
+	Ok(SignalHeader {
+		label: signal.label,
+		transducer: signal.transducer,
+		physical_dimension: signal.physical_dimension,
+		physical_min: signal.physical_min,
+		physical_max: signal.physical_max,
+		digital_min: signal.digital_min,
+		digital_max: signal.digital_max,
+		prefilter: signal.prefilter,
+		samples_per_record: samples_per_record as usize,
+		reserved: String::new(),
+	})
+}
+
+/// 物理值换算为 16 位数字值，返回值及是否超出物理范围
+///
+/// 物理最小值可以大于最大值（反相信号），NaN 视为截断
+fn to_digital(signal: &SignalHeader, value: f64) -> (i16, bool) {
+	let lower = signal.physical_min.min(signal.physical_max);
+	let upper = signal.physical_min.max(signal.physical_max);
+	let clipped = !(lower..=upper).contains(&value);
+
+	let gain = (signal.digital_max - signal.digital_min) as f64
+		/ (signal.physical_max - signal.physical_min);
+	let digital = ((value - signal.physical_min) * gain + signal.digital_min as f64).round();
+	let digital = if digital.is_nan() { 0.0 } else { digital };
+	let digital = digital.clamp(signal.digital_min as f64, signal.digital_max as f64);
+	(digital as i16, clipped)
+}
+
+/// 检查数值能否原样写入 8 字符的头字段，避免截断后头部与数据定标不一致
+fn check_number_field(value: f64, field_name: &str) -> Result<(), EdfWriterError> {
+	let text = value.to_string();
+	if text.len() > NUMBER_FIELD_WIDTH {
+		return Err(EdfWriterError::InvalidParameter(format!(
+			"{field_name} {text} 超过 {NUMBER_FIELD_WIDTH} 个字符，无法写入文件头"
+		)));
+	}
+	Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::EdfFileReader;
+	use std::fs;
+
+	fn test_signal(label: &str, physical_range: (f64, f64), sample_rate: f64) -> EdfSignalParam {
+		EdfSignalParam {
+			label: label.to_string(),
+			transducer: "AgAgCl electrode".to_string(),
+			physical_dimension: "uV".to_string(),
+			physical_min: physical_range.0,
+			physical_max: physical_range.1,
+			digital_min: -32768,
+			digital_max: 32767,
+			prefilter: "HP:0.1Hz LP:70Hz".to_string(),
+			sample_rate,
+		}
+	}
+
+	#[test]
+	fn writes_mixed_rate_edf_plus_c_with_clipping_stats() {
+		let path = std::env::temp_dir().join("edf_io_native_writer_continuous.edf");
+		let options = EdfWriterOptions {
+			patient: "MCH-0234567 F 02-MAY-1951 Haagse_Harry".to_string(),
+			recording: "PSG-1234/2002 NN Telemetry03".to_string(),
+			start: EdfStartTime {
+				year: 2026,
+				month: 10,
+				day: 18,
+				hour: 9,
+				minute: 30,
+				second: 5,
+			},
+			record_duration_secs: 0.5,
+			..EdfWriterOptions::default()
+		};
+		let signals = vec![
+			test_signal("EEG Fz", (-100.0, 100.0), 8.0),
+			test_signal("Resp", (0.0, 10.0), 2.0),
+		];
+		let mut writer = EdfWriter::create(&path, signals, options).unwrap();
+		writer
+			.write_samples(&[vec![0.0, 25.0, 50.0, 150.0], vec![1.0]])
+			.unwrap();
+		writer
+			.write_samples(&[vec![-25.0, -50.0, -120.0, 99.5], vec![9.0]])
+			.unwrap();
+		assert!(matches!(
+			writer.write_samples(&[vec![0.0; 3], vec![1.0]]),
+			Err(EdfWriterError::DataSizeMismatch {
+				expected: 4,
+				actual: 3
+			})
+		));
+		assert!(
+			writer
+				.write_record_at(5.0, &[vec![0.0; 4], vec![1.0]])
+				.is_err()
+		);
+		writer.add_annotation(Annotation::new(0.7, "Lights off").with_duration(0.2));
+		let summary = writer.finalize().unwrap();
+
+		let mut reader = EdfFileReader::open(path.to_str().unwrap()).unwrap();
+		let header = reader.header().clone();
+		let fz = reader.read_channel(0).unwrap();
+		let resp = reader.read_channel(1).unwrap();
+		let annotations = reader.read_annotations().unwrap();
+		fs::remove_file(&path).ok();
+
+		assert_eq!(summary.record_count, 2);
+		assert_eq!(summary.clipped_samples, [2, 0]);
+		assert_eq!(summary.total_clipped(), 2);
+		assert!(!header.is_bdf);
+		assert_eq!(header.continuity, Some(DataContinuity::Continuous));
+		assert_eq!(header.record_count, Some(2));
+		assert_eq!(header.record_duration_secs, 0.5);
+		assert_eq!(header.patient.name.as_deref(), Some("Haagse Harry"));
+		assert_eq!(header.recording.start_date.as_deref(), Some("18-OCT-2026"));
+		assert_eq!(header.start_time, "09.30.05");
+		assert_eq!(reader.channel_sample_rates(), [8.0, 2.0]);
+		let expected_fz = [0.0, 25.0, 50.0, 100.0, -25.0, -50.0, -100.0, 99.5];
+		assert!(
+			fz.iter()
+				.zip(expected_fz)
+				.all(|(actual, expected)| (*actual - expected).abs() < 0.01)
+		);
+		assert!((resp[0] - 1.0).abs() < 1e-3 && (resp[1] - 9.0).abs() < 1e-3);
+		assert_eq!(
+			annotations,
+			[Annotation::new(0.7, "Lights off").with_duration(0.2)]
+		);
+	}
+
+	#[test]
+	fn writes_edf_plus_d_record_onsets_into_time_keeping_tals() {
+		let path = std::env::temp_dir().join("edf_io_native_writer_discontinuous.edf");
+		let options = EdfWriterOptions {
+			continuity: DataContinuity::Discontinuous,
+			..EdfWriterOptions::default()
+		};
+		let mut writer = EdfWriter::create(
+			&path,
+			vec![test_signal("EEG Cz", (-50.0, 50.0), 4.0)],
+			options,
+		)
+		.unwrap();
+		writer.write_samples(&[vec![1.0; 4]]).unwrap();
+		writer.write_record_at(5.0, &[vec![2.0; 4]]).unwrap();
+		assert!(writer.write_record_at(5.5, &[vec![3.0; 4]]).is_err());
+		writer.add_annotation(Annotation::new(5.25, "Arousal"));
+		let summary = writer.finalize().unwrap();
+		let bytes = fs::read(&path).unwrap();
+		let mut reader = EdfFileReader::open(path.to_str().unwrap()).unwrap();
+		let annotations = reader.read_annotations().unwrap();
+		fs::remove_file(&path).ok();
+
+		let second_slot = &bytes[bytes.len() - 60..];
+		assert_eq!(summary.record_count, 2);
+		assert_eq!(
+			reader.header().continuity,
+			Some(DataContinuity::Discontinuous)
+		);
+		assert_eq!(reader.record_count(), 2);
+		assert!(second_slot.starts_with(b"+5\x14\x14\x00+5.25\x14Arousal\x14\x00"));
+		assert_eq!(annotations, [Annotation::new(5.25, "Arousal")]);
+		assert!(
+			EdfWriter::create(
+				&path,
+				vec![test_signal("EEG Cz", (-50.0, 50.0), 2.5)],
+				EdfWriterOptions::default()
+			)
+			.is_err()
+		);
+	}
+
+	#[test]
+	fn short_records_keep_time_keeping_tals_within_minimum_slot() {
+		let path = std::env::temp_dir().join("edf_io_native_writer_short_records.edf");
+		let options = EdfWriterOptions {
+			record_duration_secs: 0.1,
+			annotation_bytes_per_record: 16,
+			..EdfWriterOptions::default()
+		};
+		let mut writer = EdfWriter::create(
+			&path,
+			vec![test_signal("EEG Oz", (-50.0, 50.0), 40.0)],
+			options,
+		)
+		.unwrap();
+		for _ in 0..12 {
+			writer.write_samples(&[vec![0.0; 4]]).unwrap();
+		}
+		writer.finalize().unwrap();
+		let bytes = fs::read(&path).unwrap();
+		fs::remove_file(&path).ok();
+
+		let record_size = 4 * 2 + 16;
+		let data = &bytes[bytes.len() - 12 * record_size..];
+		let slot = |record: usize| &data[record * record_size + 8..(record + 1) * record_size];
+		assert!(slot(3).starts_with(b"+0.3\x14\x14\x00"));
+		assert!(slot(11).starts_with(b"+1.1\x14\x14\x00"));
+
+		let oversized = EdfSignalParam {
+			physical_max: 123_456_789.0,
+			..test_signal("EEG Oz", (-50.0, 50.0), 40.0)
+		};
+		assert!(matches!(
+			EdfWriter::create(&path, vec![oversized], EdfWriterOptions::default()),
+			Err(EdfWriterError::InvalidParameter(_))
+		));
+	}
+}
@@ -2,8 +2,9 @@
 //!
 //! 生成 EDF+ 格式的测试数据文件
 
+use crate::annotation::Annotation;
 use edfplus::{EdfWriter, SignalParam};
-use rand::Rng;
+use eeg_simulator::{EegSimulationConfig, EegSimulator};
 use std::fs::OpenOptions;
 use std::io::{Read, Seek, SeekFrom, Write};
 use std::path::Path;
@@ -21,6 +22,9 @@ pub enum GeneratorError {
 
 	#[error("EDF+ 错误: {0}")]
 	EdfError(String),
+
+	#[error("仿真信号生成失败: {0}")]
+	SimulationError(String),
 }
 
 impl From<edfplus::EdfError> for GeneratorError {
@@ -29,6 +33,12 @@ impl From<edfplus::EdfError> for GeneratorError {
 	}
 }
 
+impl From<eeg_simulator::SimulationError> for GeneratorError {
+	fn from(err: eeg_simulator::SimulationError) -> Self {
+		GeneratorError::SimulationError(err.to_string())
+	}
+}
+
 /// 测试 EDF+ 数据生成器
 ///
 /// 生成模拟 EEG 数据的 EDF+ 测试文件
@@ -39,6 +49,8 @@ pub struct TestEdfGenerator {
 	sample_rate: u32,
 	/// 时长 (秒)
 	duration_secs: u32,
+	/// 写入 EDF+ 注释通道的注释
+	annotations: Vec<Annotation>,
 }
 
 impl TestEdfGenerator {
@@ -53,9 +65,19 @@ impl TestEdfGenerator {
 			channel_count,
 			sample_rate,
 			duration_secs,
+			annotations: Vec::new(),
 		}
 	}
 
+	/// 设置要写入的 EDF+ 注释，例如刺激代码或睡眠分期
+	///
+	/// # Arguments
+	/// * `annotations` - 注释列表，起始时刻相对文件开始
+	pub fn with_annotations(mut self, annotations: Vec<Annotation>) -> Self {
+		self.annotations = annotations;
+		self
+	}
+
 	/// 生成测试 EDF+ 文件
 	///
 	/// # Arguments
@@ -94,30 +116,32 @@ impl TestEdfGenerator {
 			writer.add_signal(signal)?;
 		}
 
+		// edfplus 只保存写入对应时刻的样本之前添加的注释，必须先于数据记录添加
+		for annotation in &self.annotations {
+			writer.add_annotation(
+				annotation.onset_secs,
+				annotation.duration_secs,
+				&annotation.text,
+			)?;
+		}
+
 		// 生成并写入数据
 		let samples_per_record = self.sample_rate as usize;
 		let record_count = samples_per_channel / samples_per_record;
 
 		eprintln!("写入 {} 个数据记录...", record_count);
 
-		let mut rng = rand::rng();
-		let base_freq = 10.0; // 基础频率 10 Hz
+		let mut simulator = EegSimulator::new(EegSimulationConfig::for_recording(
+			self.sample_rate as f32,
+			self.channel_count,
+		))?;
 
 		for record in 0..record_count {
-			let mut channel_data: Vec<Vec<f64>> =
-				vec![Vec::with_capacity(samples_per_record); self.channel_count];
-
-			for sample_idx in 0..samples_per_record {
-				let t = (record * samples_per_record + sample_idx) as f64 / self.sample_rate as f64;
-
-				for channel_samples in channel_data.iter_mut().take(self.channel_count) {
-					// 生成模拟 EEG 信号：正弦波 + 噪声
-					let signal_val = (t * base_freq * 2.0 * std::f64::consts::PI).sin() * 50.0;
-					let noise: f64 = (rng.random::<f64>() - 0.5) * 20.0;
-					let value = signal_val + noise;
-					channel_samples.push(value);
-				}
-			}
+			let channel_data: Vec<Vec<f64>> = simulator
+				.generate(samples_per_record)
+				.into_iter()
+				.map(|samples| samples.into_iter().map(f64::from).collect())
+				.collect();
 
 			// 写入这一秒的数据
 			writer.write_samples(&channel_data)?;
@@ -242,6 +266,7 @@ fn write_ascii_field(buf: &mut [u8], value: &str) {
 #[cfg(test)]
 mod tests {
 	use super::*;
+	use crate::EdfLoader;
 	use std::fs;
 
 	#[test]
@@ -264,6 +289,25 @@ mod tests {
 		assert_eq!(rewritten[260], b' ');
 	}
 
+	#[test]
+	fn generated_annotations_round_trip_through_loader() {
+		let path = std::env::temp_dir().join("edf_io_generator_annotations_test.edf");
+		let annotations = vec![
+			Annotation::new(1.5, "stim A"),
+			Annotation::new(2.25, "stim B").with_duration(0.5),
+		];
+
+		TestEdfGenerator::new(2, 64, 4)
+			.with_annotations(annotations.clone())
+			.generate(&path)
+			.unwrap();
+		let loader = EdfLoader::from_file(path.to_str().unwrap()).unwrap();
+		fs::remove_file(&path).ok();
+
+		assert_eq!(loader.annotations(), annotations.as_slice());
+		assert_eq!(loader.channel_count(), 2);
+	}
+
 	#[test]
 	fn normalize_recording_field_writes_edf_plus_compliant_date() {
 		let mut header = vec![b' '; 256];
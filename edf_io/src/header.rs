@@ -0,0 +1,421 @@
+//! EDF/BDF 文件头模型
+//!
+//! 按 EDF/EDF+ 规范解析全局头与逐信号头，EDF+ 的患者与记录标识拆分为子字段
+
+use crate::loader::EdfLoaderError;
+use std::fmt;
+
+/// EDF+ 中表示“未知”的子字段占位符
+const UNKNOWN_SUBFIELD: &str = "X";
+
+/// EDF+/BDF+ 数据记录是否连续
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DataContinuity {
+	/// `EDF+C` / `BDF+C`
+	Continuous,
+	/// `EDF+D` / `BDF+D`，记录之间可能存在间隔
+	Discontinuous,
+}
+
+/// 完整的 EDF/BDF 文件头
+#[derive(Debug, Clone, PartialEq)]
+pub struct EdfFileHeader {
+	/// 是否为 24 位 BDF
+	pub is_bdf: bool,
+	/// EDF+/BDF+ 的连续性，普通 EDF/BDF 为 None
+	pub continuity: Option<DataContinuity>,
+	/// 患者标识
+	pub patient: PatientIdentification,
+	/// 记录标识
+	pub recording: RecordingIdentification,
+	/// 原始开始日期 "dd.mm.yy"
+	pub start_date: String,
+	/// 原始开始时间 "hh.mm.ss"
+	pub start_time: String,
+	/// 解析后的开始时刻，字段不合法时为 None
+	pub start: Option<EdfStartTime>,
+	/// 文件头长度（字节）
+	pub header_bytes: usize,
+	/// 保留字段原文，EDF+ 中为 "EDF+C" 等
+	pub reserved: String,
+	/// 数据记录数量，-1（未知）时为 None
+	pub record_count: Option<usize>,
+	/// 单个数据记录的时长（秒）
+	pub record_duration_secs: f64,
+	/// 全部信号头，包含注释通道
+	pub signals: Vec<SignalHeader>,
+}
+
+/// 单个信号的头信息
+#[derive(Debug, Clone, PartialEq)]
+pub struct SignalHeader {
+	/// 信号标签 (如 "EEG Fp1")
+	pub label: String,
+	/// 传感器类型 (如 "AgAgCl electrode")
+	pub transducer: String,
+	/// 物理单位
+	pub physical_dimension: String,
+	/// 物理最小值
+	pub physical_min: f64,
+	/// 物理最大值
+	pub physical_max: f64,
+	/// 数字最小值
+	pub digital_min: i32,
+	/// 数字最大值
+	pub digital_max: i32,
+	/// 预滤波描述 (如 "HP:0.1Hz LP:75Hz")
+	pub prefilter: String,
+	/// 每个数据记录中的采样数
+	pub samples_per_record: usize,
+	/// 信号保留字段
+	pub reserved: String,
+}
+
+/// 患者标识，EDF+ 格式为 "代码 性别 出生日期 姓名 [附加信息]"
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PatientIdentification {
+	/// 原始 80 字节字段
+	pub raw: String,
+	/// 医院患者代码
+	pub code: Option<String>,
+	/// 性别，"F" 或 "M"
+	pub sex: Option<String>,
+	/// 出生日期 "dd-MMM-yyyy"
+	pub birthdate: Option<String>,
+	/// 姓名，下划线已还原为空格
+	pub name: Option<String>,
+	/// 其余子字段
+	pub additional: Vec<String>,
+}
+
+/// 记录标识，EDF+ 格式为 "Startdate 日期 检查号 技师 设备 [附加信息]"
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RecordingIdentification {
+	/// 原始 80 字节字段
+	pub raw: String,
+	/// 开始日期 "dd-MMM-yyyy"，包含四位年份
+	pub start_date: Option<String>,
+	/// 检查或管理编号
+	pub admin_code: Option<String>,
+	/// 技师
+	pub technician: Option<String>,
+	/// 采集设备
+	pub equipment: Option<String>,
+	/// 其余子字段
+	pub additional: Vec<String>,
+}
+
+/// 记录开始时刻
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct EdfStartTime {
+	pub year: u16,
+	pub month: u8,
+	pub day: u8,
+	pub hour: u8,
+	pub minute: u8,
+	pub second: u8,
+}
+
+impl fmt::Display for EdfStartTime {
+	fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+		write!(
+			formatter,
+			"{:04}-{:02}-{:02} {:02}:{:02}:{:02}",
+			self.year, self.month, self.day, self.hour, self.minute, self.second
+		)
+	}
+}
+
+impl EdfFileHeader {
+	/// 从完整的文件头字节解析
+	///
+	/// # Arguments
+	/// * `bytes` - 至少包含全局头与全部信号头的字节
+	pub fn parse(bytes: &[u8]) -> Result<Self, EdfLoaderError> {
+		if bytes.len() < 256 {
+			return Err(EdfLoaderError::InvalidFormat("头长度不足".to_string()));
+		}
+
+		let is_bdf = bytes[0] == 0xFF && &bytes[1..8] == b"BIOSEMI";
+		let header_bytes = parse_ascii_usize(&bytes[184..192], "头长度")?;
+		let signal_count = parse_ascii_usize(&bytes[252..256], "通道数")?;
+		if signal_count == 0 || header_bytes < 256 + 256 * signal_count {
+			return Err(EdfLoaderError::InvalidFormat("头字段不合法".to_string()));
+		}
+		if bytes.len() < header_bytes {
+			return Err(EdfLoaderError::InvalidFormat(
+				"信号头长度不完整".to_string(),
+			));
+		}
+
+		let reserved = parse_ascii_field(&bytes[192..236]);
+		let continuity = match reserved.get(3..5) {
+			Some("+C") => Some(DataContinuity::Continuous),
+			Some("+D") => Some(DataContinuity::Discontinuous),
+			_ => None,
+		};
+		let record_count = match parse_ascii_field(&bytes[236..244]).as_str() {
+			"-1" => None,
+			_ => Some(parse_ascii_usize(&bytes[236..244], "记录数")?),
+		};
+		let record_duration_secs = parse_ascii_f64(&bytes[244..252], "记录时长")?;
+
+		// 信号头按字段分组存放：先是所有信号的标签，再是所有信号的传感器类型……
+		let field = |offset_per_signal: usize, width: usize, index: usize| {
+			let start = 256 + offset_per_signal * signal_count + width * index;
+			&bytes[start..start + width]
+		};
+		let signals = (0..signal_count)
+			.map(|index| {
+				Ok(SignalHeader {
+					label: parse_ascii_field(field(0, 16, index)),
+					transducer: parse_ascii_field(field(16, 80, index)),
+					physical_dimension: parse_ascii_field(field(96, 8, index)),
+					physical_min: parse_ascii_f64(field(104, 8, index), "物理最小值")?,
+					physical_max: parse_ascii_f64(field(112, 8, index), "物理最大值")?,
+					digital_min: parse_ascii_i32(field(120, 8, index), "数字最小值")?,
+					digital_max: parse_ascii_i32(field(128, 8, index), "数字最大值")?,
+					prefilter: parse_ascii_field(field(136, 80, index)),
+					samples_per_record: parse_ascii_usize(field(216, 8, index), "每记录采样数")?,
+					reserved: parse_ascii_field(field(224, 32, index)),
+				})
+			})
+			.collect::<Result<Vec<_>, EdfLoaderError>>()?;
+
+		let patient = PatientIdentification::parse(&parse_ascii_field(&bytes[8..88]));
+		let recording = RecordingIdentification::parse(&parse_ascii_field(&bytes[88..168]));
+		let start_date = parse_ascii_field(&bytes[168..176]);
+		let start_time = parse_ascii_field(&bytes[176..184]);
+		let start = EdfStartTime::parse(&start_date, &start_time, recording.start_date.as_deref());
+
+		Ok(Self {
+			is_bdf,
+			continuity,
+			patient,
+			recording,
+			start_date,
+			start_time,
+			start,
+			header_bytes,
+			reserved,
+			record_count,
+			record_duration_secs,
+			signals,
+		})
+	}
+
+	/// 非注释信号各自的采样率 (Hz)，混合采样率文件中可以互不相同
+	pub fn data_sample_rates(&self) -> Vec<f64> {
+		self.data_signals()
+			.map(|signal| signal.sample_rate(self.record_duration_secs))
+			.collect()
+	}
+
+	/// 非注释信号的头信息，顺序与读取器的通道索引一致
+	pub fn data_signals(&self) -> impl Iterator<Item = &SignalHeader> {
+		self.signals.iter().filter(|signal| !signal.is_annotation())
+	}
+}
+
+impl SignalHeader {
+	/// 是否为 EDF+/BDF+ 注释通道
+	pub fn is_annotation(&self) -> bool {
+		self.label == "EDF Annotations" || self.label == "BDF Annotations"
+	}
+
+	/// 采样率，由每记录采样数除以记录时长得到
+	///
+	/// # Arguments
+	/// * `record_duration_secs` - 数据记录时长（秒），不大于 0 时视为 1 秒
+	pub fn sample_rate(&self, record_duration_secs: f64) -> f64 {
+		if record_duration_secs > 0.0 {
+			self.samples_per_record as f64 / record_duration_secs
+		} else {
+			self.samples_per_record as f64
+		}
+	}
+}
+
+impl PatientIdentification {
+	fn parse(raw: &str) -> Self {
+		let mut subfields = raw.split_whitespace();
+		let code = subfields.next().and_then(known_subfield);
+		let sex = subfields.next().and_then(known_subfield);
+		let birthdate = subfields.next().and_then(known_subfield);
+		let name = subfields
+			.next()
+			.and_then(known_subfield)
+			.map(|name| name.replace('_', " "));
+		Self {
+			raw: raw.to_string(),
+			code,
+			sex,
+			birthdate,
+			name,
+			additional: subfields.map(str::to_string).collect(),
+		}
+	}
+}
+
+impl RecordingIdentification {
+	fn parse(raw: &str) -> Self {
+		let mut subfields = raw.split_whitespace();
+		// 非 EDF+ 文件没有 "Startdate" 前缀，整段只作为原文保留
+		if subfields.next() != Some("Startdate") {
+			return Self {
+				raw: raw.to_string(),
+				start_date: None,
+				admin_code: None,
+				technician: None,
+				equipment: None,
+				additional: Vec::new(),
+			};
+		}
+
+		Self {
+			raw: raw.to_string(),
+			start_date: subfields.next().and_then(known_subfield),
+			admin_code: subfields.next().and_then(known_subfield),
+			technician: subfields.next().and_then(known_subfield),
+			equipment: subfields.next().and_then(known_subfield),
+			additional: subfields.map(str::to_string).collect(),
+		}
+	}
+}
+
+impl EdfStartTime {
+	/// 解析 "dd.mm.yy" 与 "hh.mm.ss"
+	///
+	/// 两位年份按规范以 1985 为界；EDF+ 记录标识中有四位年份时优先使用
+	fn parse(date: &str, time: &str, edf_plus_date: Option<&str>) -> Option<Self> {
+		let [day, month, short_year] = parse_dotted_triplet(date)?;
+		let [hour, minute, second] = parse_dotted_triplet(time)?;
+		let year = edf_plus_date
+			.and_then(|text| text.rsplit('-').next())
+			.and_then(|text| text.parse::<u16>().ok())
+			.unwrap_or(if short_year >= 85 {
+				1900 + short_year as u16
+			} else {
+				2000 + short_year as u16
+			});
+
+		let valid = (1..=12).contains(&month)
+			&& (1..=31).contains(&day)
+			&& hour < 24
+			&& minute < 60
+			&& second < 60;
+		valid.then_some(Self {
+			year,
+			month,
+			day,
+			hour,
+			minute,
+			second,
+		})
+	}
+}
+
+fn parse_dotted_triplet(text: &str) -> Option<[u8; 3]> {
+	let mut parts = text.split('.').map(|part| part.parse::<u8>().ok());
+	let triplet = [parts.next()??, parts.next()??, parts.next()??];
+	parts.next().is_none().then_some(triplet)
+}
+
+fn known_subfield(subfield: &str) -> Option<String> {
+	(subfield != UNKNOWN_SUBFIELD).then(|| subfield.to_string())
+}
+
+pub(crate) fn parse_ascii_usize(bytes: &[u8], field_name: &str) -> Result<usize, EdfLoaderError> {
+	let text = parse_ascii_field(bytes);
+	text.parse::<usize>()
+		.map_err(|error| EdfLoaderError::InvalidFormat(format!("{field_name} 解析失败: {error}")))
+}
+
+/// 写入定长 ASCII 字段，不足部分以空格填充，超长部分截断
+pub(crate) fn write_ascii_field(buf: &mut [u8], value: &str) {
+	buf.fill(b' ');
+	let bytes = value.as_bytes();
+	let len = bytes.len().min(buf.len());
+	buf[..len].copy_from_slice(&bytes[..len]);
+}
+
+/// 按字段分组为所有信号依次写入同一信号头字段，返回下一字段的偏移
+///
+/// EDF/BDF 头部中的各字段需要“按字段分组”为所有通道依次写入，
+/// 不是每个通道一个独立的 256 字节块
+pub(crate) fn write_signal_field<T, F>(
+	header: &mut [u8],
+	mut offset: usize,
+	field_width: usize,
+	signals: &[T],
+	formatter: F,
+) -> usize
+where
+	F: Fn(&T) -> String,
+{
+	for signal in signals {
+		write_ascii_field(
+			&mut header[offset..offset + field_width],
+			&formatter(signal),
+		);
+		offset += field_width;
+	}
+	offset
+}
+
+fn parse_ascii_i32(bytes: &[u8], field_name: &str) -> Result<i32, EdfLoaderError> {
+	let text = parse_ascii_field(bytes);
+	text.parse::<i32>()
+		.map_err(|error| EdfLoaderError::InvalidFormat(format!("{field_name} 解析失败: {error}")))
+}
+
+fn parse_ascii_f64(bytes: &[u8], field_name: &str) -> Result<f64, EdfLoaderError> {
+	let text = parse_ascii_field(bytes);
+	text.parse::<f64>()
+		.map_err(|error| EdfLoaderError::InvalidFormat(format!("{field_name} 解析失败: {error}")))
+}
+
+fn parse_ascii_field(bytes: &[u8]) -> String {
+	String::from_utf8_lossy(bytes).trim().to_string()
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn parses_edf_plus_identification_subfields() {
+		let patient = PatientIdentification::parse("MCH-0234567 F 02-MAY-1951 Haagse_Harry extra");
+		let recording =
+			RecordingIdentification::parse("Startdate 02-MAR-2002 PSG-1234/2002 X Telemetry03");
+		let legacy = RecordingIdentification::parse("Test EEG Data");
+
+		assert_eq!(patient.code.as_deref(), Some("MCH-0234567"));
+		assert_eq!(patient.sex.as_deref(), Some("F"));
+		assert_eq!(patient.birthdate.as_deref(), Some("02-MAY-1951"));
+		assert_eq!(patient.name.as_deref(), Some("Haagse Harry"));
+		assert_eq!(patient.additional, ["extra"]);
+		assert_eq!(recording.start_date.as_deref(), Some("02-MAR-2002"));
+		assert_eq!(recording.admin_code.as_deref(), Some("PSG-1234/2002"));
+		assert_eq!(recording.technician, None);
+		assert_eq!(recording.equipment.as_deref(), Some("Telemetry03"));
+		assert_eq!(legacy.start_date, None);
+	}
+
+	#[test]
+	fn resolves_start_year_from_clipping_date_or_edf_plus_field() {
+		let legacy = EdfStartTime::parse("02.03.84", "13.45.07", None);
+		let plus = EdfStartTime::parse("02.03.84", "13.45.07", Some("02-MAR-2184"));
+
+		assert_eq!(legacy.map(|start| start.year), Some(2084));
+		assert_eq!(
+			plus.map(|start| start.to_string()).as_deref(),
+			Some("2184-03-02 13:45:07")
+		);
+		assert_eq!(
+			EdfStartTime::parse("02.03.99", "00.00.00", None).map(|start| start.year),
+			Some(1999)
+		);
+		assert_eq!(EdfStartTime::parse("31.13.99", "00.00.00", None), None);
+	}
+}
@@ -1,11 +1,26 @@
 //! edf_io - EDF+/BDF 文件读写库
 //!
-//! 提供 EDF+ 和 BDF 文件的读取和测试数据生成功能
+//! 提供 EDF+ 和 BDF 文件的读取、按需分段读取、原生写入、注释读写和测试数据生成功能
 
+mod annotation;
 mod bdf_writer;
+mod edf_writer;
 mod generator;
+mod header;
 mod loader;
+mod reader;
+mod resample;
 
+pub use annotation::{Annotation, encode_annotation, parse_annotation_record};
 pub use bdf_writer::{BdfSignalParam, BdfWriter, BdfWriterError, generate_test_bdf};
+pub use edf_writer::{
+	EdfSignalParam, EdfWriteSummary, EdfWriter, EdfWriterError, EdfWriterOptions,
+};
 pub use generator::TestEdfGenerator;
+pub use header::{
+	DataContinuity, EdfFileHeader, EdfStartTime, PatientIdentification, RecordingIdentification,
+	SignalHeader,
+};
 pub use loader::{EdfLoader, EdfLoaderError};
+pub use reader::EdfFileReader;
+pub use resample::{resample_linear, time_axis};
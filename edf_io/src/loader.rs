@@ -2,8 +2,11 @@
 //!
 //! 使用 edfplus 库读取 EDF+ 格式文件
 
+use crate::annotation::Annotation;
+use crate::header::EdfFileHeader;
+use crate::reader::EdfFileReader;
+use crate::resample::{resample_linear, time_axis};
 use edfplus::EdfReader;
-use std::fs;
 use thiserror::Error;
 
 /// EDF+ 加载错误
@@ -36,6 +39,18 @@ pub struct EdfLoader {
 	total_points: usize,
 	/// 各通道物理值数据
 	channels: Vec<Vec<f32>>,
+	/// 各通道标签
+	labels: Vec<String>,
+	/// 各通道物理单位
+	physical_dimensions: Vec<String>,
+	/// 各通道采样率
+	channel_sample_rates: Vec<f32>,
+	/// EDF+/BDF+ 注释
+	annotations: Vec<Annotation>,
+	/// 第一个数据记录相对文件头开始时刻的偏移（秒）
+	start_offset_secs: f64,
+	/// 完整文件头
+	header: EdfFileHeader,
 }
 
 impl EdfLoader {
@@ -47,19 +62,18 @@ impl EdfLoader {
 	/// # Returns
 	/// 成功返回 EdfLoader 实例
 	pub fn from_file(path: &str) -> Result<Self, EdfLoaderError> {
-		if is_bdf_file(path)? {
-			return Self::from_bdf_file(path);
+		// 只解析文件头，避免为判断格式读入整个文件
+		let mut file_reader = EdfFileReader::open(path)?;
+		if file_reader.is_bdf() {
+			return Self::from_bdf_reader(file_reader);
 		}
 
 		let mut reader =
 			EdfReader::open(path).map_err(|e| EdfLoaderError::FileOpenError(e.to_string()))?;
 		let header = reader.header();
 		let channel_count = header.signals.len();
-		let sample_rate = header
-			.signals
-			.first()
-			.map(|signal| signal.samples_per_record.max(0) as u32)
-			.unwrap_or(0);
+		// 每记录采样数需除以记录时长，且各信号可以不同，统一取文件头解析结果
+		let sample_rate = file_reader.sample_rate();
 
 		let mut channels: Vec<Vec<f32>> = Vec::with_capacity(channel_count);
 		for signal_idx in 0..channel_count {
@@ -70,6 +84,12 @@ impl EdfLoader {
 		}
 
 		let total_points = channels.first().map(|channel| channel.len()).unwrap_or(0);
+		if file_reader.channel_count() != channel_count {
+			return Err(EdfLoaderError::InvalidFormat(format!(
+				"头部通道数 {} 与读取的通道数 {channel_count} 不一致",
+				file_reader.channel_count()
+			)));
+		}
 
 		Ok(Self {
 			path: path.to_string(),
@@ -77,128 +97,33 @@ impl EdfLoader {
 			sample_rate,
 			total_points,
 			channels,
+			labels: file_reader.labels().to_vec(),
+			physical_dimensions: file_reader.physical_dimensions().to_vec(),
+			channel_sample_rates: file_reader.channel_sample_rates().to_vec(),
+			annotations: file_reader.read_annotations()?,
+			start_offset_secs: file_reader.read_start_offset_secs()?.unwrap_or(0.0),
+			header: file_reader.header().clone(),
 		})
 	}
 
-	/// 从 BDF 文件加载数据
-	fn from_bdf_file(path: &str) -> Result<Self, EdfLoaderError> {
-		let bytes =
-			fs::read(path).map_err(|error| EdfLoaderError::FileOpenError(error.to_string()))?;
-		if bytes.len() < 256 {
-			return Err(EdfLoaderError::InvalidFormat("BDF 头长度不足".to_string()));
-		}
-
-		let header_bytes = parse_ascii_usize(&bytes[184..192], "头长度")?;
-		let channel_count = parse_ascii_usize(&bytes[252..256], "通道数")?;
-		if header_bytes < 256 || channel_count == 0 {
-			return Err(EdfLoaderError::InvalidFormat(
-				"BDF 头字段不合法".to_string(),
-			));
-		}
-		if bytes.len() < header_bytes {
-			return Err(EdfLoaderError::InvalidFormat(
-				"BDF 文件长度小于头长度".to_string(),
-			));
-		}
-
-		let num_records = parse_ascii_usize(&bytes[236..244], "记录数")?;
-		let record_duration = parse_ascii_f32(&bytes[244..252], "记录时长")?;
-		if record_duration <= 0.0 {
-			return Err(EdfLoaderError::InvalidFormat(
-				"BDF 记录时长必须大于 0".to_string(),
-			));
-		}
-
-		let signal_offset = 256;
-		let labels_offset = signal_offset;
-		let physical_min_offset =
-			labels_offset + 16 * channel_count + 80 * channel_count + 8 * channel_count;
-		let physical_max_offset = physical_min_offset + 8 * channel_count;
-		let digital_min_offset = physical_max_offset + 8 * channel_count;
-		let digital_max_offset = digital_min_offset + 8 * channel_count;
-		let samples_per_record_offset = digital_max_offset + 8 * channel_count + 80 * channel_count;
-		let reserved_offset = samples_per_record_offset + 8 * channel_count;
-		let expected_header_end = reserved_offset + 32 * channel_count;
-		if expected_header_end > header_bytes {
-			return Err(EdfLoaderError::InvalidFormat(
-				"BDF 信号头长度不完整".to_string(),
-			));
-		}
-
-		let mut channels = Vec::with_capacity(channel_count);
-		let mut sample_rate = 0u32;
-		let mut record_size = 0usize;
-
-		let mut signal_headers = Vec::with_capacity(channel_count);
-		for index in 0..channel_count {
-			let physical_min = parse_ascii_f32(
-				&bytes[physical_min_offset + index * 8..physical_min_offset + (index + 1) * 8],
-				"物理最小值",
-			)?;
-			let physical_max = parse_ascii_f32(
-				&bytes[physical_max_offset + index * 8..physical_max_offset + (index + 1) * 8],
-				"物理最大值",
-			)?;
-			let digital_min = parse_ascii_i32(
-				&bytes[digital_min_offset + index * 8..digital_min_offset + (index + 1) * 8],
-				"数字最小值",
-			)?;
-			let digital_max = parse_ascii_i32(
-				&bytes[digital_max_offset + index * 8..digital_max_offset + (index + 1) * 8],
-				"数字最大值",
-			)?;
-			let samples_per_record = parse_ascii_usize(
-				&bytes[samples_per_record_offset + index * 8
-					..samples_per_record_offset + (index + 1) * 8],
-				"每记录采样数",
-			)?;
-
-			if index == 0 {
-				sample_rate = (samples_per_record as f32 / record_duration).round() as u32;
-			}
-			record_size = record_size.saturating_add(samples_per_record.saturating_mul(3));
-			channels.push(Vec::with_capacity(
-				num_records.saturating_mul(samples_per_record),
-			));
-			signal_headers.push(BdfSignalHeader {
-				physical_min,
-				physical_max,
-				digital_min,
-				digital_max,
-				samples_per_record,
-			});
-		}
-
-		let data_bytes = &bytes[header_bytes..];
-		let expected_data_size = num_records.saturating_mul(record_size);
-		if data_bytes.len() < expected_data_size {
-			return Err(EdfLoaderError::InvalidFormat(format!(
-				"BDF 数据长度不足: 期望 {expected_data_size} 字节，实际 {} 字节",
-				data_bytes.len()
-			)));
-		}
-
-		for record_index in 0..num_records {
-			let mut record_offset = record_index * record_size;
-			for (channel_index, signal_header) in signal_headers.iter().enumerate() {
-				for _ in 0..signal_header.samples_per_record {
-					let sample_bytes = &data_bytes[record_offset..record_offset + 3];
-					let digital_value = decode_bdf_sample(sample_bytes);
-					let physical_value = digital_to_physical(digital_value, signal_header);
-					channels[channel_index].push(physical_value);
-					record_offset += 3;
-				}
-			}
-		}
-
-		let total_points = channels.first().map(|channel| channel.len()).unwrap_or(0);
+	/// 通过按需读取器一次性读入 BDF 文件的全部通道
+	fn from_bdf_reader(mut reader: EdfFileReader) -> Result<Self, EdfLoaderError> {
+		let channels = (0..reader.channel_count())
+			.map(|channel| reader.read_channel(channel))
+			.collect::<Result<Vec<_>, _>>()?;
 
 		Ok(Self {
-			path: path.to_string(),
-			channel_count,
-			sample_rate,
-			total_points,
+			path: reader.path().to_string(),
+			channel_count: reader.channel_count(),
+			sample_rate: reader.sample_rate(),
+			total_points: reader.total_points(),
 			channels,
+			labels: reader.labels().to_vec(),
+			physical_dimensions: reader.physical_dimensions().to_vec(),
+			channel_sample_rates: reader.channel_sample_rates().to_vec(),
+			annotations: reader.read_annotations()?,
+			start_offset_secs: reader.read_start_offset_secs()?.unwrap_or(0.0),
+			header: reader.header().clone(),
 		})
 	}
 
@@ -207,12 +132,12 @@ impl EdfLoader {
 		self.channel_count
 	}
 
-	/// 获取采样率
+	/// 获取采样率，取第一个通道的采样率；混合采样率文件请使用 `channel_sample_rates`
 	pub fn sample_rate(&self) -> u32 {
 		self.sample_rate
 	}
 
-	/// 获取总数据点数
+	/// 获取总数据点数，取第一个通道的样本数
 	pub fn total_points(&self) -> usize {
 		self.total_points
 	}
@@ -226,67 +151,135 @@ impl EdfLoader {
 	pub fn channels(&self) -> &[Vec<f32>] {
 		&self.channels
 	}
-}
 
-#[derive(Debug, Clone, Copy)]
-struct BdfSignalHeader {
-	physical_min: f32,
-	physical_max: f32,
-	digital_min: i32,
-	digital_max: i32,
-	samples_per_record: usize,
-}
+	/// 获取各通道标签
+	pub fn labels(&self) -> &[String] {
+		&self.labels
+	}
 
-fn is_bdf_file(path: &str) -> Result<bool, EdfLoaderError> {
-	let bytes = fs::read(path).map_err(|error| EdfLoaderError::FileOpenError(error.to_string()))?;
-	if bytes.len() < 8 {
-		return Ok(false);
+	/// 获取各通道物理单位
+	pub fn physical_dimensions(&self) -> &[String] {
+		&self.physical_dimensions
 	}
-	Ok(bytes[0] == 0xFF && &bytes[1..8] == b"BIOSEMI")
-}
 
-fn parse_ascii_usize(bytes: &[u8], field_name: &str) -> Result<usize, EdfLoaderError> {
-	let text = parse_ascii_field(bytes);
-	text.parse::<usize>()
-		.map_err(|error| EdfLoaderError::InvalidFormat(format!("{field_name} 解析失败: {error}")))
-}
+	/// 获取各通道采样率，由每记录采样数除以记录时长得到
+	pub fn channel_sample_rates(&self) -> &[f32] {
+		&self.channel_sample_rates
+	}
 
-fn parse_ascii_i32(bytes: &[u8], field_name: &str) -> Result<i32, EdfLoaderError> {
-	let text = parse_ascii_field(bytes);
-	text.parse::<i32>()
-		.map_err(|error| EdfLoaderError::InvalidFormat(format!("{field_name} 解析失败: {error}")))
-}
+	/// 所有通道采样率是否相同
+	pub fn has_uniform_sample_rate(&self) -> bool {
+		self.channel_sample_rates
+			.windows(2)
+			.all(|pair| pair[0] == pair[1])
+	}
 
-fn parse_ascii_f32(bytes: &[u8], field_name: &str) -> Result<f32, EdfLoaderError> {
-	let text = parse_ascii_field(bytes);
-	text.parse::<f32>()
-		.map_err(|error| EdfLoaderError::InvalidFormat(format!("{field_name} 解析失败: {error}")))
-}
+	/// 获取单个通道的时间轴（秒），按该通道自身的采样率计算
+	///
+	/// # Arguments
+	/// * `channel` - 通道索引
+	pub fn channel_time_axis(&self, channel: usize) -> Result<Vec<f64>, EdfLoaderError> {
+		let samples = self
+			.channels
+			.get(channel)
+			.ok_or(EdfLoaderError::InvalidChannel(channel))?;
+		Ok(time_axis(self.channel_sample_rates[channel], samples.len()))
+	}
 
-fn parse_ascii_field(bytes: &[u8]) -> String {
-	String::from_utf8_lossy(bytes).trim().to_string()
-}
+	/// 把所有通道线性插值到同一采样率，便于按统一时间轴显示
+	///
+	/// # Arguments
+	/// * `target_rate` - 目标采样率 (Hz)，通常取最高的通道采样率
+	pub fn resample_channels(&self, target_rate: f32) -> Vec<Vec<f32>> {
+		self.channels
+			.iter()
+			.zip(&self.channel_sample_rates)
+			.map(|(samples, source_rate)| resample_linear(samples, *source_rate, target_rate))
+			.collect()
+	}
 
-fn decode_bdf_sample(bytes: &[u8]) -> i32 {
-	let sign_byte = if bytes[2] & 0x80 == 0 { 0x00 } else { 0xFF };
-	i32::from_le_bytes([bytes[0], bytes[1], bytes[2], sign_byte])
-}
+	/// 获取 EDF+/BDF+ 注释，按起始时刻排序
+	pub fn annotations(&self) -> &[Annotation] {
+		&self.annotations
+	}
 
-fn digital_to_physical(digital_value: i32, signal_header: &BdfSignalHeader) -> f32 {
-	let digital_range = (signal_header.digital_max - signal_header.digital_min) as f32;
-	if digital_range.abs() <= f32::EPSILON {
-		return signal_header.physical_min;
+	/// 第一个数据记录相对文件头开始时刻的偏移（秒）
+	///
+	/// 取自 EDF+/BDF+ 首个记录的计时注释，可表示亚秒级开始时刻，普通 EDF/BDF 为 0
+	pub fn start_offset_secs(&self) -> f64 {
+		self.start_offset_secs
 	}
 
-	let physical_range = signal_header.physical_max - signal_header.physical_min;
-	((digital_value - signal_header.digital_min) as f32 / digital_range) * physical_range
-		+ signal_header.physical_min
+	/// 获取完整文件头，包括患者与记录标识、开始时刻和逐信号头
+	pub fn header(&self) -> &EdfFileHeader {
+		&self.header
+	}
 }
 
 #[cfg(test)]
 mod tests {
 	use super::EdfLoader;
-	use crate::{BdfSignalParam, BdfWriter};
+	use crate::{BdfSignalParam, BdfWriter, EdfSignalParam, EdfWriter, EdfWriterOptions};
+
+	#[test]
+	fn keeps_per_signal_sample_rates_in_mixed_rate_file() {
+		let path = std::env::temp_dir().join("codex_loader_mixed_rate_test.bdf");
+		let signals = vec![
+			BdfSignalParam {
+				label: "EEG Fp1".to_string(),
+				physical_max: 200.0,
+				physical_min: -200.0,
+				digital_max: 8_388_607,
+				digital_min: -8_388_608,
+				sample_rate: 8,
+				physical_dimension: "uV".to_string(),
+			},
+			BdfSignalParam {
+				label: "Resp".to_string(),
+				physical_max: 100.0,
+				physical_min: -100.0,
+				digital_max: 8_388_607,
+				digital_min: -8_388_608,
+				sample_rate: 2,
+				physical_dimension: "%".to_string(),
+			},
+		];
+
+		let mut writer = BdfWriter::create(&path, signals, 8, 24).unwrap();
+		for record in 0..3 {
+			let eeg = (0..8).map(|index| (record * 8 + index) as f64).collect();
+			let resp = (0..2)
+				.map(|index| (record * 2 + index) as f64 * 10.0)
+				.collect();
+			writer.write_samples(&[eeg, resp]).unwrap();
+		}
+		writer.finalize().unwrap();
+
+		let path_text = path.to_string_lossy().to_string();
+		let loader = EdfLoader::from_file(&path_text).unwrap();
+		let mut reader = crate::EdfFileReader::open(&path_text).unwrap();
+		let resp_tail = reader.read_range(1, 3, 10).unwrap();
+		std::fs::remove_file(&path).ok();
+
+		assert!(!loader.has_uniform_sample_rate());
+		assert_eq!(loader.sample_rate(), 8);
+		assert_eq!(loader.channel_sample_rates(), [8.0, 2.0]);
+		assert_eq!(loader.channels()[0].len(), 24);
+		assert_eq!(loader.channels()[1].len(), 6);
+		assert_eq!(loader.header().signals[1].sample_rate(1.0), 2.0);
+		assert!((loader.channels()[0][13] - 13.0).abs() < 1e-3);
+		assert!((loader.channels()[1][3] - 30.0).abs() < 1e-3);
+		assert_eq!(resp_tail, loader.channels()[1][3..]);
+
+		let resp_time = loader.channel_time_axis(1).unwrap();
+		assert_eq!(resp_time[..3], [0.0, 0.5, 1.0]);
+		assert!(loader.channel_time_axis(2).is_err());
+
+		let aligned = loader.resample_channels(8.0);
+		assert_eq!(aligned[0], loader.channels()[0]);
+		assert_eq!(aligned[1].len(), 24);
+		assert!((aligned[1][2] - 5.0).abs() < 1e-3);
+	}
 
 	#[test]
 	fn can_read_generated_bdf_file() {
@@ -326,5 +319,48 @@ mod tests {
 		assert_eq!(loader.sample_rate(), 4);
 		assert_eq!(loader.total_points(), 4);
 		assert_eq!(loader.channels().len(), 2);
+		assert_eq!(loader.labels(), ["EEG CH0", "EEG CH1"]);
+		assert_eq!(loader.physical_dimensions(), ["uV", "uV"]);
+		assert_eq!(loader.channel_sample_rates(), [4.0, 4.0]);
+
+		let header = loader.header();
+		assert!(header.is_bdf);
+		assert_eq!(header.recording.start_date.as_deref(), Some("21-MAR-2026"));
+		assert_eq!(
+			header.start.map(|start| start.to_string()).as_deref(),
+			Some("2026-03-21 00:00:00")
+		);
+		assert_eq!(header.record_count, Some(1));
+		assert_eq!(header.signals[1].transducer, "AgAgCl electrodes");
+		assert_eq!(header.signals[1].prefilter, "HP:0.1Hz LP:70Hz");
+		assert_eq!(header.signals[1].digital_min, -8_388_608);
+	}
+
+	#[test]
+	fn reads_start_offset_from_first_time_keeping_tal() {
+		let path = std::env::temp_dir().join("codex_loader_start_offset_test.edf");
+		let signal = EdfSignalParam {
+			label: "EEG Cz".to_string(),
+			transducer: "AgAgCl electrode".to_string(),
+			physical_dimension: "uV".to_string(),
+			physical_min: -100.0,
+			physical_max: 100.0,
+			digital_min: -32768,
+			digital_max: 32767,
+			prefilter: String::new(),
+			sample_rate: 4.0,
+		};
+		let mut writer =
+			EdfWriter::create(&path, vec![signal], EdfWriterOptions::default()).unwrap();
+		writer.write_record_at(0.25, &[vec![1.0; 4]]).unwrap();
+		writer.write_samples(&[vec![2.0; 4]]).unwrap();
+		assert!(writer.write_record_at(5.0, &[vec![3.0; 4]]).is_err());
+		writer.finalize().unwrap();
+
+		let loader = EdfLoader::from_file(&path.to_string_lossy()).unwrap();
+		std::fs::remove_file(&path).ok();
+
+		assert_eq!(loader.start_offset_secs(), 0.25);
+		assert_eq!(loader.channels()[0].len(), 8);
 	}
 }
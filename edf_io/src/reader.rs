@@ -0,0 +1,569 @@
+//! EDF/BDF 按需读取器
+//!
+//! 打开时只解析文件头，样本在调用 `read_range` 时按数据记录定位后读取，
+//! 适合浏览无法一次装入内存的长时记录
+
+use crate::annotation::{Annotation, parse_annotation_record, parse_record_onset};
+use crate::header::{EdfFileHeader, SignalHeader, parse_ascii_usize};
+use crate::loader::EdfLoaderError;
+use std::fs::File;
+use std::io::{Read, Seek, SeekFrom};
+
+/// 按需读取的 EDF/BDF 文件
+///
+/// 通道索引不包含注释通道，与 `EdfLoader` 的通道顺序一致
+#[derive(Debug)]
+pub struct EdfFileReader {
+	/// 文件路径
+	path: String,
+	/// 打开的文件句柄
+	file: File,
+	/// 文件头
+	header: EdfFileHeader,
+	/// 数据记录数量
+	record_count: usize,
+	/// 单个数据记录长度（字节）
+	record_size: usize,
+	/// 数据通道布局
+	signals: Vec<SignalLayout>,
+	/// EDF+/BDF+ 注释通道布局
+	annotation_slots: Vec<AnnotationSlot>,
+	/// 各通道标签
+	labels: Vec<String>,
+	/// 各通道物理单位
+	physical_dimensions: Vec<String>,
+	/// 各通道采样率
+	channel_sample_rates: Vec<f32>,
+}
+
+impl EdfFileReader {
+	/// 打开文件并解析文件头，不读取任何样本
+	///
+	/// # Arguments
+	/// * `path` - EDF/BDF 文件路径
+	pub fn open(path: &str) -> Result<Self, EdfLoaderError> {
+		let mut file =
+			File::open(path).map_err(|error| EdfLoaderError::FileOpenError(error.to_string()))?;
+		let file_size = file
+			.metadata()
+			.map_err(|error| EdfLoaderError::FileOpenError(error.to_string()))?
+			.len() as usize;
+
+		let mut fixed_header = [0u8; 256];
+		file.read_exact(&mut fixed_header)
+			.map_err(|_| EdfLoaderError::InvalidFormat("头长度不足".to_string()))?;
+		let header_bytes = parse_ascii_usize(&fixed_header[184..192], "头长度")?.max(256);
+		// 头长度来自不可信的文件内容，分配缓冲区前先与文件实际长度比较
+		if header_bytes > file_size {
+			return Err(EdfLoaderError::InvalidFormat(format!(
+				"头长度 {header_bytes} 超过文件长度 {file_size}"
+			)));
+		}
+		let mut header_data = vec![0u8; header_bytes];
+		header_data[..256].copy_from_slice(&fixed_header);
+		file.read_exact(&mut header_data[256..])
+			.map_err(|_| EdfLoaderError::InvalidFormat("信号头长度不完整".to_string()))?;
+		let header = EdfFileHeader::parse(&header_data)?;
+
+		let bytes_per_sample = if header.is_bdf { 3 } else { 2 };
+		let mut signals = Vec::with_capacity(header.signals.len());
+		let mut annotation_slots = Vec::new();
+		let mut record_size = 0usize;
+		for signal in &header.signals {
+			let record_offset = record_size;
+			record_size += signal.samples_per_record * bytes_per_sample;
+			if signal.is_annotation() {
+				annotation_slots.push(AnnotationSlot {
+					record_offset,
+					len: signal.samples_per_record * bytes_per_sample,
+				});
+				continue;
+			}
+			signals.push(SignalLayout {
+				physical_min: signal.physical_min,
+				physical_max: signal.physical_max,
+				digital_min: signal.digital_min,
+				digital_max: signal.digital_max,
+				samples_per_record: signal.samples_per_record,
+				record_offset,
+			});
+		}
+
+		// 记录数为 -1 表示采集中断未回写，按文件长度推算完整记录数
+		let available_records = file_size.saturating_sub(header_bytes) / record_size.max(1);
+		let record_count = header.record_count.unwrap_or(available_records);
+		if record_count > available_records {
+			return Err(EdfLoaderError::InvalidFormat(format!(
+				"数据长度不足: 期望 {record_count} 个记录，实际 {available_records} 个"
+			)));
+		}
+
+		let data_signals: Vec<&SignalHeader> = header.data_signals().collect();
+		let labels = data_signals
+			.iter()
+			.map(|signal| signal.label.clone())
+			.collect();
+		let physical_dimensions = data_signals
+			.iter()
+			.map(|signal| signal.physical_dimension.clone())
+			.collect();
+		let channel_sample_rates = data_signals
+			.iter()
+			.map(|signal| signal.sample_rate(header.record_duration_secs) as f32)
+			.collect();
+
+		Ok(Self {
+			path: path.to_string(),
+			file,
+			record_count,
+			record_size,
+			signals,
+			annotation_slots,
+			labels,
+			physical_dimensions,
+			channel_sample_rates,
+			header,
+		})
+	}
+
+	/// 读取单个通道 `[start_sample, start_sample + len)` 范围内的物理值
+	///
+	/// 超出通道末尾的部分被截断，起点越界时返回空向量
+	///
+	/// # Arguments
+	/// * `channel` - 通道索引（不含注释通道）
+	/// * `start_sample` - 起始样本索引
+	/// * `len` - 样本数量
+	pub fn read_range(
+		&mut self,
+		channel: usize,
+		start_sample: usize,
+		len: usize,
+	) -> Result<Vec<f32>, EdfLoaderError> {
+		let signal = self
+			.signals
+			.get(channel)
+			.ok_or(EdfLoaderError::InvalidChannel(channel))?;
+		let end = start_sample
+			.saturating_add(len)
+			.min(signal.samples_per_record * self.record_count);
+		if start_sample >= end {
+			return Ok(Vec::new());
+		}
+
+		let bytes_per_sample = if self.header.is_bdf { 3 } else { 2 };
+		let mut samples = Vec::with_capacity(end - start_sample);
+		let mut buffer = Vec::new();
+		let mut position = start_sample;
+		// 每个数据记录中该通道的样本是连续的，逐记录读取一段
+		while position < end {
+			let record = position / signal.samples_per_record;
+			let within = position % signal.samples_per_record;
+			let count = (signal.samples_per_record - within).min(end - position);
+			let offset = self.header.header_bytes
+				+ record * self.record_size
+				+ signal.record_offset
+				+ within * bytes_per_sample;
+
+			buffer.resize(count * bytes_per_sample, 0);
+			self.file
+				.seek(SeekFrom::Start(offset as u64))
+				.map_err(|error| EdfLoaderError::ReadError(error.to_string()))?;
+			self.file
+				.read_exact(&mut buffer)
+				.map_err(|error| EdfLoaderError::ReadError(error.to_string()))?;
+			samples.extend(
+				buffer
+					.chunks_exact(bytes_per_sample)
+					.map(|bytes| signal.to_physical(decode_sample(bytes))),
+			);
+			position += count;
+		}
+		Ok(samples)
+	}
+
+	/// 读取全部 EDF+/BDF+ 注释，按起始时刻排序，不含每个记录的计时注释
+	///
+	/// 没有注释通道的文件返回空向量
+	pub fn read_annotations(&mut self) -> Result<Vec<Annotation>, EdfLoaderError> {
+		let mut annotations = Vec::new();
+		let mut buffer = Vec::new();
+		for record in 0..self.record_count {
+			for slot in &self.annotation_slots {
+				let offset =
+					self.header.header_bytes + record * self.record_size + slot.record_offset;
+				buffer.resize(slot.len, 0);
+				self.file
+					.seek(SeekFrom::Start(offset as u64))
+					.map_err(|error| EdfLoaderError::ReadError(error.to_string()))?;
+				self.file
+					.read_exact(&mut buffer)
+					.map_err(|error| EdfLoaderError::ReadError(error.to_string()))?;
+				annotations.extend(parse_annotation_record(&buffer)?);
+			}
+		}
+		annotations.sort_by(|left, right| left.onset_secs.total_cmp(&right.onset_secs));
+		Ok(annotations)
+	}
+
+	/// 读取第一个数据记录计时注释中的起始时刻（秒），即记录相对文件头开始时刻的偏移
+	///
+	/// 普通 EDF/BDF 没有注释通道，返回 None
+	pub fn read_start_offset_secs(&mut self) -> Result<Option<f64>, EdfLoaderError> {
+		let Some(slot) = self.annotation_slots.first().copied() else {
+			return Ok(None);
+		};
+		if self.record_count == 0 {
+			return Ok(None);
+		}
+		let mut buffer = vec![0; slot.len];
+		self.file
+			.seek(SeekFrom::Start(
+				(self.header.header_bytes + slot.record_offset) as u64,
+			))
+			.map_err(|error| EdfLoaderError::ReadError(error.to_string()))?;
+		self.file
+			.read_exact(&mut buffer)
+			.map_err(|error| EdfLoaderError::ReadError(error.to_string()))?;
+		parse_record_onset(&buffer)
+	}
+
+	/// 读取单个通道的全部样本
+	pub fn read_channel(&mut self, channel: usize) -> Result<Vec<f32>, EdfLoaderError> {
+		let len = self.channel_len(channel)?;
+		self.read_range(channel, 0, len)
+	}
+
+	/// 获取单个通道的样本数
+	pub fn channel_len(&self, channel: usize) -> Result<usize, EdfLoaderError> {
+		self.signals
+			.get(channel)
+			.map(|signal| signal.samples_per_record * self.record_count)
+			.ok_or(EdfLoaderError::InvalidChannel(channel))
+	}
+
+	/// 是否为 24 位 BDF 文件
+	pub fn is_bdf(&self) -> bool {
+		self.header.is_bdf
+	}
+
+	/// 获取完整文件头
+	pub fn header(&self) -> &EdfFileHeader {
+		&self.header
+	}
+
+	/// 按目标采样率读取 `[start_sample, start_sample + len)` 范围内线性插值后的物理值
+	///
+	/// 样本索引以目标采样率计，结果与对整个通道调用 `resample_linear` 后切片一致，
+	/// 便于混合采样率文件按统一时间轴分页读取
+	///
+	/// # Arguments
+	/// * `channel` - 通道索引（不含注释通道）
+	/// * `start_sample` - 目标采样率下的起始样本索引
+	/// * `len` - 样本数量
+	/// * `target_rate` - 目标采样率 (Hz)
+	pub fn read_range_at_rate(
+		&mut self,
+		channel: usize,
+		start_sample: usize,
+		len: usize,
+		target_rate: f32,
+	) -> Result<Vec<f32>, EdfLoaderError> {
+		let source_rate = *self
+			.channel_sample_rates
+			.get(channel)
+			.ok_or(EdfLoaderError::InvalidChannel(channel))?;
+		if source_rate == target_rate {
+			return self.read_range(channel, start_sample, len);
+		}
+
+		let end = start_sample
+			.saturating_add(len)
+			.min(self.channel_len_at_rate(channel, target_rate)?);
+		if start_sample >= end {
+			return Ok(Vec::new());
+		}
+
+		// 读取覆盖插值所需左右相邻样本的原始范围
+		let ratio = source_rate as f64 / target_rate as f64;
+		let first = (start_sample as f64 * ratio).floor() as usize;
+		let last = ((end - 1) as f64 * ratio).floor() as usize + 1;
+		let source = self.read_range(channel, first, last + 1 - first)?;
+		let Some(max_index) = source.len().checked_sub(1) else {
+			return Ok(Vec::new());
+		};
+
+		Ok((start_sample..end)
+			.map(|index| {
+				let position = index as f64 * ratio - first as f64;
+				let left = (position.floor() as usize).min(max_index);
+				let right = (left + 1).min(max_index);
+				let fraction = (position - left as f64).clamp(0.0, 1.0) as f32;
+				source[left] + (source[right] - source[left]) * fraction
+			})
+			.collect())
+	}
+
+	/// 获取通道换算到目标采样率后的样本数量
+	///
+	/// # Arguments
+	/// * `channel` - 通道索引（不含注释通道）
+	/// * `target_rate` - 目标采样率 (Hz)
+	pub fn channel_len_at_rate(
+		&self,
+		channel: usize,
+		target_rate: f32,
+	) -> Result<usize, EdfLoaderError> {
+		let len = self.channel_len(channel)?;
+		let source_rate = self.channel_sample_rates[channel];
+		if source_rate == target_rate {
+			return Ok(len);
+		}
+		if source_rate <= 0.0 || target_rate <= 0.0 {
+			return Ok(0);
+		}
+		let ratio = source_rate as f64 / target_rate as f64;
+		Ok((len as f64 / ratio).round() as usize)
+	}
+
+	/// 获取最高的通道采样率，混合采样率文件按此对齐时不会对任何通道降采样
+	pub fn max_sample_rate(&self) -> f32 {
+		self.channel_sample_rates
+			.iter()
+			.copied()
+			.fold(0.0, f32::max)
+	}
+
+	/// 获取通道数量（不含注释通道）
+	pub fn channel_count(&self) -> usize {
+		self.signals.len()
+	}
+
+	/// 获取采样率，取第一个通道的采样率
+	pub fn sample_rate(&self) -> u32 {
+		self.channel_sample_rates
+			.first()
+			.map(|rate| rate.round() as u32)
+			.unwrap_or(0)
+	}
+
+	/// 获取总数据点数，取第一个通道的样本数
+	pub fn total_points(&self) -> usize {
+		self.channel_len(0).unwrap_or(0)
+	}
+
+	/// 获取数据记录数量
+	pub fn record_count(&self) -> usize {
+		self.record_count
+	}
+
+	/// 获取文件路径
+	pub fn path(&self) -> &str {
+		&self.path
+	}
+
+	/// 获取各通道标签
+	pub fn labels(&self) -> &[String] {
+		&self.labels
+	}
+
+	/// 获取各通道物理单位
+	pub fn physical_dimensions(&self) -> &[String] {
+		&self.physical_dimensions
+	}
+
+	/// 获取各通道采样率，由每记录采样数除以记录时长得到
+	pub fn channel_sample_rates(&self) -> &[f32] {
+		&self.channel_sample_rates
+	}
+}
+
+/// 注释通道在记录中的位置
+#[derive(Debug, Clone, Copy)]
+struct AnnotationSlot {
+	/// 注释通道在数据记录内的字节偏移
+	record_offset: usize,
+	/// 每个记录中注释通道的字节数
+	len: usize,
+}
+
+/// 数据通道在记录中的位置与定标参数
+#[derive(Debug, Clone, Copy)]
+struct SignalLayout {
+	physical_min: f64,
+	physical_max: f64,
+	digital_min: i32,
+	digital_max: i32,
+	samples_per_record: usize,
+	/// 通道在数据记录内的字节偏移
+	record_offset: usize,
+}
+
+impl SignalLayout {
+	fn to_physical(self, digital_value: i32) -> f32 {
+		let digital_range = (self.digital_max - self.digital_min) as f64;
+		if digital_range.abs() <= f64::EPSILON {
+			return self.physical_min as f32;
+		}
+
+		let physical_range = self.physical_max - self.physical_min;
+		(((digital_value - self.digital_min) as f64 / digital_range) * physical_range
+			+ self.physical_min) as f32
+	}
+}
+
+/// 解码小端有符号样本，EDF 为 16 位，BDF 为 24 位
+fn decode_sample(bytes: &[u8]) -> i32 {
+	match bytes {
+		[low, high] => i16::from_le_bytes([*low, *high]) as i32,
+		[low, middle, high] => {
+			let sign_byte = if high & 0x80 == 0 { 0x00 } else { 0xFF };
+			i32::from_le_bytes([*low, *middle, *high, sign_byte])
+		}
+		_ => 0,
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::EdfFileReader;
+	use crate::{
+		BdfSignalParam, BdfWriter, EdfLoader, EdfLoaderError, TestEdfGenerator, resample_linear,
+	};
+
+	fn write_test_bdf(path: &std::path::Path) {
+		let signals = (0..2)
+			.map(|channel| BdfSignalParam {
+				label: format!("EEG CH{channel}"),
+				physical_max: 200.0,
+				physical_min: -200.0,
+				digital_max: 8_388_607,
+				digital_min: -8_388_608,
+				sample_rate: 4,
+				physical_dimension: "uV".to_string(),
+			})
+			.collect();
+		let mut writer = BdfWriter::create(path, signals, 4, 12).unwrap();
+		for record in 0..3 {
+			let data: Vec<Vec<f64>> = (0..2)
+				.map(|channel| {
+					(0..4)
+						.map(|sample| (record * 4 + sample) as f64 * 10.0 - channel as f64 * 50.0)
+						.collect()
+				})
+				.collect();
+			writer.write_samples(&data).unwrap();
+		}
+		writer.finalize().unwrap();
+	}
+
+	#[test]
+	fn read_range_matches_eager_loader_across_records() {
+		let path = std::env::temp_dir().join("codex_reader_range_test.bdf");
+		write_test_bdf(&path);
+		let path_text = path.to_string_lossy().to_string();
+
+		let loader = EdfLoader::from_file(&path_text).unwrap();
+		let mut reader = EdfFileReader::open(&path_text).unwrap();
+		let second_channel = reader.read_range(1, 3, 6).unwrap();
+		let full_channel = reader.read_channel(0).unwrap();
+		std::fs::remove_file(&path).ok();
+
+		assert!(reader.is_bdf());
+		assert_eq!(reader.record_count(), 3);
+		assert_eq!(reader.total_points(), 12);
+		assert_eq!(reader.labels(), loader.labels());
+		assert_eq!(full_channel, loader.channels()[0]);
+		assert_eq!(second_channel, loader.channels()[1][3..9]);
+		assert!((second_channel[0] - (30.0 - 50.0)).abs() < 1e-3);
+	}
+
+	#[test]
+	fn read_range_matches_edfplus_loader_for_16_bit_edf() {
+		let path = std::env::temp_dir().join("codex_reader_range_test.edf");
+		TestEdfGenerator::new(2, 32, 3).generate(&path).unwrap();
+		let path_text = path.to_string_lossy().to_string();
+
+		let loader = EdfLoader::from_file(&path_text).unwrap();
+		let mut reader = EdfFileReader::open(&path_text).unwrap();
+		let across_records = reader.read_range(1, 20, 40).unwrap();
+		let full_channel = reader.read_channel(0).unwrap();
+		std::fs::remove_file(&path).ok();
+
+		let close = |left: &[f32], right: &[f32]| {
+			left.len() == right.len()
+				&& left
+					.iter()
+					.zip(right)
+					.all(|(left, right)| (left - right).abs() < 1e-3)
+		};
+		assert!(!reader.is_bdf());
+		assert_eq!(reader.total_points(), loader.total_points());
+		assert!(close(&full_channel, &loader.channels()[0]));
+		assert!(close(&across_records, &loader.channels()[1][20..60]));
+	}
+
+	#[test]
+	fn read_range_at_rate_matches_resampled_full_channel() {
+		let path = std::env::temp_dir().join("codex_reader_range_at_rate_test.bdf");
+		let signals = [("EEG Fp1", 8), ("Resp", 2)]
+			.into_iter()
+			.map(|(label, sample_rate)| BdfSignalParam {
+				label: label.to_string(),
+				physical_max: 200.0,
+				physical_min: -200.0,
+				digital_max: 8_388_607,
+				digital_min: -8_388_608,
+				sample_rate,
+				physical_dimension: "uV".to_string(),
+			})
+			.collect();
+		let mut writer = BdfWriter::create(&path, signals, 8, 24).unwrap();
+		for record in 0..3 {
+			let eeg = (0..8).map(|sample| (record * 8 + sample) as f64).collect();
+			let resp = (0..2)
+				.map(|sample| (record * 2 + sample) as f64 * 10.0)
+				.collect();
+			writer.write_samples(&[eeg, resp]).unwrap();
+		}
+		writer.finalize().unwrap();
+
+		let mut reader = EdfFileReader::open(&path.to_string_lossy()).unwrap();
+		let resampled = resample_linear(&reader.read_channel(1).unwrap(), 2.0, 8.0);
+		let page = reader.read_range_at_rate(1, 5, 10, 8.0).unwrap();
+		let tail = reader.read_range_at_rate(1, 20, 10, 8.0).unwrap();
+		let eeg_page = reader.read_range_at_rate(0, 5, 10, 8.0).unwrap();
+		std::fs::remove_file(&path).ok();
+
+		assert_eq!(reader.max_sample_rate(), 8.0);
+		assert_eq!(reader.channel_len_at_rate(1, 8.0).unwrap(), resampled.len());
+		assert_eq!(page, resampled[5..15]);
+		assert_eq!(tail, resampled[20..]);
+		assert_eq!(eeg_page, reader.read_range(0, 5, 10).unwrap());
+	}
+
+	#[test]
+	fn read_range_truncates_at_end_and_rejects_unknown_channel() {
+		let path = std::env::temp_dir().join("codex_reader_bounds_test.bdf");
+		write_test_bdf(&path);
+		let mut reader = EdfFileReader::open(&path.to_string_lossy()).unwrap();
+
+		let tail = reader.read_range(0, 10, 100).unwrap();
+		let beyond = reader.read_range(0, 50, 4).unwrap();
+		let invalid = reader.read_range(2, 0, 4);
+		// 头长度字段远大于文件本身时不应按该长度分配缓冲区
+		let mut corrupted = std::fs::read(&path).unwrap();
+		corrupted[184..192].copy_from_slice(b"99999999");
+		std::fs::write(&path, &corrupted).unwrap();
+		let oversized_header = EdfFileReader::open(&path.to_string_lossy());
+		std::fs::remove_file(&path).ok();
+
+		assert_eq!(tail.len(), 2);
+		assert!(beyond.is_empty());
+		assert!(matches!(invalid, Err(EdfLoaderError::InvalidChannel(2))));
+		assert!(matches!(
+			oversized_header,
+			Err(EdfLoaderError::InvalidFormat(_))
+		));
+	}
+}
@@ -0,0 +1,67 @@
+//! 混合采样率通道的对齐工具
+//!
+//! EDF/BDF 允许每个信号有不同的每记录采样数，显示或联合分析时需要换算到同一采样率
+
+/// 生成通道的时间轴（秒），第 n 个样本位于 n / sample_rate
+///
+/// # Arguments
+/// * `sample_rate` - 通道采样率 (Hz)
+/// * `len` - 样本数量
+pub fn time_axis(sample_rate: f32, len: usize) -> Vec<f64> {
+	if sample_rate <= 0.0 {
+		return vec![0.0; len];
+	}
+	(0..len)
+		.map(|index| index as f64 / sample_rate as f64)
+		.collect()
+}
+
+/// 线性插值重采样到目标采样率，输出时长与输入相同
+///
+/// 适合把慢速通道（如 SpO2、呼吸）升采样到 EEG 采样率；降采样时不做抗混叠滤波，
+/// 需要保留频谱时请先低通滤波
+///
+/// # Arguments
+/// * `samples` - 原始样本
+/// * `source_rate` - 原采样率 (Hz)
+/// * `target_rate` - 目标采样率 (Hz)
+pub fn resample_linear(samples: &[f32], source_rate: f32, target_rate: f32) -> Vec<f32> {
+	if samples.is_empty() || source_rate <= 0.0 || target_rate <= 0.0 {
+		return Vec::new();
+	}
+	if source_rate == target_rate {
+		return samples.to_vec();
+	}
+
+	let ratio = source_rate as f64 / target_rate as f64;
+	let output_len = (samples.len() as f64 / ratio).round() as usize;
+	let last = samples.len() - 1;
+	(0..output_len)
+		.map(|index| {
+			let position = index as f64 * ratio;
+			let left = (position.floor() as usize).min(last);
+			let right = (left + 1).min(last);
+			let fraction = (position - left as f64).clamp(0.0, 1.0) as f32;
+			samples[left] + (samples[right] - samples[left]) * fraction
+		})
+		.collect()
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn resample_linear_interpolates_and_keeps_duration() {
+		let slow = [0.0, 2.0, 4.0];
+
+		let upsampled = resample_linear(&slow, 1.0, 4.0);
+		let downsampled = resample_linear(&[0.0, 1.0, 2.0, 3.0, 4.0, 5.0], 4.0, 2.0);
+
+		assert_eq!(upsampled.len(), 12);
+		assert_eq!(upsampled[..6], [0.0, 0.5, 1.0, 1.5, 2.0, 2.5]);
+		assert_eq!(upsampled[11], 4.0);
+		assert_eq!(downsampled, [0.0, 2.0, 4.0]);
+		assert_eq!(time_axis(4.0, 3), [0.0, 0.25, 0.5]);
+	}
+}
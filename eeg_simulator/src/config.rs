@@ -0,0 +1,129 @@
+//! 仿真信号配置。
+
+/// 背景噪声的谱形状。
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NoiseColor {
+	/// 平坦谱。
+	White,
+
+	/// 功率按 1/f 衰减，接近静息 EEG 背景。
+	Pink,
+
+	/// 功率按 1/f² 衰减，类似慢漂移。
+	Brown,
+}
+
+/// 事件相关电位中的一个高斯形成分，例如 P300。
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ErpComponent {
+	/// 峰潜伏期 (秒)，相对事件时刻。
+	pub latency_secs: f32,
+
+	/// 峰值 (µV)，负值表示负向成分。
+	pub amplitude: f32,
+
+	/// 高斯标准差 (秒)。
+	pub width_secs: f32,
+}
+
+/// 类 EEG 仿真信号配置，幅度单位均为 µV，发生率为每秒平均次数。
+///
+/// 幅度或发生率为 0 时关闭对应成分；所有随机成分都由 `seed` 决定，相同配置输出相同。
+#[derive(Debug, Clone, PartialEq)]
+pub struct EegSimulationConfig {
+	pub sample_rate: f32,
+	pub channel_count: usize,
+	pub seed: u64,
+
+	/// 背景噪声的颜色与标准差。
+	pub background_color: NoiseColor,
+	pub background_amplitude: f32,
+
+	/// α 爆发的中心频率、峰值、发生率与平均持续时间。
+	pub alpha_frequency_hz: f32,
+	pub alpha_amplitude: f32,
+	pub alpha_burst_rate_hz: f32,
+	pub alpha_burst_duration_secs: f32,
+
+	/// 各通道独立出现的尖波。
+	pub spike_rate_hz: f32,
+	pub spike_amplitude: f32,
+
+	/// 所有通道同时出现的眨眼伪迹，幅度随通道序号衰减。
+	pub blink_rate_hz: f32,
+	pub blink_amplitude: f32,
+
+	/// 工频干扰。
+	pub line_frequency_hz: f32,
+	pub line_amplitude: f32,
+
+	/// 每个事件之后叠加的 ERP 成分。
+	pub erp_components: Vec<ErpComponent>,
+
+	/// 锁定 ERP 的事件时刻 (秒)，从仿真起点开始计算。
+	pub event_onsets_secs: Vec<f64>,
+}
+
+impl Default for EegSimulationConfig {
+	fn default() -> Self {
+		Self {
+			sample_rate: 256.0,
+			channel_count: 4,
+			seed: 0,
+			background_color: NoiseColor::Pink,
+			background_amplitude: 10.0,
+			alpha_frequency_hz: 10.0,
+			alpha_amplitude: 20.0,
+			alpha_burst_rate_hz: 0.5,
+			alpha_burst_duration_secs: 1.5,
+			spike_rate_hz: 0.02,
+			spike_amplitude: 80.0,
+			blink_rate_hz: 0.2,
+			blink_amplitude: 80.0,
+			line_frequency_hz: 50.0,
+			line_amplitude: 5.0,
+			erp_components: vec![
+				ErpComponent {
+					latency_secs: 0.1,
+					amplitude: 3.0,
+					width_secs: 0.02,
+				},
+				ErpComponent {
+					latency_secs: 0.17,
+					amplitude: -5.0,
+					width_secs: 0.025,
+				},
+				ErpComponent {
+					latency_secs: 0.35,
+					amplitude: 8.0,
+					width_secs: 0.06,
+				},
+			],
+			event_onsets_secs: Vec::new(),
+		}
+	}
+}
+
+impl EegSimulationConfig {
+	/// 按给定采样率和通道数使用默认成分，关闭不低于奈奎斯特频率的 α 与工频振荡，
+	/// 便于为任意采样率的测试文件生成数据。
+	pub fn for_recording(sample_rate: f32, channel_count: usize) -> Self {
+		let nyquist = sample_rate / 2.0;
+		let defaults = Self::default();
+		Self {
+			sample_rate,
+			channel_count,
+			alpha_amplitude: if defaults.alpha_frequency_hz < nyquist {
+				defaults.alpha_amplitude
+			} else {
+				0.0
+			},
+			line_amplitude: if defaults.line_frequency_hz < nyquist {
+				defaults.line_amplitude
+			} else {
+				0.0
+			},
+			..defaults
+		}
+	}
+}
@@ -0,0 +1,8 @@
+use thiserror::Error;
+
+/// 仿真配置错误。
+#[derive(Debug, Error)]
+pub enum SimulationError {
+	#[error("参数无效: {0}")]
+	InvalidArgument(String),
+}
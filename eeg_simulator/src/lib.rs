@@ -0,0 +1,15 @@
+//! eeg_simulator - 带种子的类 EEG 仿真信号
+//!
+//! 1/f 背景、α 爆发、尖波、眨眼伪迹、工频干扰和锁定事件的 ERP 叠加而成，
+//! 实时波形页面、EDF/BDF 测试文件工具与信号处理测试共用同一份数据来源
+
+#![deny(clippy::unwrap_used)]
+#![deny(clippy::expect_used)]
+
+mod config;
+mod error;
+mod simulator;
+
+pub use config::{EegSimulationConfig, ErpComponent, NoiseColor};
+pub use error::SimulationError;
+pub use simulator::EegSimulator;
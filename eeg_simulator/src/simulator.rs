@@ -0,0 +1,445 @@
+//! 逐帧输出的类 EEG 仿真器。
+
+use std::f32::consts::{PI, TAU};
+
+use crate::{
+	config::{EegSimulationConfig, NoiseColor},
+	error::SimulationError,
+};
+
+/// Kellet 粉红噪声滤波器的极点与增益，单位方差白噪声输入时输出标准差约为 3.05。
+const PINK_POLES: [f32; 6] = [0.99886, 0.99332, 0.969, 0.8665, 0.55, -0.7616];
+const PINK_GAINS: [f32; 6] = [
+	0.055_517_9,
+	0.075_075_9,
+	0.153_852,
+	0.310_485_6,
+	0.532_952_2,
+	-0.016_898,
+];
+const PINK_DIRECT_GAIN: f32 = 0.5362;
+const PINK_DELAYED_GAIN: f32 = 0.115_926;
+const PINK_NORMALIZATION: f32 = 1.0 / 3.05;
+
+/// 泄漏积分得到褐色噪声，0.1 / sqrt(1 - 0.995²) ≈ 1，输出近似单位方差。
+const BROWN_LEAK: f32 = 0.995;
+const BROWN_GAIN: f32 = 0.1;
+
+/// 滤波器状态预热的样本数，使第一个输出样本已处于平稳状态。
+const BACKGROUND_WARMUP_SAMPLES: usize = 4096;
+
+/// 眨眼幅度在相邻通道间的衰减比例，通道视为由前额向枕区排列。
+const BLINK_CHANNEL_DECAY: f32 = 0.6;
+const BLINK_DURATION_SECS: f32 = 0.4;
+const SPIKE_DURATION_SECS: f32 = 0.25;
+
+/// α 爆发的频率抖动 (Hz) 与持续时间的相对抖动。
+const ALPHA_FREQUENCY_JITTER: f32 = 0.5;
+const ALPHA_DURATION_JITTER: f32 = 0.5;
+
+/// 逐帧输出的类 EEG 仿真器，每帧包含所有通道的一个样本。
+///
+/// 信号由 1/f 背景、α 爆发、尖波、眨眼伪迹、工频干扰和锁定事件的 ERP 叠加而成，
+/// 既可逐帧驱动实时波形页面，也可通过 [`EegSimulator::generate`] 一次生成整段测试数据。
+#[derive(Debug, Clone)]
+pub struct EegSimulator {
+	config: EegSimulationConfig,
+	rng: SeedRng,
+	sample_index: u64,
+	channels: Vec<ChannelState>,
+	spike_template: Vec<f32>,
+	blink_template: Vec<f32>,
+	erp_template: Vec<f32>,
+	blinks: Vec<usize>,
+	event_samples: Vec<u64>,
+	next_event: usize,
+	responses: Vec<usize>,
+}
+
+impl EegSimulator {
+	pub fn new(config: EegSimulationConfig) -> Result<Self, SimulationError> {
+		validate_simulation_config(&config)?;
+		Ok(Self::build(config))
+	}
+
+	pub fn config(&self) -> &EegSimulationConfig {
+		&self.config
+	}
+
+	pub fn channel_count(&self) -> usize {
+		self.channels.len()
+	}
+
+	/// 下一帧对应的时刻 (秒)。
+	pub fn current_time_secs(&self) -> f64 {
+		self.sample_index as f64 / self.config.sample_rate as f64
+	}
+
+	/// 回到起点，之后的输出与新建仿真器完全相同。
+	pub fn reset(&mut self) {
+		*self = Self::build(std::mem::take(&mut self.config));
+	}
+
+	/// 生成下一帧，长度等于通道数。
+	pub fn next_frame(&mut self) -> Vec<f32> {
+		let sample_rate = self.config.sample_rate;
+		if self.rng.chance(self.config.blink_rate_hz / sample_rate) {
+			self.blinks.push(0);
+		}
+		while self
+			.event_samples
+			.get(self.next_event)
+			.is_some_and(|onset| *onset <= self.sample_index)
+		{
+			self.responses.push(0);
+			self.next_event += 1;
+		}
+
+		let time = self.current_time_secs();
+		let line = self.config.line_amplitude
+			* (std::f64::consts::TAU * self.config.line_frequency_hz as f64 * time).sin() as f32;
+		let response = advance_instances(&mut self.responses, &self.erp_template);
+		let blink =
+			self.config.blink_amplitude * advance_instances(&mut self.blinks, &self.blink_template);
+
+		let frame = self
+			.channels
+			.iter_mut()
+			.map(|channel| {
+				line + response
+					+ blink * channel.blink_gain
+					+ channel.next_sample(&mut self.rng, &self.config, &self.spike_template)
+			})
+			.collect();
+		self.sample_index += 1;
+		frame
+	}
+
+	/// 连续生成若干帧，返回 `[通道][样本]`。
+	pub fn generate(&mut self, sample_count: usize) -> Vec<Vec<f32>> {
+		let mut channels = vec![Vec::with_capacity(sample_count); self.channels.len()];
+		for _ in 0..sample_count {
+			for (channel, value) in channels.iter_mut().zip(self.next_frame()) {
+				channel.push(value);
+			}
+		}
+		channels
+	}
+
+	fn build(config: EegSimulationConfig) -> Self {
+		let sample_rate = config.sample_rate;
+		let mut rng = SeedRng { state: config.seed };
+		let channels = (0..config.channel_count)
+			.map(|index| {
+				let mut channel = ChannelState {
+					blink_gain: BLINK_CHANNEL_DECAY.powi(index as i32),
+					..ChannelState::default()
+				};
+				for _ in 0..BACKGROUND_WARMUP_SAMPLES {
+					channel.next_background(rng.gaussian(), config.background_color);
+				}
+				channel
+			})
+			.collect();
+
+		let spike_template = build_template(sample_rate, SPIKE_DURATION_SECS, |time| {
+			-gaussian_bump(time, 0.03, 0.008) + 0.35 * gaussian_bump(time, 0.12, 0.04)
+		});
+		let blink_template = build_template(sample_rate, BLINK_DURATION_SECS, |time| {
+			(PI * time / BLINK_DURATION_SECS).sin().powi(2)
+		});
+		let erp_duration = config
+			.erp_components
+			.iter()
+			.map(|component| component.latency_secs + 4.0 * component.width_secs)
+			.fold(0.0, f32::max);
+		let erp_template = build_template(sample_rate, erp_duration, |time| {
+			config
+				.erp_components
+				.iter()
+				.map(|component| {
+					component.amplitude
+						* gaussian_bump(time, component.latency_secs, component.width_secs)
+				})
+				.sum()
+		});
+
+		let mut event_samples: Vec<u64> = config
+			.event_onsets_secs
+			.iter()
+			.filter(|onset| **onset >= 0.0)
+			.map(|onset| (onset * sample_rate as f64).round() as u64)
+			.collect();
+		event_samples.sort_unstable();
+
+		Self {
+			config,
+			rng,
+			sample_index: 0,
+			channels,
+			spike_template,
+			blink_template,
+			erp_template,
+			blinks: Vec::new(),
+			event_samples,
+			next_event: 0,
+			responses: Vec::new(),
+		}
+	}
+}
+
+fn validate_simulation_config(config: &EegSimulationConfig) -> Result<(), SimulationError> {
+	if config.sample_rate <= 0.0 {
+		return Err(SimulationError::InvalidArgument(
+			"采样率必须大于 0".to_string(),
+		));
+	}
+	if config.channel_count == 0 {
+		return Err(SimulationError::InvalidArgument(
+			"通道数必须大于 0".to_string(),
+		));
+	}
+	let rates = [
+		config.alpha_burst_rate_hz,
+		config.spike_rate_hz,
+		config.blink_rate_hz,
+	];
+	if rates.iter().any(|rate| !(rate.is_finite() && *rate >= 0.0)) {
+		return Err(SimulationError::InvalidArgument(
+			"发生率必须为非负有限值".to_string(),
+		));
+	}
+
+	let nyquist = config.sample_rate / 2.0;
+	let oscillations = [
+		("α", config.alpha_frequency_hz, config.alpha_amplitude),
+		("工频", config.line_frequency_hz, config.line_amplitude),
+	];
+	for (name, frequency, amplitude) in oscillations {
+		if amplitude != 0.0 && !(frequency > 0.0 && frequency < nyquist) {
+			return Err(SimulationError::InvalidArgument(format!(
+				"{name}频率 {frequency} Hz 必须位于 (0, {nyquist}) Hz"
+			)));
+		}
+	}
+	if config.alpha_amplitude != 0.0 && config.alpha_burst_duration_secs <= 0.0 {
+		return Err(SimulationError::InvalidArgument(
+			"α 爆发持续时间必须大于 0".to_string(),
+		));
+	}
+	if config
+		.erp_components
+		.iter()
+		.any(|component| component.width_secs <= 0.0 || component.latency_secs < 0.0)
+	{
+		return Err(SimulationError::InvalidArgument(
+			"ERP 成分宽度必须大于 0 且潜伏期不能为负".to_string(),
+		));
+	}
+	Ok(())
+}
+
+#[derive(Debug, Clone, Default)]
+struct ChannelState {
+	pink: [f32; 6],
+	previous_white: f32,
+	brown: f32,
+	blink_gain: f32,
+	alpha: Option<AlphaBurst>,
+	spikes: Vec<usize>,
+}
+
+impl ChannelState {
+	fn next_sample(
+		&mut self,
+		rng: &mut SeedRng,
+		config: &EegSimulationConfig,
+		spike_template: &[f32],
+	) -> f32 {
+		let background = self.next_background(rng.gaussian(), config.background_color);
+
+		if self.alpha.is_none() && rng.chance(config.alpha_burst_rate_hz / config.sample_rate) {
+			self.alpha = Some(AlphaBurst::start(rng, config));
+		}
+		let alpha = match self.alpha.as_mut() {
+			Some(burst) => {
+				let value = burst.advance();
+				if burst.position >= burst.length {
+					self.alpha = None;
+				}
+				value
+			}
+			None => 0.0,
+		};
+
+		if rng.chance(config.spike_rate_hz / config.sample_rate) {
+			self.spikes.push(0);
+		}
+		let spike = advance_instances(&mut self.spikes, spike_template);
+
+		config.background_amplitude * background
+			+ config.alpha_amplitude * alpha
+			+ config.spike_amplitude * spike
+	}
+
+	/// 由单位方差白噪声得到对应颜色的单位方差背景。
+	fn next_background(&mut self, white: f32, color: NoiseColor) -> f32 {
+		match color {
+			NoiseColor::White => white,
+			NoiseColor::Pink => {
+				for ((state, pole), gain) in self.pink.iter_mut().zip(PINK_POLES).zip(PINK_GAINS) {
+					*state = pole * *state + gain * white;
+				}
+				let value = self.pink.iter().sum::<f32>()
+					+ PINK_DIRECT_GAIN * white
+					+ PINK_DELAYED_GAIN * self.previous_white;
+				self.previous_white = white;
+				value * PINK_NORMALIZATION
+			}
+			NoiseColor::Brown => {
+				self.brown = BROWN_LEAK * self.brown + BROWN_GAIN * white;
+				self.brown
+			}
+		}
+	}
+}
+
+/// 一次 α 爆发，包络为 sin² 窗，频率与持续时间带随机抖动。
+#[derive(Debug, Clone, Copy)]
+struct AlphaBurst {
+	position: usize,
+	length: usize,
+	phase: f32,
+	phase_step: f32,
+}
+
+impl AlphaBurst {
+	fn start(rng: &mut SeedRng, config: &EegSimulationConfig) -> Self {
+		let duration =
+			config.alpha_burst_duration_secs * (1.0 + ALPHA_DURATION_JITTER * rng.symmetric());
+		let frequency = config.alpha_frequency_hz + ALPHA_FREQUENCY_JITTER * rng.symmetric();
+		Self {
+			position: 0,
+			length: ((duration * config.sample_rate).round() as usize).max(1),
+			phase: PI * rng.symmetric(),
+			phase_step: TAU * frequency / config.sample_rate,
+		}
+	}
+
+	fn advance(&mut self) -> f32 {
+		let envelope = (PI * self.position as f32 / self.length as f32)
+			.sin()
+			.powi(2);
+		let value = envelope * self.phase.sin();
+		self.phase = (self.phase + self.phase_step) % TAU;
+		self.position += 1;
+		value
+	}
+}
+
+/// SplitMix64 伪随机数，同一种子在各平台上输出一致。
+#[derive(Debug, Clone)]
+struct SeedRng {
+	state: u64,
+}
+
+impl SeedRng {
+	fn next_u64(&mut self) -> u64 {
+		self.state = self.state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+		let mut mixed = self.state;
+		mixed = (mixed ^ (mixed >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+		mixed = (mixed ^ (mixed >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+		mixed ^ (mixed >> 31)
+	}
+
+	/// [0, 1) 内的均匀随机数。
+	fn uniform(&mut self) -> f32 {
+		(self.next_u64() >> 40) as f32 / (1u64 << 24) as f32
+	}
+
+	/// [-1, 1) 内的均匀随机数。
+	fn symmetric(&mut self) -> f32 {
+		self.uniform() * 2.0 - 1.0
+	}
+
+	fn chance(&mut self, probability: f32) -> bool {
+		self.uniform() < probability
+	}
+
+	/// Box-Muller 变换得到标准正态随机数。
+	fn gaussian(&mut self) -> f32 {
+		let radius = (-2.0 * (1.0 - self.uniform()).ln()).sqrt();
+		radius * (TAU * self.uniform()).cos()
+	}
+}
+
+/// 推进所有正在播放的模板实例并返回它们在当前样本的叠加值，播放完的实例被移除。
+fn advance_instances(offsets: &mut Vec<usize>, template: &[f32]) -> f32 {
+	let mut value = 0.0;
+	offsets.retain_mut(|offset| {
+		let Some(sample) = template.get(*offset) else {
+			return false;
+		};
+		value += sample;
+		*offset += 1;
+		*offset < template.len()
+	});
+	value
+}
+
+fn build_template(sample_rate: f32, duration_secs: f32, shape: impl Fn(f32) -> f32) -> Vec<f32> {
+	let length = (duration_secs * sample_rate).ceil() as usize;
+	(0..length)
+		.map(|index| shape(index as f32 / sample_rate))
+		.collect()
+}
+
+fn gaussian_bump(time: f32, center: f32, width: f32) -> f32 {
+	(-0.5 * ((time - center) / width).powi(2)).exp()
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn simulator_should_be_reproducible_for_a_seed() {
+		let config = EegSimulationConfig {
+			seed: 11,
+			..EegSimulationConfig::default()
+		};
+		let mut simulator = EegSimulator::new(config.clone())
+			.unwrap_or_else(|error| panic!("创建仿真器失败: {error}"));
+		let first = simulator.generate(512);
+		simulator.reset();
+		let other_seed = EegSimulator::new(EegSimulationConfig { seed: 12, ..config })
+			.unwrap_or_else(|error| panic!("创建仿真器失败: {error}"))
+			.generate(512);
+
+		assert_eq!(first.len(), 4);
+		assert!(first.iter().all(|channel| channel.len() == 512));
+		assert_eq!(simulator.generate(512), first);
+		assert_ne!(other_seed, first);
+	}
+
+	#[test]
+	fn recording_config_should_drop_oscillations_above_nyquist() {
+		let low_rate = EegSimulationConfig::for_recording(64.0, 2);
+		let high_rate = EegSimulationConfig::for_recording(256.0, 2);
+
+		assert_eq!(low_rate.line_amplitude, 0.0);
+		assert!(low_rate.alpha_amplitude > 0.0);
+		assert_eq!(
+			high_rate.line_amplitude,
+			EegSimulationConfig::default().line_amplitude
+		);
+		assert!(EegSimulator::new(low_rate).is_ok());
+		assert!(matches!(
+			EegSimulator::new(EegSimulationConfig {
+				line_frequency_hz: 200.0,
+				..EegSimulationConfig::default()
+			}),
+			Err(SimulationError::InvalidArgument(_))
+		));
+	}
+}
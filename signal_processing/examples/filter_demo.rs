@@ -2,7 +2,7 @@ use std::{fs, path::PathBuf};
 
 use plotters::prelude::*;
 use signal_processing::{
-	CompositeComponent, FilterKind, FirDesignConfig, apply_fir, design_fir,
+	CompositeComponent, FilterKind, FirDesignConfig, WindowKind, apply_fir, design_fir,
 	generate_composite_signal,
 };
 
@@ -28,6 +28,7 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
 		filter_kind: FilterKind::LowPass,
 		cutoff_hz: vec![20.0],
 		tap_count: 101,
+		window: WindowKind::Hamming,
 	})?;
 	let filtered = apply_fir(&signal, &coeffs)?;
 
@@ -0,0 +1,484 @@
+use crate::{
+	error::SignalProcessError,
+	multichannel::{ChannelView, MultiChannelSignal},
+	spectrum::compute_power_spectrum_with_config,
+	types::{
+		ArtifactConfig, ArtifactInterval, ArtifactKind, ArtifactReport, BadChannel,
+		BadChannelReason, SignalBuffer, WelchSpectrumConfig,
+	},
+};
+
+/// 计算肌电功率占比时忽略的低频漂移 (Hz)。
+const MUSCLE_TOTAL_LOW_HZ: f32 = 1.0;
+
+/// 中位数绝对偏差换算为正态标准差的系数。
+const MAD_TO_SIGMA: f32 = 0.6745;
+
+/// 判定坏导至少需要的通道数。
+const MIN_CHANNELS_FOR_BAD_CHANNEL: usize = 3;
+
+/// 单个分段上触发的伪迹及其指标值。
+type EpochFlags = Vec<(ArtifactKind, f32)>;
+
+/// 按分段扫描多通道信号，返回伪迹区间与坏导列表。
+pub fn detect_artifacts(
+	signal: &MultiChannelSignal,
+	config: &ArtifactConfig,
+) -> Result<ArtifactReport, SignalProcessError> {
+	validate_artifact_config(config)?;
+
+	let mut report = ArtifactReport::default();
+	for (channel_index, channel) in signal.channels().enumerate() {
+		if channel.samples.is_empty() {
+			return Err(SignalProcessError::EmptyInput);
+		}
+
+		let epoch_length = epoch_length(channel.info.sample_rate, config.epoch_secs);
+		let channel_range = min_max(channel.samples);
+		let epoch_flags = channel
+			.samples
+			.chunks(epoch_length)
+			.map(|epoch| scan_epoch(epoch, &channel, channel_range, config))
+			.collect::<Result<Vec<_>, SignalProcessError>>()?;
+		let flagged_fraction = epoch_flags.iter().filter(|flags| !flags.is_empty()).count() as f32
+			/ epoch_flags.len() as f32;
+
+		let epoch_secs = epoch_length as f64 / channel.info.sample_rate as f64;
+		let channel_end_secs = channel.samples.len() as f64 / channel.info.sample_rate as f64;
+		for (kind, first_epoch, last_epoch, value) in merge_epoch_flags(&epoch_flags) {
+			report.intervals.push(ArtifactInterval {
+				channel_index,
+				channel_label: channel.info.label.clone(),
+				kind,
+				start_secs: signal.start_time_secs() + first_epoch as f64 * epoch_secs,
+				end_secs: signal.start_time_secs()
+					+ ((last_epoch + 1) as f64 * epoch_secs).min(channel_end_secs),
+				value,
+			});
+		}
+		if flagged_fraction > config.max_artifact_fraction {
+			report.bad_channels.push(BadChannel {
+				channel_index,
+				channel_label: channel.info.label.clone(),
+				reason: BadChannelReason::ExcessiveArtifacts,
+				value: flagged_fraction,
+			});
+		}
+	}
+
+	report
+		.bad_channels
+		.extend(detect_variance_outliers(signal, config));
+	report
+		.bad_channels
+		.extend(detect_uncorrelated_channels(signal, config));
+	report
+		.bad_channels
+		.sort_by_key(|bad_channel| bad_channel.channel_index);
+	Ok(report)
+}
+
+fn epoch_length(sample_rate: f32, epoch_secs: f32) -> usize {
+	((sample_rate * epoch_secs).round() as usize).max(1)
+}
+
+/// 检查单个分段，可能同时触发多种伪迹；`channel_range` 为整条通道的 (最小值, 最大值)。
+fn scan_epoch(
+	epoch: &[f32],
+	channel: &ChannelView<'_>,
+	channel_range: (f32, f32),
+	config: &ArtifactConfig,
+) -> Result<EpochFlags, SignalProcessError> {
+	let mut flags = Vec::new();
+	let (minimum, maximum) = min_max(epoch);
+	let peak_to_peak = maximum - minimum;
+	if peak_to_peak < config.flat_peak_to_peak {
+		flags.push((ArtifactKind::Flat, peak_to_peak));
+		return Ok(flags);
+	}
+	if peak_to_peak > config.max_peak_to_peak {
+		flags.push((ArtifactKind::AmplitudeOutlier, peak_to_peak));
+	}
+
+	let run = longest_extreme_run(epoch, channel_range);
+	if run >= config.clipping_run_length {
+		flags.push((ArtifactKind::Clipping, run as f32));
+	}
+
+	if let Some(ratio) = muscle_power_ratio(epoch, channel.info.sample_rate, config)?
+		&& ratio > config.muscle_power_ratio
+	{
+		flags.push((ArtifactKind::MuscleNoise, ratio));
+	}
+	Ok(flags)
+}
+
+fn min_max(samples: &[f32]) -> (f32, f32) {
+	samples.iter().fold(
+		(f32::INFINITY, f32::NEG_INFINITY),
+		|(minimum, maximum), value| (minimum.min(*value), maximum.max(*value)),
+	)
+}
+
+/// 分段内连续停在通道全局极值上的最长样本数。
+fn longest_extreme_run(epoch: &[f32], (minimum, maximum): (f32, f32)) -> usize {
+	let range = maximum - minimum;
+	if range <= 0.0 {
+		return 0;
+	}
+
+	let tolerance = range * 1e-6;
+	let mut longest = 0;
+	let mut current = 0;
+	for value in epoch {
+		if (value - maximum).abs() <= tolerance || (value - minimum).abs() <= tolerance {
+			current += 1;
+			longest = longest.max(current);
+		} else {
+			current = 0;
+		}
+	}
+	longest
+}
+
+/// 用 Welch 功率谱计算肌电频段功率占比，分段太短或频段超出 Nyquist 时返回 None。
+fn muscle_power_ratio(
+	epoch: &[f32],
+	sample_rate: f32,
+	config: &ArtifactConfig,
+) -> Result<Option<f32>, SignalProcessError> {
+	let segment_length = epoch.len().min(sample_rate.round() as usize);
+	if segment_length < 8 || config.muscle_band_hz[0] >= sample_rate / 2.0 {
+		return Ok(None);
+	}
+
+	let spectrum = compute_power_spectrum_with_config(
+		&SignalBuffer {
+			sample_rate,
+			samples: epoch.to_vec(),
+		},
+		WelchSpectrumConfig {
+			segment_length,
+			overlap_length: segment_length / 2,
+		},
+	)?;
+	let [band_low, band_high] = config.muscle_band_hz;
+	let (band_power, total_power) = spectrum
+		.iter()
+		.filter(|point| point.frequency >= MUSCLE_TOTAL_LOW_HZ)
+		.fold((0.0, 0.0), |(band, total), point| {
+			let in_band = point.frequency >= band_low && point.frequency <= band_high;
+			(
+				band + if in_band { point.value } else { 0.0 },
+				total + point.value,
+			)
+		});
+	if total_power <= 0.0 {
+		return Ok(None);
+	}
+	Ok(Some(band_power / total_power))
+}
+
+/// 把连续分段上的同类伪迹合并为 (类别, 首分段, 末分段, 最极端指标)。
+fn merge_epoch_flags(epoch_flags: &[EpochFlags]) -> Vec<(ArtifactKind, usize, usize, f32)> {
+	let mut merged: Vec<(ArtifactKind, usize, usize, f32)> = Vec::new();
+	for (epoch_index, flags) in epoch_flags.iter().enumerate() {
+		for (kind, value) in flags.iter().copied() {
+			let open = merged
+				.iter_mut()
+				.rev()
+				.find(|(open_kind, _, last, _)| *open_kind == kind && last + 1 == epoch_index);
+			match open {
+				Some((_, _, last, extreme)) => {
+					*last = epoch_index;
+					*extreme = if kind == ArtifactKind::Flat {
+						extreme.min(value)
+					} else {
+						extreme.max(value)
+					};
+				}
+				None => merged.push((kind, epoch_index, epoch_index, value)),
+			}
+		}
+	}
+	merged.sort_by_key(|(_, first, _, _)| *first);
+	merged
+}
+
+/// 对数方差的稳健 z 分数超过阈值的通道。
+fn detect_variance_outliers(
+	signal: &MultiChannelSignal,
+	config: &ArtifactConfig,
+) -> Vec<BadChannel> {
+	if signal.channel_count() < MIN_CHANNELS_FOR_BAD_CHANNEL {
+		return Vec::new();
+	}
+
+	let log_variances = signal
+		.channels()
+		.map(|channel| variance(channel.samples).max(f32::MIN_POSITIVE).ln())
+		.collect::<Vec<_>>();
+	let center = median(&log_variances);
+	let deviations = log_variances
+		.iter()
+		.map(|value| (value - center).abs())
+		.collect::<Vec<_>>();
+	let spread = median(&deviations);
+	if spread <= 0.0 {
+		return Vec::new();
+	}
+
+	signal
+		.channels()
+		.zip(log_variances)
+		.enumerate()
+		.filter_map(|(channel_index, (channel, log_variance))| {
+			let z_score = MAD_TO_SIGMA * (log_variance - center) / spread;
+			(z_score.abs() > config.variance_z_threshold).then(|| BadChannel {
+				channel_index,
+				channel_label: channel.info.label.clone(),
+				reason: if z_score > 0.0 {
+					BadChannelReason::HighVariance
+				} else {
+					BadChannelReason::LowVariance
+				},
+				value: z_score,
+			})
+		})
+		.collect()
+}
+
+/// 与同采样率其他通道相关系数绝对值的中位数过低的通道。
+fn detect_uncorrelated_channels(
+	signal: &MultiChannelSignal,
+	config: &ArtifactConfig,
+) -> Vec<BadChannel> {
+	let channels = signal.channels().collect::<Vec<_>>();
+	channels
+		.iter()
+		.enumerate()
+		.filter_map(|(channel_index, channel)| {
+			let correlations = channels
+				.iter()
+				.enumerate()
+				.filter(|(other_index, other)| {
+					*other_index != channel_index
+						&& other.info.sample_rate == channel.info.sample_rate
+				})
+				.map(|(_, other)| correlation(channel.samples, other.samples).abs())
+				.collect::<Vec<_>>();
+			if correlations.len() + 1 < MIN_CHANNELS_FOR_BAD_CHANNEL {
+				return None;
+			}
+
+			let typical = median(&correlations);
+			(typical < config.min_channel_correlation).then(|| BadChannel {
+				channel_index,
+				channel_label: channel.info.label.clone(),
+				reason: BadChannelReason::LowCorrelation,
+				value: typical,
+			})
+		})
+		.collect()
+}
+
+fn variance(samples: &[f32]) -> f32 {
+	let mean = samples.iter().sum::<f32>() / samples.len() as f32;
+	samples
+		.iter()
+		.map(|value| (value - mean) * (value - mean))
+		.sum::<f32>()
+		/ samples.len() as f32
+}
+
+/// 按较短通道的长度计算 Pearson 相关系数，任一通道为常数时记为 0。
+fn correlation(left: &[f32], right: &[f32]) -> f32 {
+	let length = left.len().min(right.len());
+	let (left, right) = (&left[..length], &right[..length]);
+	let left_mean = left.iter().sum::<f32>() / length as f32;
+	let right_mean = right.iter().sum::<f32>() / length as f32;
+	let (covariance, left_power, right_power) = left.iter().zip(right.iter()).fold(
+		(0.0, 0.0, 0.0),
+		|(covariance, left_power, right_power), (left, right)| {
+			let left = left - left_mean;
+			let right = right - right_mean;
+			(
+				covariance + left * right,
+				left_power + left * left,
+				right_power + right * right,
+			)
+		},
+	);
+	let denominator = (left_power * right_power).sqrt();
+	if denominator <= 0.0 {
+		return 0.0;
+	}
+	covariance / denominator
+}
+
+fn median(values: &[f32]) -> f32 {
+	let mut sorted = values.to_vec();
+	sorted.sort_by(f32::total_cmp);
+	let middle = sorted.len() / 2;
+	if sorted.len().is_multiple_of(2) {
+		(sorted[middle - 1] + sorted[middle]) / 2.0
+	} else {
+		sorted[middle]
+	}
+}
+
+fn validate_artifact_config(config: &ArtifactConfig) -> Result<(), SignalProcessError> {
+	if config.epoch_secs <= 0.0 {
+		return Err(SignalProcessError::InvalidArgument(
+			"分段时长必须大于 0".to_string(),
+		));
+	}
+	if config.flat_peak_to_peak < 0.0 || config.max_peak_to_peak <= config.flat_peak_to_peak {
+		return Err(SignalProcessError::InvalidArgument(
+			"峰峰值阈值必须满足 0 <= flat < max".to_string(),
+		));
+	}
+	if config.clipping_run_length < 2 {
+		return Err(SignalProcessError::InvalidArgument(
+			"削顶判定的连续样本数至少为 2".to_string(),
+		));
+	}
+	if !(0.0 <= config.muscle_band_hz[0] && config.muscle_band_hz[0] < config.muscle_band_hz[1]) {
+		return Err(SignalProcessError::InvalidArgument(
+			"肌电频段必须满足 0 <= low < high".to_string(),
+		));
+	}
+	if !(0.0..=1.0).contains(&config.max_artifact_fraction) {
+		return Err(SignalProcessError::InvalidArgument(
+			"伪迹分段比例阈值必须位于 [0, 1] 内".to_string(),
+		));
+	}
+	Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+	use std::f32::consts::TAU;
+
+	use super::*;
+	use crate::{EegSimulationConfig, generate_eeg_like_signal};
+
+	fn sine_channel(frequency: f32, amplitude: f32) -> SignalBuffer {
+		SignalBuffer {
+			sample_rate: 256.0,
+			samples: (0..1024)
+				.map(|index| amplitude * (TAU * frequency * index as f32 / 256.0).sin())
+				.collect(),
+		}
+	}
+
+	fn kinds_on(report: &ArtifactReport, channel_index: usize) -> Vec<(ArtifactKind, f64, f64)> {
+		report
+			.intervals
+			.iter()
+			.filter(|interval| interval.channel_index == channel_index)
+			.map(|interval| (interval.kind, interval.start_secs, interval.end_secs))
+			.collect()
+	}
+
+	#[test]
+	fn epoch_artifacts_should_be_labeled_and_merged() {
+		let clean = sine_channel(10.0, 20.0);
+		let mut flat = sine_channel(10.0, 20.0);
+		flat.samples[256..768].fill(0.0);
+		let mut spiky = sine_channel(10.0, 20.0);
+		spiky.samples[600] = 400.0;
+		let mut clipped = sine_channel(10.0, 20.0);
+		for sample in clipped.samples[768..].iter_mut() {
+			*sample = (*sample * 3.0).min(40.0);
+		}
+		let mut muscle = sine_channel(10.0, 20.0);
+		for (index, sample) in muscle.samples[..256].iter_mut().enumerate() {
+			*sample += 40.0 * (TAU * 35.0 * index as f32 / 256.0).sin();
+		}
+		let signal = MultiChannelSignal::from_signal_buffers(
+			vec![clean, flat, spiky, clipped, muscle],
+			&["Fp1", "Fp2", "C3", "C4", "T3"],
+			"uV",
+		)
+		.unwrap_or_else(|error| panic!("多通道信号创建失败: {error}"));
+
+		let report = detect_artifacts(&signal, &ArtifactConfig::default())
+			.unwrap_or_else(|error| panic!("伪迹检测失败: {error}"));
+
+		assert!(kinds_on(&report, 0).is_empty());
+		assert_eq!(kinds_on(&report, 1), [(ArtifactKind::Flat, 1.0, 3.0)]);
+		assert_eq!(
+			kinds_on(&report, 2),
+			[(ArtifactKind::AmplitudeOutlier, 2.0, 3.0)]
+		);
+		assert!(kinds_on(&report, 3).contains(&(ArtifactKind::Clipping, 3.0, 4.0)));
+		assert_eq!(
+			kinds_on(&report, 4),
+			[(ArtifactKind::MuscleNoise, 0.0, 1.0)]
+		);
+	}
+
+	#[test]
+	fn bad_channels_should_report_variance_and_correlation() {
+		let mut channels = [18.0, 19.0, 20.0, 21.0, 22.0]
+			.into_iter()
+			.map(|amplitude| sine_channel(10.0, amplitude))
+			.collect::<Vec<_>>();
+		channels.push(sine_channel(10.0, 0.02));
+		channels.push(sine_channel(3.7, 20.0));
+		let signal = MultiChannelSignal::from_signal_buffers(
+			channels,
+			&["F3", "F4", "C3", "C4", "Cz", "P3", "P4"],
+			"uV",
+		)
+		.unwrap_or_else(|error| panic!("多通道信号创建失败: {error}"));
+
+		let report = detect_artifacts(&signal, &ArtifactConfig::default())
+			.unwrap_or_else(|error| panic!("伪迹检测失败: {error}"));
+		let reasons = report
+			.bad_channels
+			.iter()
+			.map(|bad_channel| (bad_channel.channel_label.as_str(), bad_channel.reason))
+			.collect::<Vec<_>>();
+
+		assert!(reasons.contains(&("P3", BadChannelReason::LowVariance)));
+		assert!(reasons.contains(&("P3", BadChannelReason::ExcessiveArtifacts)));
+		assert!(reasons.contains(&("P4", BadChannelReason::LowCorrelation)));
+		assert!(
+			reasons
+				.iter()
+				.all(|(label, _)| *label == "P3" || *label == "P4")
+		);
+	}
+
+	#[test]
+	fn simulated_blinks_should_be_flagged_on_frontal_channel() {
+		let config = EegSimulationConfig {
+			channel_count: 4,
+			blink_rate_hz: 0.3,
+			blink_amplitude: 300.0,
+			spike_rate_hz: 0.0,
+			..EegSimulationConfig::default()
+		};
+		let outliers_on_first_channel = |config: &EegSimulationConfig| {
+			let signal = generate_eeg_like_signal(config, 40.0)
+				.unwrap_or_else(|error| panic!("生成仿真 EEG 失败: {error}"));
+			let report = detect_artifacts(&signal, &ArtifactConfig::default())
+				.unwrap_or_else(|error| panic!("伪迹检测失败: {error}"));
+			kinds_on(&report, 0)
+				.into_iter()
+				.filter(|(kind, _, _)| *kind == ArtifactKind::AmplitudeOutlier)
+				.count()
+		};
+
+		assert!(outliers_on_first_channel(&config) > 0);
+		assert_eq!(
+			outliers_on_first_channel(&EegSimulationConfig {
+				blink_amplitude: 0.0,
+				..config
+			}),
+			0
+		);
+	}
+}
@@ -0,0 +1,369 @@
+use crate::{
+	error::SignalProcessError,
+	spectrum::compute_power_spectrum_with_config,
+	types::{
+		BandPowerConfig, BandPowerFeatures, BandPowerValue, IntegrationMethod, SignalBuffer,
+		SpectrumPoint,
+	},
+};
+
+/// 频点落在频段边界上的容差 (Hz)。
+const FREQUENCY_TOLERANCE: f32 = 1e-4;
+
+/// 基于 Welch 功率谱计算单通道的频段功率特征。
+pub fn compute_band_power(
+	signal: &SignalBuffer,
+	config: &BandPowerConfig,
+) -> Result<BandPowerFeatures, SignalProcessError> {
+	let spectrum = compute_power_spectrum_with_config(signal, config.welch)?;
+	compute_band_power_from_spectrum(&spectrum, config)
+}
+
+/// 逐通道计算频段功率特征，输出顺序与输入通道一致。
+pub fn compute_band_power_per_channel(
+	channels: &[SignalBuffer],
+	config: &BandPowerConfig,
+) -> Result<Vec<BandPowerFeatures>, SignalProcessError> {
+	if channels.is_empty() {
+		return Err(SignalProcessError::EmptyInput);
+	}
+	channels
+		.iter()
+		.map(|channel| compute_band_power(channel, config))
+		.collect()
+}
+
+/// 在已有的单边功率谱上计算频段功率特征。
+pub fn compute_band_power_from_spectrum(
+	spectrum: &[SpectrumPoint],
+	config: &BandPowerConfig,
+) -> Result<BandPowerFeatures, SignalProcessError> {
+	validate_band_power_config(config)?;
+	if spectrum.len() < 2 {
+		return Err(SignalProcessError::EmptyInput);
+	}
+
+	let [total_low, total_high] = config.total_range_hz;
+	let total_power = integrate_band(spectrum, total_low, total_high, config.integration)?;
+	let bands = config
+		.bands
+		.iter()
+		.map(|band| {
+			let absolute_power =
+				integrate_band(spectrum, band.low_hz, band.high_hz, config.integration)?;
+			Ok(BandPowerValue {
+				name: band.name.clone(),
+				low_hz: band.low_hz,
+				high_hz: band.high_hz,
+				absolute_power,
+				relative_power: if total_power > 0.0 {
+					absolute_power / total_power
+				} else {
+					0.0
+				},
+			})
+		})
+		.collect::<Result<Vec<_>, SignalProcessError>>()?;
+
+	Ok(BandPowerFeatures {
+		bands,
+		total_power,
+		spectral_edge_frequency: spectral_edge_frequency(
+			spectrum,
+			config.total_range_hz,
+			config.spectral_edge_ratio,
+		)?,
+		peak_alpha_frequency: peak_frequency(spectrum, config.alpha_range_hz),
+	})
+}
+
+/// 对 [low, high] 内的频点积分功率谱密度。
+///
+/// 频点需等间隔，这与 Welch 输出一致。
+fn integrate_band(
+	spectrum: &[SpectrumPoint],
+	low: f32,
+	high: f32,
+	method: IntegrationMethod,
+) -> Result<f32, SignalProcessError> {
+	let values = band_points(spectrum, low, high)
+		.map(|point| point.value)
+		.collect::<Vec<_>>();
+	if values.len() < 2 {
+		return Err(SignalProcessError::InvalidArgument(format!(
+			"频段 {low}-{high} Hz 内的频点不足，请增大 Welch 分段长度"
+		)));
+	}
+
+	let step = spectrum[1].frequency - spectrum[0].frequency;
+	let power = match method {
+		IntegrationMethod::Trapezoid => trapezoid(&values, step),
+		IntegrationMethod::Simpson => simpson(&values, step),
+	};
+	Ok(power)
+}
+
+fn band_points(
+	spectrum: &[SpectrumPoint],
+	low: f32,
+	high: f32,
+) -> impl Iterator<Item = &SpectrumPoint> {
+	spectrum.iter().filter(move |point| {
+		point.frequency >= low - FREQUENCY_TOLERANCE
+			&& point.frequency <= high + FREQUENCY_TOLERANCE
+	})
+}
+
+fn trapezoid(values: &[f32], step: f32) -> f32 {
+	values
+		.windows(2)
+		.map(|pair| (pair[0] + pair[1]) * step / 2.0)
+		.sum()
+}
+
+/// 复合 Simpson 积分，区间数为奇数时最后一个区间按梯形补齐。
+fn simpson(values: &[f32], step: f32) -> f32 {
+	let interval_count = values.len() - 1;
+	let simpson_intervals = interval_count - interval_count % 2;
+	let mut sum = 0.0;
+	for pair_start in (0..simpson_intervals).step_by(2) {
+		sum += (values[pair_start] + 4.0 * values[pair_start + 1] + values[pair_start + 2]) * step
+			/ 3.0;
+	}
+	if simpson_intervals < interval_count {
+		sum += trapezoid(&values[simpson_intervals..], step);
+	}
+	sum
+}
+
+/// 累计功率达到给定比例时的频率，在相邻频点之间线性插值。
+fn spectral_edge_frequency(
+	spectrum: &[SpectrumPoint],
+	range_hz: [f32; 2],
+	ratio: f32,
+) -> Result<f32, SignalProcessError> {
+	let points = band_points(spectrum, range_hz[0], range_hz[1]).collect::<Vec<_>>();
+	if points.len() < 2 {
+		return Err(SignalProcessError::InvalidArgument(
+			"总功率范围内的频点不足".to_string(),
+		));
+	}
+
+	let segments = points
+		.windows(2)
+		.map(|pair| (pair[0].value + pair[1].value) * (pair[1].frequency - pair[0].frequency) / 2.0)
+		.collect::<Vec<_>>();
+	let target = segments.iter().sum::<f32>() * ratio;
+	let mut cumulative = 0.0;
+	for (index, segment) in segments.iter().enumerate() {
+		if cumulative + segment >= target && *segment > 0.0 {
+			let fraction = (target - cumulative) / segment;
+			let start = points[index].frequency;
+			return Ok(start + fraction * (points[index + 1].frequency - start));
+		}
+		cumulative += segment;
+	}
+	Ok(points[points.len() - 1].frequency)
+}
+
+/// 范围内的局部谱峰频率，用抛物线插值细化到频点之间。
+fn peak_frequency(spectrum: &[SpectrumPoint], range_hz: [f32; 2]) -> Option<f32> {
+	let (index, _) = spectrum
+		.iter()
+		.enumerate()
+		.filter(|(_, point)| {
+			point.frequency >= range_hz[0] - FREQUENCY_TOLERANCE
+				&& point.frequency <= range_hz[1] + FREQUENCY_TOLERANCE
+		})
+		.max_by(|left, right| left.1.value.total_cmp(&right.1.value))?;
+	if index == 0 || index + 1 >= spectrum.len() {
+		return None;
+	}
+
+	let left = spectrum[index - 1].value;
+	let center = spectrum[index].value;
+	let right = spectrum[index + 1].value;
+	if center <= left || center <= right {
+		return None;
+	}
+
+	let step = spectrum[1].frequency - spectrum[0].frequency;
+	let curvature = left - 2.0 * center + right;
+	let offset = if curvature < 0.0 {
+		0.5 * (left - right) / curvature
+	} else {
+		0.0
+	};
+	Some(spectrum[index].frequency + offset * step)
+}
+
+fn validate_band_power_config(config: &BandPowerConfig) -> Result<(), SignalProcessError> {
+	if config.bands.is_empty() {
+		return Err(SignalProcessError::InvalidArgument(
+			"至少需要一个频段".to_string(),
+		));
+	}
+	for band in &config.bands {
+		if !(0.0 <= band.low_hz && band.low_hz < band.high_hz) {
+			return Err(SignalProcessError::InvalidArgument(format!(
+				"频段 {} 必须满足 0 <= low < high",
+				band.name
+			)));
+		}
+	}
+	for range in [config.total_range_hz, config.alpha_range_hz] {
+		if !(0.0 <= range[0] && range[0] < range[1]) {
+			return Err(SignalProcessError::InvalidArgument(
+				"频率范围必须满足 0 <= low < high".to_string(),
+			));
+		}
+	}
+	if !(0.0 < config.spectral_edge_ratio && config.spectral_edge_ratio <= 1.0) {
+		return Err(SignalProcessError::InvalidArgument(
+			"谱边缘比例必须位于 (0, 1] 内".to_string(),
+		));
+	}
+	Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+	use crate::{
+		CompositeComponent, EegSimulationConfig, FrequencyBand, generate_composite_signal,
+		generate_eeg_like_signal, generate_sine_wave, types::SineWaveConfig,
+	};
+
+	use super::*;
+
+	#[test]
+	fn simpson_should_integrate_quadratic_exactly() {
+		let spectrum = (0..=8)
+			.map(|index| {
+				let frequency = index as f32 * 0.5;
+				SpectrumPoint {
+					frequency,
+					value: frequency * frequency,
+				}
+			})
+			.collect::<Vec<_>>();
+
+		let simpson = integrate_band(&spectrum, 0.0, 4.0, IntegrationMethod::Simpson)
+			.unwrap_or_else(|error| panic!("积分失败: {error}"));
+		let trapezoid = integrate_band(&spectrum, 0.0, 4.0, IntegrationMethod::Trapezoid)
+			.unwrap_or_else(|error| panic!("积分失败: {error}"));
+
+		assert!((simpson - 64.0 / 3.0).abs() < 1e-4);
+		assert!((trapezoid - 21.5).abs() < 1e-4);
+	}
+
+	#[test]
+	fn alpha_sine_should_dominate_relative_power() {
+		let signal = generate_sine_wave(&SineWaveConfig {
+			sample_rate: 256.0,
+			frequency: 10.3,
+			amplitude: 2.0,
+			phase: 0.0,
+			duration_secs: 8.0,
+		})
+		.unwrap_or_else(|error| panic!("生成测试正弦波失败: {error}"));
+
+		let config = BandPowerConfig {
+			integration: IntegrationMethod::Trapezoid,
+			..BandPowerConfig::default()
+		};
+
+		let features = compute_band_power(&signal, &config)
+			.unwrap_or_else(|error| panic!("频段功率失败: {error}"));
+		let alpha = features
+			.bands
+			.iter()
+			.find(|band| band.name == "alpha")
+			.unwrap_or_else(|| panic!("缺少 alpha 频段"));
+
+		assert!((features.total_power - 2.0).abs() < 0.05);
+		assert!(alpha.relative_power > 0.95);
+		let peak = features
+			.peak_alpha_frequency
+			.unwrap_or_else(|| panic!("未找到 alpha 峰值"));
+		assert!((peak - 10.3).abs() < 0.2);
+	}
+
+	#[test]
+	fn simulated_alpha_bursts_should_raise_alpha_power() {
+		let alpha_power = |config: &EegSimulationConfig| {
+			let signal = generate_eeg_like_signal(config, 30.0)
+				.unwrap_or_else(|error| panic!("生成仿真 EEG 失败: {error}"));
+			let channel = signal
+				.channel(0)
+				.unwrap_or_else(|| panic!("缺少第 0 通道"))
+				.to_signal_buffer();
+			compute_band_power(&channel, &BandPowerConfig::default())
+				.unwrap_or_else(|error| panic!("频段功率失败: {error}"))
+		};
+		let config = EegSimulationConfig {
+			blink_rate_hz: 0.0,
+			spike_rate_hz: 0.0,
+			..EegSimulationConfig::default()
+		};
+		let with_alpha = alpha_power(&config);
+		let without_alpha = alpha_power(&EegSimulationConfig {
+			alpha_amplitude: 0.0,
+			..config.clone()
+		});
+		let relative_alpha = |features: &BandPowerFeatures| {
+			features
+				.bands
+				.iter()
+				.find(|band| band.name == "alpha")
+				.unwrap_or_else(|| panic!("缺少 alpha 频段"))
+				.relative_power
+		};
+
+		assert!(relative_alpha(&with_alpha) > 2.0 * relative_alpha(&without_alpha));
+		let peak = with_alpha
+			.peak_alpha_frequency
+			.unwrap_or_else(|| panic!("未找到 alpha 峰值"));
+		assert!((peak - config.alpha_frequency_hz).abs() < 0.5);
+	}
+
+	#[test]
+	fn spectral_edge_should_follow_power_distribution() {
+		let signal = generate_composite_signal(
+			256.0,
+			8.0,
+			&[
+				CompositeComponent {
+					frequency: 6.0,
+					amplitude: 1.0,
+					phase: 0.0,
+				},
+				CompositeComponent {
+					frequency: 20.0,
+					amplitude: 1.0,
+					phase: 0.3,
+				},
+			],
+		)
+		.unwrap_or_else(|error| panic!("生成复合信号失败: {error}"));
+		let mut config = BandPowerConfig {
+			bands: vec![FrequencyBand {
+				name: "low".to_string(),
+				low_hz: 1.0,
+				high_hz: 10.0,
+			}],
+			..BandPowerConfig::default()
+		};
+
+		let edge_95 = compute_band_power(&signal, &config)
+			.unwrap_or_else(|error| panic!("频段功率失败: {error}"))
+			.spectral_edge_frequency;
+		config.spectral_edge_ratio = 0.25;
+		let features = compute_band_power(&signal, &config)
+			.unwrap_or_else(|error| panic!("频段功率失败: {error}"));
+
+		assert!((edge_95 - 20.0).abs() < 2.0);
+		assert!((features.spectral_edge_frequency - 6.0).abs() < 2.0);
+		assert!((features.bands[0].relative_power - 0.5).abs() < 0.02);
+		assert_eq!(features.peak_alpha_frequency, None);
+	}
+}
@@ -0,0 +1,498 @@
+use std::f64::consts::TAU;
+
+use rustfft::{FftPlanner, num_complex::Complex64};
+
+use crate::{
+	error::SignalProcessError,
+	linalg::{Matrix, solve},
+	multichannel::MultiChannelSignal,
+	spectrum::build_segment_starts,
+	types::{
+		Complex32, ConnectivityMatrix, ConnectivityMetric, CrossSpectralDensity, GrangerConfig,
+		WelchSpectrumConfig, WindowKind,
+	},
+	window::build_window,
+};
+
+/// 各 Welch 分段、各通道的加窗单边频谱，按 [分段][通道][频率] 排列。
+type SegmentSpectra = Vec<Vec<Vec<Complex32>>>;
+
+/// 双变量模型中的 2×2 实矩阵。
+type Matrix2 = [[f64; 2]; 2];
+
+/// 计算 Welch 互谱密度矩阵，归一化方式与 `compute_power_spectrum_with_config` 一致。
+pub fn compute_cross_spectral_density(
+	signal: &MultiChannelSignal,
+	config: WelchSpectrumConfig,
+) -> Result<CrossSpectralDensity, SignalProcessError> {
+	let (sample_rate, spectra) = compute_segment_spectra(signal, config)?;
+	let channel_count = signal.channel_count();
+	let frequency_count = config.segment_length / 2 + 1;
+	let window_power = build_window(WindowKind::Hann, config.segment_length)
+		.iter()
+		.map(|value| value * value)
+		.sum::<f32>();
+	let normalization = sample_rate * window_power * spectra.len() as f32;
+
+	let values = (0..channel_count)
+		.map(|first| {
+			(0..channel_count)
+				.map(|second| {
+					(0..frequency_count)
+						.map(|index| {
+							let sum = spectra
+								.iter()
+								.map(|segment| {
+									segment[first][index] * segment[second][index].conj()
+								})
+								.sum::<Complex32>();
+							let one_sided = if index != 0
+								&& !(config.segment_length.is_multiple_of(2)
+									&& index == frequency_count - 1)
+							{
+								2.0
+							} else {
+								1.0
+							};
+							sum * (one_sided / normalization)
+						})
+						.collect()
+				})
+				.collect()
+		})
+		.collect();
+
+	Ok(CrossSpectralDensity {
+		channel_labels: signal.labels().map(str::to_string).collect(),
+		frequencies: frequency_axis(sample_rate, config.segment_length),
+		values,
+	})
+}
+
+/// 按 Welch 分段计算全部通道对的连接性指标。
+///
+/// 相干与虚部相干由平均互谱得到；锁相值与相位滞后指数统计各分段互谱的相位，
+/// 后者见 Stam, C. J. et al. 2007，参考文献：Phase lag index: assessment of functional
+/// connectivity from multi channel EEG and MEG with diminished bias from common sources,
+/// doi: 10.1002/hbm.20346
+pub fn compute_connectivity(
+	signal: &MultiChannelSignal,
+	config: WelchSpectrumConfig,
+	metric: ConnectivityMetric,
+) -> Result<ConnectivityMatrix, SignalProcessError> {
+	let sample_rate = validate_channels(signal)?;
+	let channel_count = signal.channel_count();
+	let frequency_count = config.segment_length / 2 + 1;
+	let values = match metric {
+		ConnectivityMetric::Coherence | ConnectivityMetric::ImaginaryCoherence => {
+			let csd = compute_cross_spectral_density(signal, config)?;
+			map_pairs(channel_count, frequency_count, |first, second, index| {
+				let cross = csd.values[first][second][index];
+				let power = (csd.values[first][first][index].re
+					* csd.values[second][second][index].re)
+					.max(0.0);
+				if power <= 0.0 {
+					return 0.0;
+				}
+				match metric {
+					ConnectivityMetric::Coherence => cross.norm_sqr() / power,
+					_ => cross.im / power.sqrt(),
+				}
+			})
+		}
+		ConnectivityMetric::PhaseLockingValue | ConnectivityMetric::PhaseLagIndex => {
+			let (_, spectra) = compute_segment_spectra(signal, config)?;
+			let segment_count = spectra.len() as f32;
+			map_pairs(channel_count, frequency_count, |first, second, index| {
+				let crosses = spectra
+					.iter()
+					.map(|segment| segment[first][index] * segment[second][index].conj());
+				match metric {
+					ConnectivityMetric::PhaseLockingValue => {
+						crosses
+							.filter(|cross| cross.norm() > 0.0)
+							.map(|cross| cross / cross.norm())
+							.sum::<Complex32>()
+							.norm() / segment_count
+					}
+					_ => crosses.map(|cross| sign(cross.im)).sum::<f32>().abs() / segment_count,
+				}
+			})
+		}
+	};
+
+	Ok(ConnectivityMatrix {
+		channel_labels: signal.labels().map(str::to_string).collect(),
+		frequencies: frequency_axis(sample_rate, config.segment_length),
+		values,
+	})
+}
+
+/// 对每对通道拟合双变量自回归模型，计算 Geweke 谱 Granger 因果。
+///
+/// values[源][目标][频率] = ln(S_tt / (S_tt - (Σ_ss - Σ_st²/Σ_tt)·|H_ts|²))，对角线为 0。
+/// 方法见 Ding, M. et al. 2006，参考文献：Granger causality: basic theory and application
+/// to neuroscience, doi: 10.1002/9783527609970.ch17
+pub fn compute_spectral_granger(
+	signal: &MultiChannelSignal,
+	config: &GrangerConfig,
+) -> Result<ConnectivityMatrix, SignalProcessError> {
+	let sample_rate = validate_channels(signal)?;
+	let channel_count = signal.channel_count();
+	let sample_count = signal.channel_data()[0].len();
+	if config.model_order == 0 {
+		return Err(SignalProcessError::InvalidArgument(
+			"自回归模型阶数必须大于 0".to_string(),
+		));
+	}
+	if config.frequency_count < 2 {
+		return Err(SignalProcessError::InvalidArgument(
+			"Granger 频点数必须至少为 2".to_string(),
+		));
+	}
+	if sample_count <= 10 * config.model_order {
+		return Err(SignalProcessError::InvalidArgument(format!(
+			"样本数 {sample_count} 不足以拟合 {} 阶自回归模型",
+			config.model_order
+		)));
+	}
+
+	let frequencies = (0..config.frequency_count)
+		.map(|index| index as f32 * sample_rate / 2.0 / (config.frequency_count - 1) as f32)
+		.collect::<Vec<_>>();
+	let centered = signal
+		.channel_data()
+		.iter()
+		.map(|channel| {
+			let mean = channel.iter().map(|value| *value as f64).sum::<f64>() / sample_count as f64;
+			channel
+				.iter()
+				.map(|value| *value as f64 - mean)
+				.collect::<Vec<_>>()
+		})
+		.collect::<Vec<_>>();
+
+	let mut values = vec![vec![vec![0.0; config.frequency_count]; channel_count]; channel_count];
+	for target in 0..channel_count {
+		for source in (target + 1)..channel_count {
+			let (coefficients, noise) =
+				fit_bivariate_var(&centered[target], &centered[source], config.model_order)?;
+			for (index, frequency) in frequencies.iter().enumerate() {
+				let omega = TAU * *frequency as f64 / sample_rate as f64;
+				let [to_target, to_source] = pairwise_causality(&coefficients, &noise, omega);
+				values[source][target][index] = to_target as f32;
+				values[target][source][index] = to_source as f32;
+			}
+		}
+	}
+
+	Ok(ConnectivityMatrix {
+		channel_labels: signal.labels().map(str::to_string).collect(),
+		frequencies,
+		values,
+	})
+}
+
+/// 校验通道一致性并返回采样率。
+fn validate_channels(signal: &MultiChannelSignal) -> Result<f32, SignalProcessError> {
+	let sample_rate = signal.uniform_sample_rate().ok_or_else(|| {
+		SignalProcessError::InvalidArgument("连接性分析要求各通道采样率一致".to_string())
+	})?;
+	let sample_count = signal.channel_data()[0].len();
+	if sample_count == 0 {
+		return Err(SignalProcessError::EmptyInput);
+	}
+	if signal
+		.channel_data()
+		.iter()
+		.any(|channel| channel.len() != sample_count)
+	{
+		return Err(SignalProcessError::InvalidArgument(
+			"连接性分析要求各通道长度一致".to_string(),
+		));
+	}
+	Ok(sample_rate)
+}
+
+fn compute_segment_spectra(
+	signal: &MultiChannelSignal,
+	config: WelchSpectrumConfig,
+) -> Result<(f32, SegmentSpectra), SignalProcessError> {
+	let sample_rate = validate_channels(signal)?;
+	let sample_count = signal.channel_data()[0].len();
+	if config.segment_length < 8 || config.segment_length > sample_count {
+		return Err(SignalProcessError::InvalidArgument(format!(
+			"Welch 分段长度必须位于 [8, {sample_count}] 内"
+		)));
+	}
+	if config.overlap_length >= config.segment_length {
+		return Err(SignalProcessError::InvalidArgument(
+			"Welch 重叠长度必须小于分段长度".to_string(),
+		));
+	}
+
+	let window = build_window(WindowKind::Hann, config.segment_length);
+	let fft = FftPlanner::<f32>::new().plan_fft_forward(config.segment_length);
+	let half = config.segment_length / 2;
+	let spectra = build_segment_starts(
+		sample_count,
+		config.segment_length,
+		config.segment_length - config.overlap_length,
+	)
+	.into_iter()
+	.map(|start| {
+		signal
+			.channel_data()
+			.iter()
+			.map(|channel| {
+				let mut bins = channel[start..start + config.segment_length]
+					.iter()
+					.zip(window.iter())
+					.map(|(sample, weight)| Complex32::new(sample * weight, 0.0))
+					.collect::<Vec<_>>();
+				fft.process(&mut bins);
+				bins.truncate(half + 1);
+				bins
+			})
+			.collect()
+	})
+	.collect();
+	Ok((sample_rate, spectra))
+}
+
+fn frequency_axis(sample_rate: f32, segment_length: usize) -> Vec<f32> {
+	(0..=segment_length / 2)
+		.map(|index| index as f32 * sample_rate / segment_length as f32)
+		.collect()
+}
+
+/// 对每对通道、每个频点求值，对角线按相同公式计算。
+fn map_pairs(
+	channel_count: usize,
+	frequency_count: usize,
+	evaluate: impl Fn(usize, usize, usize) -> f32,
+) -> Vec<Vec<Vec<f32>>> {
+	(0..channel_count)
+		.map(|first| {
+			(0..channel_count)
+				.map(|second| {
+					(0..frequency_count)
+						.map(|index| evaluate(first, second, index))
+						.collect()
+				})
+				.collect()
+		})
+		.collect()
+}
+
+fn sign(value: f32) -> f32 {
+	if value > 0.0 {
+		1.0
+	} else if value < 0.0 {
+		-1.0
+	} else {
+		0.0
+	}
+}
+
+/// 最小二乘拟合 z(t) = Σ A_k·z(t-k) + e(t)，z = [first, second]。
+///
+/// 返回各阶系数矩阵 A_k 与残差协方差 Σ。
+fn fit_bivariate_var(
+	first: &[f64],
+	second: &[f64],
+	order: usize,
+) -> Result<(Vec<Matrix2>, Matrix2), SignalProcessError> {
+	let regressor_count = 2 * order;
+	let mut normal = vec![vec![0.0; regressor_count]; regressor_count];
+	let mut rhs = vec![vec![0.0; 2]; regressor_count];
+	let regressors = |time: usize| {
+		(1..=order)
+			.flat_map(move |lag| [first[time - lag], second[time - lag]])
+			.collect::<Vec<_>>()
+	};
+	for time in order..first.len() {
+		let row = regressors(time);
+		for (left_index, left) in row.iter().enumerate() {
+			for (right_index, right) in row.iter().enumerate() {
+				normal[left_index][right_index] += left * right;
+			}
+			rhs[left_index][0] += left * first[time];
+			rhs[left_index][1] += left * second[time];
+		}
+	}
+
+	let solution: Matrix = solve(&normal, &rhs).ok_or_else(|| {
+		SignalProcessError::InvalidArgument("自回归模型的正规方程奇异".to_string())
+	})?;
+	let coefficients = (0..order)
+		.map(|lag| {
+			let base = 2 * lag;
+			[
+				[solution[base][0], solution[base + 1][0]],
+				[solution[base][1], solution[base + 1][1]],
+			]
+		})
+		.collect::<Vec<_>>();
+
+	let mut noise = [[0.0; 2]; 2];
+	for time in order..first.len() {
+		let row = regressors(time);
+		let predicted = [0, 1].map(|output| {
+			row.iter()
+				.zip(solution.iter())
+				.map(|(value, weights)| value * weights[output])
+				.sum::<f64>()
+		});
+		let residual = [first[time] - predicted[0], second[time] - predicted[1]];
+		for (row_index, noise_row) in noise.iter_mut().enumerate() {
+			for (column_index, value) in noise_row.iter_mut().enumerate() {
+				*value += residual[row_index] * residual[column_index];
+			}
+		}
+	}
+	let residual_count = (first.len() - order) as f64;
+	for value in noise.iter_mut().flatten() {
+		*value /= residual_count;
+	}
+	Ok((coefficients, noise))
+}
+
+/// 返回 [second → first, first → second] 在角频率 omega 处的 Granger 因果。
+fn pairwise_causality(coefficients: &[Matrix2], noise: &Matrix2, omega: f64) -> [f64; 2] {
+	// A(ω) = I - Σ A_k·e^{-jωk}，传递函数 H = A⁻¹。
+	let mut transfer = [
+		[Complex64::new(1.0, 0.0), Complex64::new(0.0, 0.0)],
+		[Complex64::new(0.0, 0.0), Complex64::new(1.0, 0.0)],
+	];
+	for (lag, coefficient) in coefficients.iter().enumerate() {
+		let phase = Complex64::from_polar(1.0, -omega * (lag + 1) as f64);
+		for (transfer_row, coefficient_row) in transfer.iter_mut().zip(coefficient.iter()) {
+			for (value, weight) in transfer_row.iter_mut().zip(coefficient_row.iter()) {
+				*value -= weight * phase;
+			}
+		}
+	}
+	let determinant = transfer[0][0] * transfer[1][1] - transfer[0][1] * transfer[1][0];
+	let h = [
+		[transfer[1][1] / determinant, -transfer[0][1] / determinant],
+		[-transfer[1][0] / determinant, transfer[0][0] / determinant],
+	];
+
+	let spectrum = |row: usize| {
+		(0..2)
+			.flat_map(|left| (0..2).map(move |right| (left, right)))
+			.map(|(left, right)| (h[row][left] * noise[left][right] * h[row][right].conj()).re)
+			.sum::<f64>()
+	};
+	let causality = |target: usize, source: usize| {
+		let power = spectrum(target);
+		let partial = noise[source][source] - noise[target][source].powi(2) / noise[target][target];
+		let intrinsic = power - partial * h[target][source].norm_sqr();
+		if power <= 0.0 || intrinsic <= 0.0 {
+			0.0
+		} else {
+			(power / intrinsic).ln().max(0.0)
+		}
+	};
+	[causality(0, 1), causality(1, 0)]
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::{test_support::white_noise, types::SignalBuffer};
+
+	const SAMPLE_RATE: f32 = 128.0;
+
+	fn build_signal(channels: Vec<Vec<f32>>) -> MultiChannelSignal {
+		let labels = ["Fz", "Cz", "Pz", "Oz"];
+		let buffers = channels
+			.iter()
+			.cloned()
+			.map(|samples| SignalBuffer {
+				sample_rate: SAMPLE_RATE,
+				samples,
+			})
+			.collect();
+		MultiChannelSignal::from_signal_buffers(buffers, &labels[..channels.len()], "uV")
+			.unwrap_or_else(|error| panic!("多通道信号创建失败: {error}"))
+	}
+
+	fn band_mean(matrix: &ConnectivityMatrix, first: usize, second: usize) -> f32 {
+		let selected = matrix
+			.frequencies
+			.iter()
+			.zip(matrix.values[first][second].iter())
+			.filter(|(frequency, _)| (8.0..=24.0).contains(*frequency))
+			.map(|(_, value)| *value)
+			.collect::<Vec<_>>();
+		selected.iter().sum::<f32>() / selected.len() as f32
+	}
+
+	#[test]
+	fn spectral_metrics_should_separate_lagged_and_zero_lag_coupling() {
+		let source = white_noise(1, 8192);
+		let lagged = (0..8192)
+			.map(|index| if index >= 2 { source[index - 2] } else { 0.0 })
+			.zip(white_noise(2, 8192))
+			.map(|(value, noise)| value + 0.2 * noise)
+			.collect::<Vec<_>>();
+		let mirrored = source.iter().map(|value| -0.5 * value).collect();
+		let signal = build_signal(vec![source, lagged, mirrored, white_noise(3, 8192)]);
+		let config = WelchSpectrumConfig {
+			segment_length: 128,
+			overlap_length: 64,
+		};
+		let compute = |metric| {
+			compute_connectivity(&signal, config, metric)
+				.unwrap_or_else(|error| panic!("连接性计算失败: {error}"))
+		};
+
+		let csd = compute_cross_spectral_density(&signal, config)
+			.unwrap_or_else(|error| panic!("互谱计算失败: {error}"));
+		let coherence = compute(ConnectivityMetric::Coherence);
+		let imaginary = compute(ConnectivityMetric::ImaginaryCoherence);
+		let plv = compute(ConnectivityMetric::PhaseLockingValue);
+		let pli = compute(ConnectivityMetric::PhaseLagIndex);
+
+		assert_eq!(csd.values[0][0].len(), 65);
+		assert!(csd.values[0][0].iter().all(|value| value.im.abs() < 1e-6));
+		assert!(band_mean(&coherence, 0, 1) > 0.9);
+		assert!(band_mean(&coherence, 0, 2) > 0.99);
+		assert!(band_mean(&coherence, 0, 3) < 0.1);
+		assert!(band_mean(&imaginary, 0, 1).abs() > 0.3);
+		assert!(band_mean(&imaginary, 0, 2).abs() < 1e-3);
+		assert!(band_mean(&plv, 0, 1) > 0.9);
+		assert!(band_mean(&pli, 0, 1) > 0.9);
+		assert!(band_mean(&pli, 0, 2) < 1e-3);
+		assert!(band_mean(&pli, 0, 3) < 0.3);
+	}
+
+	#[test]
+	fn spectral_granger_should_follow_causal_direction() {
+		let driver = white_noise(7, 4096);
+		let innovation = white_noise(8, 4096);
+		let mut follower = vec![0.0; 4096];
+		for index in 2..4096 {
+			follower[index] =
+				0.5 * follower[index - 1] + 0.8 * driver[index - 2] + 0.3 * innovation[index];
+		}
+		let signal = build_signal(vec![driver, follower, white_noise(9, 4096)]);
+
+		let granger = compute_spectral_granger(
+			&signal,
+			&GrangerConfig {
+				model_order: 4,
+				frequency_count: 65,
+			},
+		)
+		.unwrap_or_else(|error| panic!("Granger 因果计算失败: {error}"));
+
+		assert_eq!(granger.values[0][1].len(), 65);
+		assert!(band_mean(&granger, 0, 1) > 0.5);
+		assert!(band_mean(&granger, 1, 0) < 0.02);
+		assert!(band_mean(&granger, 0, 2) < 0.02);
+		assert!(granger.values[1][1].iter().all(|value| *value == 0.0));
+	}
+}
@@ -0,0 +1,519 @@
+use std::{collections::VecDeque, fmt, sync::Arc};
+
+use rustfft::{Fft, FftPlanner, num_complex::Complex32};
+
+use crate::{
+	error::SignalProcessError,
+	filter::{FirFilter, StreamingFilter},
+	types::SignalBuffer,
+};
+
+/// 一次性滤波（[`apply_fir_fast`]）自动选择时切换到 FFT 卷积的 tap 数阈值。
+///
+/// 低于该值时直接卷积的常数开销更小，高于该值时 FFT 卷积的 O(log N) 单点代价占优。
+pub const FFT_CONVOLUTION_TAP_THRESHOLD: usize = 64;
+
+/// FIR 卷积实现方式。
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConvolutionMethod {
+	/// 一次性滤波按 tap 数自动选择；流式 [`FirConvolver`] 始终使用直接卷积。
+	Auto,
+	/// 逐点直接卷积。
+	Direct,
+	/// 基于 FFT 的分块卷积。
+	Fft,
+}
+
+impl ConvolutionMethod {
+	/// 根据 tap 数确定实际使用的实现方式。
+	fn resolve(self, tap_count: usize) -> Self {
+		match self {
+			Self::Auto if tap_count >= FFT_CONVOLUTION_TAP_THRESHOLD => Self::Fft,
+			Self::Auto => Self::Direct,
+			method => method,
+		}
+	}
+}
+
+/// 基于 overlap-save 的流式 FFT FIR 滤波器。
+///
+/// 输入先缓存到凑满一个分块（FFT 长度减去 tap 数再加 1 个样本）才做一次正反 FFT，
+/// 因此无论每次送入多少样本，FFT 次数都只取决于总样本数。代价是输出相对直接卷积
+/// 固定延迟 [`FftFirFilter::latency`] 个样本：第 n 个输出对应直接卷积的第
+/// `n - latency` 个输出，延迟期间输出 0，稳态初始化后输出稳态值。
+#[derive(Clone)]
+pub struct FftFirFilter {
+	coeffs: Vec<f32>,
+	/// 当前缓存分块之前的 tap 数 - 1 个输入，按时间先后排列。
+	history: Vec<f32>,
+	/// 尚未凑满一个分块的输入。
+	pending: Vec<f32>,
+	/// 已计算但尚未输出的样本，开头预置 `latency` 个延迟样本。
+	ready: VecDeque<f32>,
+	fft_size: usize,
+	kernel_spectrum: Vec<Complex32>,
+	forward: Arc<dyn Fft<f32>>,
+	inverse: Arc<dyn Fft<f32>>,
+	buffer: Vec<Complex32>,
+}
+
+impl fmt::Debug for FftFirFilter {
+	fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+		formatter
+			.debug_struct("FftFirFilter")
+			.field("tap_count", &self.coeffs.len())
+			.field("fft_size", &self.fft_size)
+			.field("latency", &self.latency())
+			.finish_non_exhaustive()
+	}
+}
+
+impl FftFirFilter {
+	/// 以给定系数创建 FFT FIR 滤波器。
+	pub fn new(coeffs: &[f32]) -> Result<Self, SignalProcessError> {
+		if coeffs.is_empty() {
+			return Err(SignalProcessError::InvalidArgument(
+				"FIR 系数不能为空".to_string(),
+			));
+		}
+
+		let fft_size = fft_size_for(coeffs.len());
+		let mut planner = FftPlanner::<f32>::new();
+		let forward = planner.plan_fft_forward(fft_size);
+		let inverse = planner.plan_fft_inverse(fft_size);
+		let kernel_spectrum = kernel_spectrum(coeffs, fft_size, forward.as_ref());
+		let block_length = fft_size - (coeffs.len() - 1);
+
+		Ok(Self {
+			coeffs: coeffs.to_vec(),
+			history: vec![0.0; coeffs.len() - 1],
+			pending: Vec::with_capacity(block_length),
+			ready: VecDeque::from(vec![0.0; block_length - 1]),
+			fft_size,
+			kernel_spectrum,
+			forward,
+			inverse,
+			buffer: vec![Complex32::new(0.0, 0.0); fft_size],
+		})
+	}
+
+	/// 获取滤波器系数。
+	pub fn coeffs(&self) -> &[f32] {
+		&self.coeffs
+	}
+
+	/// 单次 FFT 产出的输出样本数。
+	fn block_length(&self) -> usize {
+		self.fft_size - self.history.len()
+	}
+
+	/// 对凑满的分块做一次 overlap-save 卷积，结果追加到待输出队列。
+	fn flush_block(&mut self) {
+		let history_length = self.history.len();
+		for (slot, value) in self
+			.buffer
+			.iter_mut()
+			.zip(self.history.iter().chain(self.pending.iter()))
+		{
+			*slot = Complex32::new(*value, 0.0);
+		}
+		let fresh = self.pending.len() - history_length;
+		self.history.copy_from_slice(&self.pending[fresh..]);
+		self.pending.clear();
+
+		self.forward.process(&mut self.buffer);
+		for (bin, kernel) in self.buffer.iter_mut().zip(self.kernel_spectrum.iter()) {
+			*bin *= kernel;
+		}
+		self.inverse.process(&mut self.buffer);
+
+		let scale = 1.0 / self.fft_size as f32;
+		self.ready.extend(
+			self.buffer[history_length..]
+				.iter()
+				.map(|value| value.re * scale),
+		);
+	}
+
+	/// 清空缓存，并以 `delayed` 填充延迟期间的输出。
+	fn restart(&mut self, delayed: f32) {
+		let latency = self.latency();
+		self.pending.clear();
+		self.ready.clear();
+		self.ready.resize(latency, delayed);
+	}
+}
+
+impl StreamingFilter for FftFirFilter {
+	fn process_sample(&mut self, input: f32) -> f32 {
+		self.pending.push(input);
+		if self.pending.len() == self.block_length() {
+			self.flush_block();
+		}
+		self.ready.pop_front().unwrap_or(0.0)
+	}
+
+	fn reset(&mut self) {
+		self.history.fill(0.0);
+		self.restart(0.0);
+	}
+
+	fn order(&self) -> usize {
+		self.coeffs.len() - 1
+	}
+
+	fn initialize_steady_state(&mut self, value: f32) {
+		self.history.fill(value);
+		self.restart(value * self.coeffs.iter().sum::<f32>());
+	}
+
+	/// 输出相对直接卷积的固定延迟（样本数），等于分块长度减 1。
+	fn latency(&self) -> usize {
+		self.block_length() - 1
+	}
+}
+
+/// 直接卷积或 FFT 卷积的流式 FIR 滤波器。
+///
+/// FFT 实现带有分块缓存延迟（见 [`StreamingFilter::latency`]），会改变输出对齐，
+/// 因此流式形式下 `Auto` 始终选择直接卷积，只有显式指定 `Fft` 才使用 FFT 卷积。
+#[derive(Debug, Clone)]
+pub enum FirConvolver {
+	Direct(FirFilter),
+	Fft(FftFirFilter),
+}
+
+impl FirConvolver {
+	/// 以给定系数和实现方式创建滤波器，`Auto` 与 `Direct` 均为无延迟的直接卷积。
+	pub fn new(coeffs: &[f32], method: ConvolutionMethod) -> Result<Self, SignalProcessError> {
+		match method {
+			ConvolutionMethod::Fft => Ok(Self::Fft(FftFirFilter::new(coeffs)?)),
+			ConvolutionMethod::Auto | ConvolutionMethod::Direct => {
+				Ok(Self::Direct(FirFilter::new(coeffs)?))
+			}
+		}
+	}
+}
+
+impl StreamingFilter for FirConvolver {
+	fn process_sample(&mut self, input: f32) -> f32 {
+		match self {
+			Self::Direct(filter) => filter.process_sample(input),
+			Self::Fft(filter) => filter.process_sample(input),
+		}
+	}
+
+	fn reset(&mut self) {
+		match self {
+			Self::Direct(filter) => filter.reset(),
+			Self::Fft(filter) => filter.reset(),
+		}
+	}
+
+	fn order(&self) -> usize {
+		match self {
+			Self::Direct(filter) => filter.order(),
+			Self::Fft(filter) => filter.order(),
+		}
+	}
+
+	fn initialize_steady_state(&mut self, value: f32) {
+		match self {
+			Self::Direct(filter) => filter.initialize_steady_state(value),
+			Self::Fft(filter) => filter.initialize_steady_state(value),
+		}
+	}
+
+	fn latency(&self) -> usize {
+		match self {
+			Self::Direct(filter) => filter.latency(),
+			Self::Fft(filter) => filter.latency(),
+		}
+	}
+
+	fn process_in_place(&mut self, samples: &mut [f32]) {
+		match self {
+			Self::Direct(filter) => filter.process_in_place(samples),
+			Self::Fft(filter) => filter.process_in_place(samples),
+		}
+	}
+
+	fn process_block(
+		&mut self,
+		input: &[f32],
+		output: &mut [f32],
+	) -> Result<(), SignalProcessError> {
+		match self {
+			Self::Direct(filter) => filter.process_block(input, output),
+			Self::Fft(filter) => filter.process_block(input, output),
+		}
+	}
+}
+
+/// 按 tap 数自动选择实现方式执行 FIR 滤波，输出与 `apply_fir` 对齐。
+pub fn apply_fir_fast(
+	signal: &SignalBuffer,
+	coeffs: &[f32],
+) -> Result<SignalBuffer, SignalProcessError> {
+	apply_fir_with_method(signal, coeffs, ConvolutionMethod::Auto)
+}
+
+/// 按指定实现方式执行 FIR 滤波。
+///
+/// FFT 方式采用 overlap-add：输入按块做 FFT 卷积后把各块尾部叠加到下一块。
+pub fn apply_fir_with_method(
+	signal: &SignalBuffer,
+	coeffs: &[f32],
+	method: ConvolutionMethod,
+) -> Result<SignalBuffer, SignalProcessError> {
+	if signal.samples.is_empty() {
+		return Err(SignalProcessError::EmptyInput);
+	}
+	if coeffs.is_empty() {
+		return Err(SignalProcessError::InvalidArgument(
+			"FIR 系数不能为空".to_string(),
+		));
+	}
+
+	let samples = match method.resolve(coeffs.len()) {
+		ConvolutionMethod::Fft => overlap_add(&signal.samples, coeffs),
+		_ => {
+			let mut filter = FirFilter::new(coeffs)?;
+			let mut output = signal.samples.clone();
+			filter.process_in_place(&mut output);
+			output
+		}
+	};
+
+	Ok(SignalBuffer {
+		sample_rate: signal.sample_rate,
+		samples,
+	})
+}
+
+/// overlap-add 卷积，仅保留与输入等长的因果部分。
+fn overlap_add(samples: &[f32], coeffs: &[f32]) -> Vec<f32> {
+	let fft_size = fft_size_for(coeffs.len());
+	let block_length = fft_size - (coeffs.len() - 1);
+	let mut planner = FftPlanner::<f32>::new();
+	let forward = planner.plan_fft_forward(fft_size);
+	let inverse = planner.plan_fft_inverse(fft_size);
+	let kernel_spectrum = kernel_spectrum(coeffs, fft_size, forward.as_ref());
+	let scale = 1.0 / fft_size as f32;
+
+	let mut output = vec![0.0f32; samples.len()];
+	let mut buffer = vec![Complex32::new(0.0, 0.0); fft_size];
+	for (block_index, block) in samples.chunks(block_length).enumerate() {
+		for (slot, value) in buffer
+			.iter_mut()
+			.zip(block.iter().copied().chain(std::iter::repeat(0.0)))
+		{
+			*slot = Complex32::new(value, 0.0);
+		}
+		forward.process(&mut buffer);
+		for (bin, kernel) in buffer.iter_mut().zip(kernel_spectrum.iter()) {
+			*bin *= kernel;
+		}
+		inverse.process(&mut buffer);
+
+		let start = block_index * block_length;
+		for (target, value) in output[start..].iter_mut().zip(buffer.iter()) {
+			*target += value.re * scale;
+		}
+	}
+	output
+}
+
+/// FFT 长度取不小于 2 倍 tap 数的 2 的幂，保证每块至少产出 tap 数个样本。
+fn fft_size_for(tap_count: usize) -> usize {
+	(2 * tap_count).next_power_of_two()
+}
+
+fn kernel_spectrum(coeffs: &[f32], fft_size: usize, forward: &dyn Fft<f32>) -> Vec<Complex32> {
+	let mut spectrum: Vec<Complex32> = coeffs
+		.iter()
+		.map(|coeff| Complex32::new(*coeff, 0.0))
+		.chain(std::iter::repeat(Complex32::new(0.0, 0.0)))
+		.take(fft_size)
+		.collect();
+	forward.process(&mut spectrum);
+	spectrum
+}
+
+#[cfg(test)]
+mod tests {
+	use crate::{
+		CompositeComponent, FilterKind, FiltfiltConfig, FirDesignConfig, PadType, WindowKind,
+		apply_fir, design_fir, filtfilt, generate_composite_signal,
+	};
+
+	use super::*;
+
+	fn test_signal() -> SignalBuffer {
+		generate_composite_signal(
+			1000.0,
+			3.0,
+			&[
+				CompositeComponent {
+					frequency: 12.0,
+					amplitude: 1.0,
+					phase: 0.0,
+				},
+				CompositeComponent {
+					frequency: 180.0,
+					amplitude: 0.5,
+					phase: 0.7,
+				},
+			],
+		)
+		.unwrap_or_else(|error| panic!("复合信号生成失败: {error}"))
+	}
+
+	fn long_fir() -> Vec<f32> {
+		design_fir(&FirDesignConfig {
+			sample_rate: 1000.0,
+			filter_kind: FilterKind::LowPass,
+			cutoff_hz: vec![40.0],
+			tap_count: 301,
+			window: WindowKind::Hamming,
+		})
+		.unwrap_or_else(|error| panic!("FIR 设计失败: {error}"))
+	}
+
+	fn max_difference(left: &[f32], right: &[f32]) -> f32 {
+		left.iter()
+			.zip(right.iter())
+			.map(|(left, right)| (left - right).abs())
+			.fold(0.0, f32::max)
+	}
+
+	#[test]
+	fn overlap_add_should_match_direct_convolution() {
+		let signal = test_signal();
+		let coeffs = long_fir();
+
+		let direct =
+			apply_fir(&signal, &coeffs).unwrap_or_else(|error| panic!("直接卷积失败: {error}"));
+		let fast = apply_fir_fast(&signal, &coeffs)
+			.unwrap_or_else(|error| panic!("FFT 卷积失败: {error}"));
+
+		assert_eq!(fast.samples.len(), direct.samples.len());
+		assert!(max_difference(&fast.samples, &direct.samples) < 1e-4);
+	}
+
+	/// 流式 FFT 输出去掉延迟后应与直接卷积对齐，延迟期间输出为 0。
+	fn assert_delayed_match(streamed: &[f32], direct: &[f32], latency: usize) {
+		assert_eq!(streamed.len(), direct.len());
+		assert!(streamed[..latency].iter().all(|value| *value == 0.0));
+		assert!(max_difference(&streamed[latency..], &direct[..direct.len() - latency]) < 1e-4);
+	}
+
+	#[test]
+	fn streaming_overlap_save_should_match_delayed_direct_convolution() {
+		let signal = test_signal();
+		let coeffs = long_fir();
+		let direct =
+			apply_fir(&signal, &coeffs).unwrap_or_else(|error| panic!("直接卷积失败: {error}"));
+
+		let mut filter = FirConvolver::new(&coeffs, ConvolutionMethod::Fft)
+			.unwrap_or_else(|error| panic!("{error}"));
+		assert!(matches!(filter, FirConvolver::Fft(_)));
+		let latency = filter.latency();
+		assert_eq!(latency, 1024 - 300 - 1);
+
+		let mut streamed = Vec::with_capacity(signal.samples.len());
+		for chunk in signal.samples.chunks(97) {
+			let mut output = chunk.to_vec();
+			filter.process_in_place(&mut output);
+			streamed.extend(output);
+		}
+
+		assert_delayed_match(&streamed, &direct.samples, latency);
+	}
+
+	#[test]
+	fn single_sample_path_should_continue_block_history() {
+		let signal = test_signal();
+		let coeffs = long_fir();
+		let direct =
+			apply_fir(&signal, &coeffs).unwrap_or_else(|error| panic!("直接卷积失败: {error}"));
+
+		let mut filter = FftFirFilter::new(&coeffs).unwrap_or_else(|error| panic!("{error}"));
+		let latency = filter.latency();
+		let mut streamed = signal.samples[..500].to_vec();
+		filter.process_in_place(&mut streamed);
+		streamed.extend(
+			signal.samples[500..]
+				.iter()
+				.map(|sample| filter.process_sample(*sample)),
+		);
+
+		assert_delayed_match(&streamed, &direct.samples, latency);
+	}
+
+	#[test]
+	fn steady_state_should_fill_latency_with_dc_output() {
+		let coeffs = long_fir();
+		let gain = coeffs.iter().sum::<f32>();
+		let mut filter = FftFirFilter::new(&coeffs).unwrap_or_else(|error| panic!("{error}"));
+		filter.initialize_steady_state(2.0);
+
+		let mut streamed = vec![2.0; 3000];
+		filter.process_in_place(&mut streamed);
+
+		assert!(
+			streamed
+				.iter()
+				.all(|value| (value - 2.0 * gain).abs() < 1e-4)
+		);
+		filter.reset();
+		assert_eq!(filter.process_sample(1.0), 0.0);
+	}
+
+	#[test]
+	fn auto_method_should_stream_without_latency() {
+		let filter = FirConvolver::new(&long_fir(), ConvolutionMethod::Auto)
+			.unwrap_or_else(|error| panic!("{error}"));
+
+		assert!(matches!(filter, FirConvolver::Direct(_)));
+		assert_eq!(filter.latency(), 0);
+	}
+
+	#[test]
+	fn filtfilt_should_compensate_fft_latency_without_padding() {
+		let signal = generate_composite_signal(
+			1000.0,
+			2.0,
+			&[CompositeComponent {
+				frequency: 7.0,
+				amplitude: 1.0,
+				phase: 0.0,
+			}],
+		)
+		.unwrap_or_else(|error| panic!("复合信号生成失败: {error}"));
+		let coeffs = design_fir(&FirDesignConfig {
+			sample_rate: 1000.0,
+			filter_kind: FilterKind::LowPass,
+			cutoff_hz: vec![40.0],
+			tap_count: 101,
+			window: WindowKind::Hamming,
+		})
+		.unwrap_or_else(|error| panic!("FIR 设计失败: {error}"));
+		let fft = FirConvolver::new(&coeffs, ConvolutionMethod::Fft)
+			.unwrap_or_else(|error| panic!("{error}"));
+		let direct = FirFilter::new(&coeffs).unwrap_or_else(|error| panic!("{error}"));
+
+		for pad_type in [PadType::None, PadType::Odd] {
+			let config = FiltfiltConfig {
+				pad_type,
+				pad_length: None,
+			};
+			let expected = filtfilt(&direct, &signal, config)
+				.unwrap_or_else(|error| panic!("零相位 FIR 失败: {error}"));
+			let actual = filtfilt(&fft, &signal, config)
+				.unwrap_or_else(|error| panic!("零相位 FFT FIR 失败: {error}"));
+
+			assert!(max_difference(&actual.samples, &expected.samples) < 1e-4);
+		}
+	}
+}
@@ -0,0 +1,367 @@
+use crate::{
+	error::SignalProcessError,
+	multichannel::MultiChannelSignal,
+	types::{
+		Epoch, EpochConfig, EpochRejectReason, EpochSet, EventMarker, EvokedResponse, RejectedEpoch,
+	},
+};
+
+/// 按事件切出分段，做基线校正并按阈值剔除。
+///
+/// 分段包含事件前后的端点，共 round(pre·fs) + round(post·fs) + 1 个样本；
+/// 要求各通道采样率一致。
+pub fn extract_epochs(
+	signal: &MultiChannelSignal,
+	events: &[EventMarker],
+	config: &EpochConfig,
+) -> Result<EpochSet, SignalProcessError> {
+	if events.is_empty() {
+		return Err(SignalProcessError::EmptyInput);
+	}
+	let sample_rate = signal.uniform_sample_rate().ok_or_else(|| {
+		SignalProcessError::InvalidArgument("分段要求各通道采样率一致".to_string())
+	})?;
+	validate_epoch_config(config)?;
+
+	let pre_samples = (config.pre_secs * sample_rate).round() as usize;
+	let post_samples = (config.post_secs * sample_rate).round() as usize;
+	let epoch_length = pre_samples + post_samples + 1;
+	let times_secs = (0..epoch_length)
+		.map(|index| (index as f32 - pre_samples as f32) / sample_rate)
+		.collect::<Vec<_>>();
+	let baseline = config.baseline_secs.map(|[start, end]| {
+		let to_index = |secs: f32| {
+			(((secs + config.pre_secs) * sample_rate).round().max(0.0) as usize)
+				.min(epoch_length - 1)
+		};
+		(to_index(start), to_index(end))
+	});
+	let signal_length = signal
+		.channel_data()
+		.iter()
+		.map(Vec::len)
+		.min()
+		.unwrap_or(0);
+
+	let mut epoch_set = EpochSet {
+		channel_labels: signal.labels().map(str::to_string).collect(),
+		sample_rate,
+		times_secs,
+		epochs: Vec::new(),
+		rejected: Vec::new(),
+	};
+	for event in events.iter().copied() {
+		let onset = ((event.onset_secs - signal.start_time_secs()) * sample_rate as f64).round();
+		let start = onset - pre_samples as f64;
+		if start < 0.0 || start as usize + epoch_length > signal_length {
+			epoch_set.rejected.push(RejectedEpoch {
+				event,
+				reason: EpochRejectReason::OutOfBounds,
+			});
+			continue;
+		}
+
+		let start = start as usize;
+		let mut data = signal
+			.channel_data()
+			.iter()
+			.map(|channel| channel[start..start + epoch_length].to_vec())
+			.collect::<Vec<_>>();
+		if let Some((baseline_start, baseline_end)) = baseline {
+			for channel in data.iter_mut() {
+				let window = &channel[baseline_start..=baseline_end];
+				let mean = window.iter().sum::<f32>() / window.len() as f32;
+				channel.iter_mut().for_each(|value| *value -= mean);
+			}
+		}
+
+		match check_peak_to_peak(&data, config) {
+			Some(reason) => epoch_set.rejected.push(RejectedEpoch { event, reason }),
+			None => epoch_set.epochs.push(Epoch { event, data }),
+		}
+	}
+	Ok(epoch_set)
+}
+
+/// 对事件类型属于 `codes` 的分段求平均，多个类型会合并为一个条件。
+pub fn average_epochs(
+	epoch_set: &EpochSet,
+	codes: &[u32],
+) -> Result<EvokedResponse, SignalProcessError> {
+	let selected = epoch_set
+		.epochs
+		.iter()
+		.filter(|epoch| codes.contains(&epoch.event.code))
+		.collect::<Vec<_>>();
+	let condition = codes
+		.iter()
+		.map(u32::to_string)
+		.collect::<Vec<_>>()
+		.join("+");
+	if selected.is_empty() {
+		return Err(SignalProcessError::InvalidArgument(format!(
+			"条件 {condition} 没有可用分段"
+		)));
+	}
+
+	let epoch_count = selected.len();
+	let channel_count = epoch_set.channel_labels.len();
+	let sample_count = epoch_set.times_secs.len();
+	let mut mean = vec![vec![0.0; sample_count]; channel_count];
+	let mut standard_error = vec![vec![0.0; sample_count]; channel_count];
+	for channel_index in 0..channel_count {
+		for sample_index in 0..sample_count {
+			let values = selected
+				.iter()
+				.map(|epoch| epoch.data[channel_index][sample_index] as f64);
+			let average = values.clone().sum::<f64>() / epoch_count as f64;
+			mean[channel_index][sample_index] = average as f32;
+			if epoch_count > 1 {
+				let variance = values.map(|value| (value - average).powi(2)).sum::<f64>()
+					/ (epoch_count - 1) as f64;
+				standard_error[channel_index][sample_index] =
+					(variance / epoch_count as f64).sqrt() as f32;
+			}
+		}
+	}
+
+	Ok(EvokedResponse {
+		condition,
+		channel_labels: epoch_set.channel_labels.clone(),
+		times_secs: epoch_set.times_secs.clone(),
+		epoch_count,
+		mean,
+		standard_error,
+	})
+}
+
+/// 按事件类型分别求平均，结果按类型升序排列。
+pub fn average_by_condition(
+	epoch_set: &EpochSet,
+) -> Result<Vec<EvokedResponse>, SignalProcessError> {
+	let mut codes = epoch_set
+		.epochs
+		.iter()
+		.map(|epoch| epoch.event.code)
+		.collect::<Vec<_>>();
+	codes.sort_unstable();
+	codes.dedup();
+	codes
+		.into_iter()
+		.map(|code| average_epochs(epoch_set, &[code]))
+		.collect()
+}
+
+/// 计算差异波 `minuend - subtrahend`，例如目标减非目标得到 P300。
+///
+/// 两个条件视为相互独立，标准误按平方和开方合成。
+pub fn difference_wave(
+	minuend: &EvokedResponse,
+	subtrahend: &EvokedResponse,
+) -> Result<EvokedResponse, SignalProcessError> {
+	if minuend.channel_labels != subtrahend.channel_labels
+		|| minuend.times_secs.len() != subtrahend.times_secs.len()
+	{
+		return Err(SignalProcessError::InvalidArgument(format!(
+			"条件 {} 与 {} 的通道或时间轴不一致",
+			minuend.condition, subtrahend.condition
+		)));
+	}
+
+	let combine = |left: &[Vec<f32>], right: &[Vec<f32>], operation: fn(f32, f32) -> f32| {
+		left.iter()
+			.zip(right.iter())
+			.map(|(left, right)| {
+				left.iter()
+					.zip(right.iter())
+					.map(|(left, right)| operation(*left, *right))
+					.collect()
+			})
+			.collect()
+	};
+	Ok(EvokedResponse {
+		condition: format!("{}-{}", minuend.condition, subtrahend.condition),
+		channel_labels: minuend.channel_labels.clone(),
+		times_secs: minuend.times_secs.clone(),
+		epoch_count: minuend.epoch_count.min(subtrahend.epoch_count),
+		mean: combine(&minuend.mean, &subtrahend.mean, |left, right| left - right),
+		standard_error: combine(
+			&minuend.standard_error,
+			&subtrahend.standard_error,
+			f32::hypot,
+		),
+	})
+}
+
+fn validate_epoch_config(config: &EpochConfig) -> Result<(), SignalProcessError> {
+	if !config.pre_secs.is_finite() || config.pre_secs < 0.0 {
+		return Err(SignalProcessError::InvalidArgument(
+			"事件前时长必须为非负有限值".to_string(),
+		));
+	}
+	if !config.post_secs.is_finite() || config.post_secs <= 0.0 {
+		return Err(SignalProcessError::InvalidArgument(
+			"事件后时长必须为正的有限值".to_string(),
+		));
+	}
+	if let Some([start, end]) = config.baseline_secs
+		&& !(start >= -config.pre_secs && start <= end && end <= config.post_secs)
+	{
+		return Err(SignalProcessError::InvalidArgument(format!(
+			"基线区间 [{start}, {end}] 必须位于分段 [-{}, {}] 内",
+			config.pre_secs, config.post_secs
+		)));
+	}
+	for threshold in [config.reject_peak_to_peak, config.flat_peak_to_peak]
+		.into_iter()
+		.flatten()
+	{
+		if !threshold.is_finite() || threshold <= 0.0 {
+			return Err(SignalProcessError::InvalidArgument(
+				"峰峰值阈值必须为正的有限值".to_string(),
+			));
+		}
+	}
+	Ok(())
+}
+
+fn check_peak_to_peak(data: &[Vec<f32>], config: &EpochConfig) -> Option<EpochRejectReason> {
+	data.iter()
+		.enumerate()
+		.find_map(|(channel_index, channel)| {
+			let (minimum, maximum) = channel.iter().fold(
+				(f32::INFINITY, f32::NEG_INFINITY),
+				|(minimum, maximum), value| (minimum.min(*value), maximum.max(*value)),
+			);
+			let value = maximum - minimum;
+			if config
+				.reject_peak_to_peak
+				.is_some_and(|threshold| value > threshold)
+			{
+				Some(EpochRejectReason::PeakToPeak {
+					channel_index,
+					value,
+				})
+			} else if config
+				.flat_peak_to_peak
+				.is_some_and(|threshold| value < threshold)
+			{
+				Some(EpochRejectReason::Flat {
+					channel_index,
+					value,
+				})
+			} else {
+				None
+			}
+		})
+}
+
+#[cfg(test)]
+mod tests {
+	use std::f32::consts::PI;
+
+	use super::*;
+	use crate::types::SignalBuffer;
+
+	const SAMPLE_RATE: f32 = 100.0;
+
+	/// 在给定时刻叠加 300 ms 处达峰的正半周期波形。
+	fn add_response(samples: &mut [f32], onset_secs: f64, amplitude: f32) {
+		let onset = (onset_secs * SAMPLE_RATE as f64).round() as usize;
+		for offset in 0..=40 {
+			let phase = PI * offset as f32 / 40.0;
+			samples[onset + 10 + offset] += amplitude * phase.sin();
+		}
+	}
+
+	fn build_signal(channels: Vec<Vec<f32>>) -> MultiChannelSignal {
+		let buffers = channels
+			.into_iter()
+			.map(|samples| SignalBuffer {
+				sample_rate: SAMPLE_RATE,
+				samples,
+			})
+			.collect();
+		MultiChannelSignal::from_signal_buffers(buffers, &["Cz", "Pz"], "uV")
+			.unwrap_or_else(|error| panic!("多通道信号创建失败: {error}"))
+	}
+
+	#[test]
+	fn epochs_should_be_baseline_corrected_and_rejected() {
+		let mut cz = vec![5.0; 1000];
+		let pz = vec![-3.0; 1000];
+		add_response(&mut cz, 2.0, 10.0);
+		add_response(&mut cz, 4.0, 10.0);
+		cz[620] = 500.0;
+		let signal = build_signal(vec![cz, pz]);
+		let events = [2.0, 4.0, 6.0, 0.1, 9.5].map(|onset_secs| EventMarker {
+			onset_secs,
+			code: 1,
+		});
+
+		let epoch_set = extract_epochs(&signal, &events, &EpochConfig::default())
+			.unwrap_or_else(|error| panic!("分段失败: {error}"));
+
+		assert_eq!(epoch_set.times_secs.len(), 101);
+		assert!((epoch_set.times_secs[0] + 0.2).abs() < 1e-6);
+		assert_eq!(epoch_set.epochs.len(), 2);
+		let epoch = &epoch_set.epochs[0];
+		assert!(epoch.data[0][..20].iter().all(|value| value.abs() < 1e-6));
+		assert!((epoch.data[0][50] - 10.0).abs() < 1e-4);
+		assert!(epoch.data[1].iter().all(|value| value.abs() < 1e-6));
+		let reasons = epoch_set
+			.rejected
+			.iter()
+			.map(|rejected| (rejected.event.onset_secs, rejected.reason))
+			.collect::<Vec<_>>();
+		assert!(matches!(
+			reasons[0],
+			(
+				6.0,
+				EpochRejectReason::PeakToPeak {
+					channel_index: 0,
+					..
+				}
+			)
+		));
+		assert_eq!(reasons[1], (0.1, EpochRejectReason::OutOfBounds));
+		assert_eq!(reasons[2], (9.5, EpochRejectReason::OutOfBounds));
+	}
+
+	#[test]
+	fn condition_averages_should_yield_difference_wave() {
+		let mut cz = vec![0.0; 2000];
+		let mut events = Vec::new();
+		for trial in 0..8 {
+			let onset_secs = 1.0 + 2.0 * trial as f64;
+			let code = if trial % 2 == 0 { 1 } else { 2 };
+			let amplitude = if code == 1 {
+				8.0 + trial as f32 * 0.5
+			} else {
+				1.0
+			};
+			add_response(&mut cz, onset_secs, amplitude);
+			events.push(EventMarker { onset_secs, code });
+		}
+		let signal = build_signal(vec![cz.clone(), cz]);
+
+		let epoch_set = extract_epochs(&signal, &events, &EpochConfig::default())
+			.unwrap_or_else(|error| panic!("分段失败: {error}"));
+		let averages = average_by_condition(&epoch_set)
+			.unwrap_or_else(|error| panic!("条件平均失败: {error}"));
+		let difference = difference_wave(&averages[0], &averages[1])
+			.unwrap_or_else(|error| panic!("差异波计算失败: {error}"));
+
+		assert_eq!(averages.len(), 2);
+		assert_eq!(averages[0].condition, "1");
+		assert_eq!(averages[0].epoch_count, 4);
+		assert!((averages[0].mean[0][50] - 9.5).abs() < 1e-4);
+		assert!(averages[1].standard_error[0][50].abs() < 1e-6);
+		let target_error = (5.0f32 / 3.0 / 4.0).sqrt();
+		assert!((averages[0].standard_error[0][50] - target_error).abs() < 1e-4);
+		assert_eq!(difference.condition, "1-2");
+		assert!((difference.mean[1][50] - 8.5).abs() < 1e-4);
+		assert!((difference.standard_error[1][50] - target_error).abs() < 1e-4);
+		assert!(average_epochs(&epoch_set, &[3]).is_err());
+	}
+}
@@ -9,4 +9,16 @@ pub enum SignalProcessError {
 	EmptyInput,
 	#[error("IO 错误: {0}")]
 	Io(#[from] std::io::Error),
+	#[error("JSON 解析失败: {0}")]
+	Json(#[from] serde_json::Error),
+}
+
+impl From<eeg_simulator::SimulationError> for SignalProcessError {
+	fn from(error: eeg_simulator::SimulationError) -> Self {
+		match error {
+			eeg_simulator::SimulationError::InvalidArgument(message) => {
+				Self::InvalidArgument(message)
+			}
+		}
+	}
 }
@@ -0,0 +1,565 @@
+use crate::{
+	error::SignalProcessError,
+	spectrum::compute_power_spectrum_with_config,
+	types::{FeatureConfig, SignalBuffer, SignalFeatures, WelchSpectrumConfig},
+};
+
+/// 排列熵支持的最大阶数，阶数的阶乘决定模式计数表的大小。
+const MAX_PERMUTATION_ORDER: usize = 8;
+
+impl SignalFeatures {
+	/// 特征向量中各分量的名称，顺序与 `to_vec` 一致。
+	pub const NAMES: [&'static str; 14] = [
+		"rms",
+		"skewness",
+		"kurtosis",
+		"zero_crossing_rate",
+		"line_length",
+		"hjorth_activity",
+		"hjorth_mobility",
+		"hjorth_complexity",
+		"sample_entropy",
+		"approximate_entropy",
+		"permutation_entropy",
+		"spectral_entropy",
+		"higuchi_fractal_dimension",
+		"katz_fractal_dimension",
+	];
+
+	/// 按 `NAMES` 的顺序展开为特征向量。
+	pub fn to_vec(&self) -> Vec<f32> {
+		vec![
+			self.rms,
+			self.skewness,
+			self.kurtosis,
+			self.zero_crossing_rate,
+			self.line_length,
+			self.hjorth_activity,
+			self.hjorth_mobility,
+			self.hjorth_complexity,
+			self.sample_entropy,
+			self.approximate_entropy,
+			self.permutation_entropy,
+			self.spectral_entropy,
+			self.higuchi_fractal_dimension,
+			self.katz_fractal_dimension,
+		]
+	}
+}
+
+/// 计算单通道的全部特征。
+pub fn compute_features(
+	signal: &SignalBuffer,
+	config: &FeatureConfig,
+) -> Result<SignalFeatures, SignalProcessError> {
+	let samples = &signal.samples;
+	if samples.len() < 2 {
+		return Err(SignalProcessError::InvalidArgument(
+			"特征提取至少需要 2 个样本".to_string(),
+		));
+	}
+
+	let moments = Moments::new(samples);
+	let [hjorth_activity, hjorth_mobility, hjorth_complexity] = compute_hjorth_parameters(samples);
+	let tolerance = config.tolerance_ratio * moments.variance.sqrt();
+	Ok(SignalFeatures {
+		rms: (samples.iter().map(|value| value * value).sum::<f32>() / samples.len() as f32).sqrt(),
+		skewness: moments.skewness(),
+		kurtosis: moments.kurtosis(),
+		zero_crossing_rate: compute_zero_crossing_rate(samples),
+		line_length: compute_line_length(samples),
+		hjorth_activity,
+		hjorth_mobility,
+		hjorth_complexity,
+		sample_entropy: compute_sample_entropy(samples, config.embedding_dimension, tolerance)?,
+		approximate_entropy: compute_approximate_entropy(
+			samples,
+			config.embedding_dimension,
+			tolerance,
+		)?,
+		permutation_entropy: compute_permutation_entropy(
+			samples,
+			config.permutation_order,
+			config.permutation_delay,
+		)?,
+		spectral_entropy: compute_spectral_entropy(signal, config.spectral_segment_length)?,
+		higuchi_fractal_dimension: compute_higuchi_fractal_dimension(
+			samples,
+			config.higuchi_max_k,
+		)?,
+		katz_fractal_dimension: compute_katz_fractal_dimension(samples),
+	})
+}
+
+/// 逐通道计算特征，输出顺序与输入通道一致。
+pub fn compute_features_per_channel(
+	channels: &[SignalBuffer],
+	config: &FeatureConfig,
+) -> Result<Vec<SignalFeatures>, SignalProcessError> {
+	if channels.is_empty() {
+		return Err(SignalProcessError::EmptyInput);
+	}
+	channels
+		.iter()
+		.map(|channel| compute_features(channel, config))
+		.collect()
+}
+
+/// 逐通道计算特征并按通道顺序拼接为一个特征向量，长度为通道数 × 14。
+pub fn compute_feature_vector(
+	channels: &[SignalBuffer],
+	config: &FeatureConfig,
+) -> Result<Vec<f32>, SignalProcessError> {
+	Ok(compute_features_per_channel(channels, config)?
+		.iter()
+		.flat_map(SignalFeatures::to_vec)
+		.collect())
+}
+
+/// Hjorth 参数 [activity, mobility, complexity]，导数用一阶差分近似。
+pub fn compute_hjorth_parameters(samples: &[f32]) -> [f32; 3] {
+	let first = differences(samples);
+	let second = differences(&first);
+	let activity = Moments::new(samples).variance;
+	let first_variance = Moments::new(&first).variance;
+	let second_variance = Moments::new(&second).variance;
+
+	let mobility = ratio_sqrt(first_variance, activity);
+	let complexity = if mobility > 0.0 {
+		ratio_sqrt(second_variance, first_variance) / mobility
+	} else {
+		0.0
+	};
+	[activity, mobility, complexity]
+}
+
+/// 去均值后的过零率。
+pub fn compute_zero_crossing_rate(samples: &[f32]) -> f32 {
+	if samples.len() < 2 {
+		return 0.0;
+	}
+	let mean = Moments::new(samples).mean;
+	let crossings = samples
+		.windows(2)
+		.filter(|pair| (pair[0] - mean >= 0.0) != (pair[1] - mean >= 0.0))
+		.count();
+	crossings as f32 / (samples.len() - 1) as f32
+}
+
+/// 线长，即相邻样本差的绝对值之和。
+pub fn compute_line_length(samples: &[f32]) -> f32 {
+	samples
+		.windows(2)
+		.map(|pair| (pair[1] - pair[0]).abs())
+		.sum()
+}
+
+/// 样本熵 -ln(A / B)，A、B 分别为长度 m + 1 与 m 的匹配模板对数，不计自身匹配。
+///
+/// 没有任何长度 m + 1 的匹配时熵无定义，返回有限上界 ln(K (K - 1) / 2)（K = N - m 为模板数），
+/// 即只有一对匹配且 B 取最大值时的熵，保证特征向量中不出现无穷大。
+///
+/// 方法见 Richman, J. S. & Moorman, J. R. 2000，参考文献：Physiological time-series analysis
+/// using approximate entropy and sample entropy, doi: 10.1152/ajpheart.2000.278.6.H2039
+pub fn compute_sample_entropy(
+	samples: &[f32],
+	embedding_dimension: usize,
+	tolerance: f32,
+) -> Result<f32, SignalProcessError> {
+	validate_embedding(samples, embedding_dimension, tolerance)?;
+
+	let template_count = samples.len() - embedding_dimension;
+	let mut shorter_matches = 0u64;
+	let mut longer_matches = 0u64;
+	for first in 0..template_count {
+		for second in (first + 1)..template_count {
+			if chebyshev_within(samples, first, second, embedding_dimension, tolerance) {
+				shorter_matches += 1;
+				if (samples[first + embedding_dimension] - samples[second + embedding_dimension])
+					.abs() <= tolerance
+				{
+					longer_matches += 1;
+				}
+			}
+		}
+	}
+
+	if longer_matches == 0 {
+		let pair_count = template_count as f64 * (template_count as f64 - 1.0) / 2.0;
+		return Ok(pair_count.ln() as f32);
+	}
+	Ok(-((longer_matches as f64 / shorter_matches as f64).ln()) as f32)
+}
+
+/// 近似熵 Φm - Φm+1，统计匹配时包含模板自身。
+///
+/// 方法见 Pincus, S. M. 1991，参考文献：Approximate entropy as a measure of system complexity,
+/// doi: 10.1073/pnas.88.6.2297
+pub fn compute_approximate_entropy(
+	samples: &[f32],
+	embedding_dimension: usize,
+	tolerance: f32,
+) -> Result<f32, SignalProcessError> {
+	validate_embedding(samples, embedding_dimension, tolerance)?;
+
+	let phi = |dimension: usize| {
+		let template_count = samples.len() - dimension + 1;
+		(0..template_count)
+			.map(|first| {
+				let matches = (0..template_count)
+					.filter(|second| {
+						chebyshev_within(samples, first, *second, dimension, tolerance)
+					})
+					.count();
+				(matches as f64 / template_count as f64).ln()
+			})
+			.sum::<f64>()
+			/ template_count as f64
+	};
+	Ok((phi(embedding_dimension) - phi(embedding_dimension + 1)) as f32)
+}
+
+/// 归一化排列熵，相等的值按出现先后排序。
+///
+/// 方法见 Bandt, C. & Pompe, B. 2002，参考文献：Permutation entropy: a natural complexity
+/// measure for time series, doi: 10.1103/PhysRevLett.88.174102
+pub fn compute_permutation_entropy(
+	samples: &[f32],
+	order: usize,
+	delay: usize,
+) -> Result<f32, SignalProcessError> {
+	if !(2..=MAX_PERMUTATION_ORDER).contains(&order) {
+		return Err(SignalProcessError::InvalidArgument(format!(
+			"排列熵阶数必须位于 [2, {MAX_PERMUTATION_ORDER}] 内"
+		)));
+	}
+	if delay == 0 {
+		return Err(SignalProcessError::InvalidArgument(
+			"排列熵延迟必须大于 0".to_string(),
+		));
+	}
+	let span = (order - 1) * delay;
+	if samples.len() <= span {
+		return Err(SignalProcessError::InvalidArgument(format!(
+			"样本数 {} 不足以构造 {order} 阶排列模式",
+			samples.len()
+		)));
+	}
+
+	let pattern_count = (1..=order).product::<usize>();
+	let mut counts = vec![0usize; pattern_count];
+	let mut indices = vec![0usize; order];
+	for start in 0..samples.len() - span {
+		for (position, index) in indices.iter_mut().enumerate() {
+			*index = position;
+		}
+		indices.sort_by(|left, right| {
+			samples[start + left * delay].total_cmp(&samples[start + right * delay])
+		});
+		counts[lehmer_code(&indices)] += 1;
+	}
+
+	let total = (samples.len() - span) as f64;
+	let entropy = counts
+		.iter()
+		.filter(|count| **count > 0)
+		.map(|count| {
+			let probability = *count as f64 / total;
+			-probability * probability.ln()
+		})
+		.sum::<f64>();
+	Ok((entropy / (pattern_count as f64).ln()) as f32)
+}
+
+/// 以 Welch 功率谱为概率分布的归一化 Shannon 熵。
+pub fn compute_spectral_entropy(
+	signal: &SignalBuffer,
+	segment_length: usize,
+) -> Result<f32, SignalProcessError> {
+	let segment_length = segment_length.min(signal.samples.len());
+	let spectrum = compute_power_spectrum_with_config(
+		signal,
+		WelchSpectrumConfig {
+			segment_length,
+			overlap_length: segment_length / 2,
+		},
+	)?;
+	let total = spectrum.iter().map(|point| point.value as f64).sum::<f64>();
+	if total <= 0.0 {
+		return Ok(0.0);
+	}
+
+	let entropy = spectrum
+		.iter()
+		.filter(|point| point.value > 0.0)
+		.map(|point| {
+			let probability = point.value as f64 / total;
+			-probability * probability.ln()
+		})
+		.sum::<f64>();
+	Ok((entropy / (spectrum.len() as f64).ln()) as f32)
+}
+
+/// Higuchi 分形维数，取 ln L(k) 对 ln(1/k) 的最小二乘斜率。
+///
+/// 方法见 Higuchi, T. 1988，参考文献：Approach to an irregular time series on the basis of
+/// the fractal theory, doi: 10.1016/0167-2789(88)90081-4
+pub fn compute_higuchi_fractal_dimension(
+	samples: &[f32],
+	max_k: usize,
+) -> Result<f32, SignalProcessError> {
+	if max_k < 2 {
+		return Err(SignalProcessError::InvalidArgument(
+			"Higuchi 最大尺度必须至少为 2".to_string(),
+		));
+	}
+	if samples.len() <= 2 * max_k {
+		return Err(SignalProcessError::InvalidArgument(format!(
+			"样本数 {} 不足以计算最大尺度为 {max_k} 的 Higuchi 分形维数",
+			samples.len()
+		)));
+	}
+
+	let last = samples.len() - 1;
+	let points = (1..=max_k)
+		.map(|k| {
+			let length = (0..k)
+				.map(|offset| {
+					let step_count = (last - offset) / k;
+					let curve = (1..=step_count)
+						.map(|step| {
+							(samples[offset + step * k] - samples[offset + (step - 1) * k]).abs()
+								as f64
+						})
+						.sum::<f64>();
+					curve * last as f64 / (step_count * k) as f64 / k as f64
+				})
+				.sum::<f64>()
+				/ k as f64;
+			((1.0 / k as f64).ln(), length.max(f64::MIN_POSITIVE).ln())
+		})
+		.collect::<Vec<_>>();
+
+	let count = points.len() as f64;
+	let mean_x = points.iter().map(|(x, _)| x).sum::<f64>() / count;
+	let mean_y = points.iter().map(|(_, y)| y).sum::<f64>() / count;
+	let covariance = points
+		.iter()
+		.map(|(x, y)| (x - mean_x) * (y - mean_y))
+		.sum::<f64>();
+	let variance = points
+		.iter()
+		.map(|(x, _)| (x - mean_x).powi(2))
+		.sum::<f64>();
+	Ok((covariance / variance) as f32)
+}
+
+/// Katz 分形维数 log10(n) / (log10(n) + log10(d / L))，n 为步数，L 为曲线长度，
+/// d 为离首个样本的最大距离，与 antropy.katz_fd 一致。
+pub fn compute_katz_fractal_dimension(samples: &[f32]) -> f32 {
+	let length = compute_line_length(samples) as f64;
+	let extent = samples
+		.iter()
+		.skip(1)
+		.map(|value| (value - samples[0]).abs() as f64)
+		.fold(0.0, f64::max);
+	if length <= 0.0 || extent <= 0.0 {
+		return 1.0;
+	}
+
+	let steps = ((samples.len() - 1) as f64).log10();
+	(steps / (steps + (extent / length).log10())) as f32
+}
+
+/// 均值与二至四阶中心矩。
+struct Moments {
+	mean: f32,
+	variance: f32,
+	third: f32,
+	fourth: f32,
+}
+
+impl Moments {
+	fn new(samples: &[f32]) -> Self {
+		let count = samples.len().max(1) as f64;
+		let mean = samples.iter().map(|value| *value as f64).sum::<f64>() / count;
+		let [variance, third, fourth] = [2, 3, 4].map(|power| {
+			samples
+				.iter()
+				.map(|value| (*value as f64 - mean).powi(power))
+				.sum::<f64>()
+				/ count
+		});
+		Self {
+			mean: mean as f32,
+			variance: variance as f32,
+			third: third as f32,
+			fourth: fourth as f32,
+		}
+	}
+
+	fn skewness(&self) -> f32 {
+		if self.variance <= 0.0 {
+			return 0.0;
+		}
+		self.third / self.variance.powf(1.5)
+	}
+
+	fn kurtosis(&self) -> f32 {
+		if self.variance <= 0.0 {
+			return 0.0;
+		}
+		self.fourth / (self.variance * self.variance) - 3.0
+	}
+}
+
+fn differences(samples: &[f32]) -> Vec<f32> {
+	samples.windows(2).map(|pair| pair[1] - pair[0]).collect()
+}
+
+fn ratio_sqrt(numerator: f32, denominator: f32) -> f32 {
+	if denominator <= 0.0 {
+		return 0.0;
+	}
+	(numerator / denominator).sqrt()
+}
+
+fn validate_embedding(
+	samples: &[f32],
+	embedding_dimension: usize,
+	tolerance: f32,
+) -> Result<(), SignalProcessError> {
+	if embedding_dimension == 0 {
+		return Err(SignalProcessError::InvalidArgument(
+			"嵌入维数必须大于 0".to_string(),
+		));
+	}
+	if samples.len() <= embedding_dimension + 1 {
+		return Err(SignalProcessError::InvalidArgument(format!(
+			"样本数 {} 不足以构造 {embedding_dimension} 维模板",
+			samples.len()
+		)));
+	}
+	if !tolerance.is_finite() || tolerance < 0.0 {
+		return Err(SignalProcessError::InvalidArgument(
+			"熵容差必须为非负有限值".to_string(),
+		));
+	}
+	Ok(())
+}
+
+/// 两个以 first、second 开始、长度为 dimension 的模板的 Chebyshev 距离是否不超过容差。
+fn chebyshev_within(
+	samples: &[f32],
+	first: usize,
+	second: usize,
+	dimension: usize,
+	tolerance: f32,
+) -> bool {
+	samples[first..first + dimension]
+		.iter()
+		.zip(samples[second..second + dimension].iter())
+		.all(|(left, right)| (left - right).abs() <= tolerance)
+}
+
+/// 排列在全部排列中的字典序序号。
+fn lehmer_code(permutation: &[usize]) -> usize {
+	permutation
+		.iter()
+		.enumerate()
+		.fold(0, |code, (position, value)| {
+			let smaller_after = permutation[position + 1..]
+				.iter()
+				.filter(|later| *later < value)
+				.count();
+			code * (permutation.len() - position) + smaller_after
+		})
+}
+
+#[cfg(test)]
+mod tests {
+	use std::f32::consts::{PI, TAU};
+
+	use super::*;
+	use crate::test_support;
+
+	const SAMPLE_RATE: f32 = 256.0;
+
+	fn sine() -> SignalBuffer {
+		SignalBuffer {
+			sample_rate: SAMPLE_RATE,
+			samples: (0..1024)
+				.map(|index| 3.0 * (TAU * 10.0 * index as f32 / SAMPLE_RATE).sin())
+				.collect(),
+		}
+	}
+
+	/// 确定性的均匀白噪声，取值 [-0.5, 0.5)。
+	fn white_noise() -> SignalBuffer {
+		SignalBuffer {
+			sample_rate: SAMPLE_RATE,
+			samples: test_support::white_noise(2024, 1024),
+		}
+	}
+
+	#[test]
+	fn sine_features_should_match_closed_forms() {
+		let features = compute_features(&sine(), &FeatureConfig::default())
+			.unwrap_or_else(|error| panic!("特征提取失败: {error}"));
+
+		assert!((features.rms - 3.0 / 2.0f32.sqrt()).abs() < 1e-3);
+		assert!(features.skewness.abs() < 1e-3);
+		assert!((features.kurtosis + 1.5).abs() < 1e-3);
+		assert!((features.zero_crossing_rate - 80.0 / 1023.0).abs() < 2e-3);
+		assert!((features.hjorth_activity - 4.5).abs() < 1e-2);
+		let expected_mobility = 2.0 * (PI * 10.0 / SAMPLE_RATE).sin();
+		assert!((features.hjorth_mobility - expected_mobility).abs() < 1e-3);
+		assert!((features.hjorth_complexity - 1.0).abs() < 1e-2);
+		assert!(features.spectral_entropy < 0.3);
+		assert!(features.permutation_entropy < 0.6);
+		let higuchi = compute_higuchi_fractal_dimension(&sine().samples, 4)
+			.unwrap_or_else(|error| panic!("Higuchi 分形维数计算失败: {error}"));
+		assert!((higuchi - 1.0).abs() < 0.05);
+	}
+
+	#[test]
+	fn irregular_signal_should_have_higher_complexity() {
+		let config = FeatureConfig::default();
+		let regular = compute_features(&sine(), &config)
+			.unwrap_or_else(|error| panic!("特征提取失败: {error}"));
+		let noise = compute_features(&white_noise(), &config)
+			.unwrap_or_else(|error| panic!("特征提取失败: {error}"));
+
+		assert!(noise.sample_entropy > 1.5 && regular.sample_entropy < 0.3);
+		assert!(noise.approximate_entropy > regular.approximate_entropy + 0.5);
+		assert!(noise.permutation_entropy > 0.98);
+		assert!(noise.spectral_entropy > 0.9);
+		assert!((noise.higuchi_fractal_dimension - 2.0).abs() < 0.1);
+		assert!(noise.katz_fractal_dimension > regular.katz_fractal_dimension);
+		assert!((noise.kurtosis + 1.2).abs() < 0.1);
+		let ramp = (0..64).map(|index| index as f32).collect::<Vec<_>>();
+		let ramp_entropy = compute_permutation_entropy(&ramp, 4, 2)
+			.unwrap_or_else(|error| panic!("排列熵计算失败: {error}"));
+		assert_eq!(ramp_entropy, 0.0);
+	}
+
+	#[test]
+	fn feature_vector_should_concatenate_channels() {
+		let vector = compute_feature_vector(&[sine(), white_noise()], &FeatureConfig::default())
+			.unwrap_or_else(|error| panic!("特征向量计算失败: {error}"));
+
+		assert_eq!(vector.len(), 2 * SignalFeatures::NAMES.len());
+		assert!(vector.iter().all(|value| value.is_finite()));
+		assert!(compute_feature_vector(&[], &FeatureConfig::default()).is_err());
+	}
+
+	#[test]
+	fn sample_entropy_without_matches_should_return_finite_cap() {
+		let ramp = (0..20).map(|index| index as f32).collect::<Vec<_>>();
+
+		let entropy = compute_sample_entropy(&ramp, 2, 0.1)
+			.unwrap_or_else(|error| panic!("样本熵计算失败: {error}"));
+
+		assert!((entropy - (18.0f32 * 17.0 / 2.0).ln()).abs() < 1e-5);
+	}
+}
@@ -1,8 +1,307 @@
 use crate::{
 	error::SignalProcessError,
-	types::{IirCoefficients, SignalBuffer},
+	types::{IirCoefficients, SignalBuffer, SosCoefficients},
 };
 
+/// 可按块连续处理的流式滤波器。
+///
+/// 实现者需要在内部保留延迟线，使分块处理的结果与一次性处理整段信号逐点一致。
+pub trait StreamingFilter {
+	/// 处理单个样本并返回输出。
+	fn process_sample(&mut self, input: f32) -> f32;
+
+	/// 清空内部状态，恢复到零初始条件。
+	fn reset(&mut self);
+
+	/// 滤波器阶数，用于确定零相位滤波的默认边缘延拓长度。
+	fn order(&self) -> usize;
+
+	/// 将内部状态设置为输入恒为 `value` 时的稳态。
+	fn initialize_steady_state(&mut self, value: f32);
+
+	/// 输出相对输入的额外固定延迟（样本数），分块缓存的实现需要覆盖此方法。
+	fn latency(&self) -> usize {
+		0
+	}
+
+	/// 处理一个数据块，输出写入 `output`。
+	fn process_block(
+		&mut self,
+		input: &[f32],
+		output: &mut [f32],
+	) -> Result<(), SignalProcessError> {
+		if input.len() != output.len() {
+			return Err(SignalProcessError::InvalidArgument(format!(
+				"输入与输出长度不一致: {} != {}",
+				input.len(),
+				output.len()
+			)));
+		}
+		for (sample, output_value) in input.iter().zip(output.iter_mut()) {
+			*output_value = self.process_sample(*sample);
+		}
+		Ok(())
+	}
+
+	/// 原地处理一个数据块。
+	fn process_in_place(&mut self, samples: &mut [f32]) {
+		for sample in samples.iter_mut() {
+			*sample = self.process_sample(*sample);
+		}
+	}
+}
+
+/// 带延迟线的流式 FIR 滤波器。
+#[derive(Debug, Clone)]
+pub struct FirFilter {
+	coeffs: Vec<f32>,
+	/// 环形延迟线，`history[position]` 为最近一次输入。
+	history: Vec<f32>,
+	position: usize,
+}
+
+impl FirFilter {
+	/// 以给定系数创建 FIR 滤波器。
+	pub fn new(coeffs: &[f32]) -> Result<Self, SignalProcessError> {
+		if coeffs.is_empty() {
+			return Err(SignalProcessError::InvalidArgument(
+				"FIR 系数不能为空".to_string(),
+			));
+		}
+
+		Ok(Self {
+			coeffs: coeffs.to_vec(),
+			history: vec![0.0; coeffs.len()],
+			position: 0,
+		})
+	}
+
+	/// 获取滤波器系数。
+	pub fn coeffs(&self) -> &[f32] {
+		&self.coeffs
+	}
+}
+
+impl StreamingFilter for FirFilter {
+	fn process_sample(&mut self, input: f32) -> f32 {
+		let tap_count = self.coeffs.len();
+		self.position = if self.position == 0 {
+			tap_count - 1
+		} else {
+			self.position - 1
+		};
+		self.history[self.position] = input;
+
+		// 按系数下标升序累加，保证与一次性滤波的求和顺序一致。
+		let mut value = 0.0;
+		let mut history_index = self.position;
+		for coeff in self.coeffs.iter() {
+			value += coeff * self.history[history_index];
+			history_index += 1;
+			if history_index == tap_count {
+				history_index = 0;
+			}
+		}
+		value
+	}
+
+	fn reset(&mut self) {
+		self.history.fill(0.0);
+		self.position = 0;
+	}
+
+	fn order(&self) -> usize {
+		self.coeffs.len() - 1
+	}
+
+	fn initialize_steady_state(&mut self, value: f32) {
+		self.history.fill(value);
+		self.position = 0;
+	}
+}
+
+/// 带状态的流式二阶 IIR 滤波器（直接 I 型）。
+#[derive(Debug, Clone)]
+pub struct IirFilter {
+	coeffs: IirCoefficients,
+	x1: f32,
+	x2: f32,
+	y1: f32,
+	y2: f32,
+}
+
+impl IirFilter {
+	/// 以给定系数创建 IIR 滤波器。
+	pub fn new(coeffs: &IirCoefficients) -> Self {
+		Self {
+			coeffs: *coeffs,
+			x1: 0.0,
+			x2: 0.0,
+			y1: 0.0,
+			y2: 0.0,
+		}
+	}
+
+	/// 获取滤波器系数。
+	pub fn coeffs(&self) -> &IirCoefficients {
+		&self.coeffs
+	}
+}
+
+impl StreamingFilter for IirFilter {
+	fn process_sample(&mut self, input: f32) -> f32 {
+		let output =
+			self.coeffs.b[0] * input + self.coeffs.b[1] * self.x1 + self.coeffs.b[2] * self.x2
+				- self.coeffs.a[1] * self.y1
+				- self.coeffs.a[2] * self.y2;
+		self.x2 = self.x1;
+		self.x1 = input;
+		self.y2 = self.y1;
+		self.y1 = output;
+		output
+	}
+
+	fn reset(&mut self) {
+		self.x1 = 0.0;
+		self.x2 = 0.0;
+		self.y1 = 0.0;
+		self.y2 = 0.0;
+	}
+
+	fn order(&self) -> usize {
+		2
+	}
+
+	fn initialize_steady_state(&mut self, value: f32) {
+		let output = value * dc_gain(&self.coeffs);
+		self.x1 = value;
+		self.x2 = value;
+		self.y1 = output;
+		self.y2 = output;
+	}
+}
+
+/// 由多个二阶节级联组成的流式 IIR 滤波器。
+#[derive(Debug, Clone)]
+pub struct SosFilter {
+	sections: Vec<IirFilter>,
+}
+
+impl SosFilter {
+	/// 以给定二阶节系数创建级联滤波器。
+	pub fn new(sos: &SosCoefficients) -> Result<Self, SignalProcessError> {
+		if sos.sections.is_empty() {
+			return Err(SignalProcessError::InvalidArgument(
+				"SOS 二阶节不能为空".to_string(),
+			));
+		}
+
+		Ok(Self {
+			sections: sos.sections.iter().map(IirFilter::new).collect(),
+		})
+	}
+}
+
+impl StreamingFilter for SosFilter {
+	fn process_sample(&mut self, input: f32) -> f32 {
+		self.sections
+			.iter_mut()
+			.fold(input, |value, section| section.process_sample(value))
+	}
+
+	fn reset(&mut self) {
+		for section in self.sections.iter_mut() {
+			section.reset();
+		}
+	}
+
+	fn order(&self) -> usize {
+		2 * self.sections.len()
+	}
+
+	fn initialize_steady_state(&mut self, value: f32) {
+		let mut input = value;
+		for section in self.sections.iter_mut() {
+			section.initialize_steady_state(input);
+			input *= dc_gain(section.coeffs());
+		}
+	}
+}
+
+/// 二阶节在 z = 1 处的直流增益，极点位于 z = 1 时按 0 处理。
+fn dc_gain(coeffs: &IirCoefficients) -> f32 {
+	let denominator = coeffs.a.iter().sum::<f32>();
+	if denominator.abs() <= f32::EPSILON {
+		return 0.0;
+	}
+	coeffs.b.iter().sum::<f32>() / denominator
+}
+
+/// 多通道滤波器组，每个通道持有独立的滤波状态。
+#[derive(Debug, Clone)]
+pub struct FilterBank<F> {
+	filters: Vec<F>,
+}
+
+impl<F: StreamingFilter + Clone> FilterBank<F> {
+	/// 以同一个原型滤波器为每个通道创建独立副本。
+	pub fn new(prototype: &F, channel_count: usize) -> Result<Self, SignalProcessError> {
+		if channel_count == 0 {
+			return Err(SignalProcessError::InvalidArgument(
+				"通道数必须大于 0".to_string(),
+			));
+		}
+
+		let mut filter = prototype.clone();
+		filter.reset();
+		Ok(Self {
+			filters: vec![filter; channel_count],
+		})
+	}
+}
+
+impl<F: StreamingFilter> FilterBank<F> {
+	/// 获取通道数。
+	pub fn channel_count(&self) -> usize {
+		self.filters.len()
+	}
+
+	/// 获取指定通道的滤波器。
+	pub fn channel_mut(&mut self, channel_index: usize) -> Option<&mut F> {
+		self.filters.get_mut(channel_index)
+	}
+
+	/// 处理一块多通道数据，格式为 `[channel][samples]`。
+	pub fn process(&mut self, chunk: &[Vec<f32>]) -> Result<Vec<Vec<f32>>, SignalProcessError> {
+		let mut output = chunk.to_vec();
+		self.process_in_place(&mut output)?;
+		Ok(output)
+	}
+
+	/// 原地处理一块多通道数据，格式为 `[channel][samples]`。
+	pub fn process_in_place(&mut self, chunk: &mut [Vec<f32>]) -> Result<(), SignalProcessError> {
+		if chunk.len() != self.filters.len() {
+			return Err(SignalProcessError::InvalidArgument(format!(
+				"通道数不匹配: 期望 {}, 实际 {}",
+				self.filters.len(),
+				chunk.len()
+			)));
+		}
+
+		for (filter, channel) in self.filters.iter_mut().zip(chunk.iter_mut()) {
+			filter.process_in_place(channel);
+		}
+		Ok(())
+	}
+
+	/// 清空所有通道的状态。
+	pub fn reset(&mut self) {
+		for filter in self.filters.iter_mut() {
+			filter.reset();
+		}
+	}
+}
+
 /// 对序列执行 FIR 滤波。
 pub fn apply_fir(
 	signal: &SignalBuffer,
@@ -11,22 +310,10 @@ pub fn apply_fir(
 	if signal.samples.is_empty() {
 		return Err(SignalProcessError::EmptyInput);
 	}
-	if coeffs.is_empty() {
-		return Err(SignalProcessError::InvalidArgument(
-			"FIR 系数不能为空".to_string(),
-		));
-	}
 
-	let mut output = vec![0.0; signal.samples.len()];
-	for (output_index, output_value) in output.iter_mut().enumerate() {
-		let mut value = 0.0;
-		for (coeff_index, coeff) in coeffs.iter().enumerate() {
-			if output_index >= coeff_index {
-				value += coeff * signal.samples[output_index - coeff_index];
-			}
-		}
-		*output_value = value;
-	}
+	let mut filter = FirFilter::new(coeffs)?;
+	let mut output = signal.samples.clone();
+	filter.process_in_place(&mut output);
 
 	Ok(SignalBuffer {
 		sample_rate: signal.sample_rate,
@@ -43,42 +330,197 @@ pub fn apply_iir(
 		return Err(SignalProcessError::EmptyInput);
 	}
 
-	let mut output = vec![0.0; signal.samples.len()];
-	for index in 0..signal.samples.len() {
-		let x0 = signal.samples[index];
-		let x1 = if index >= 1 {
-			signal.samples[index - 1]
-		} else {
-			0.0
-		};
-		let x2 = if index >= 2 {
-			signal.samples[index - 2]
-		} else {
-			0.0
-		};
-		let y1 = if index >= 1 { output[index - 1] } else { 0.0 };
-		let y2 = if index >= 2 { output[index - 2] } else { 0.0 };
+	let mut filter = IirFilter::new(coeffs);
+	let mut output = signal.samples.clone();
+	filter.process_in_place(&mut output);
 
-		output[index] = coeffs.b[0] * x0 + coeffs.b[1] * x1 + coeffs.b[2] * x2
-			- coeffs.a[1] * y1
-			- coeffs.a[2] * y2;
+	Ok(SignalBuffer {
+		sample_rate: signal.sample_rate,
+		samples: output,
+	})
+}
+
+/// 对序列执行二阶节级联 IIR 滤波。
+pub fn apply_sos(
+	signal: &SignalBuffer,
+	sos: &SosCoefficients,
+) -> Result<SignalBuffer, SignalProcessError> {
+	if signal.samples.is_empty() {
+		return Err(SignalProcessError::EmptyInput);
 	}
 
+	let mut filter = SosFilter::new(sos)?;
+	let mut output = signal.samples.clone();
+	filter.process_in_place(&mut output);
+
 	Ok(SignalBuffer {
 		sample_rate: signal.sample_rate,
 		samples: output,
 	})
 }
 
+/// 零相位滤波的边缘延拓方式。
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PadType {
+	/// 以端点为中心做奇对称延拓。
+	Odd,
+	/// 以端点为轴做偶对称延拓。
+	Even,
+	/// 重复端点值。
+	Constant,
+	/// 不做延拓。
+	None,
+}
+
+/// 零相位滤波配置。
+#[derive(Debug, Clone, Copy)]
+pub struct FiltfiltConfig {
+	pub pad_type: PadType,
+
+	/// 每侧延拓的样本数，为空时取 3 × (阶数 + 1)。
+	pub pad_length: Option<usize>,
+}
+
+impl Default for FiltfiltConfig {
+	fn default() -> Self {
+		Self {
+			pad_type: PadType::Odd,
+			pad_length: None,
+		}
+	}
+}
+
+/// 使用任意流式滤波器做前向-后向零相位滤波。
+///
+/// 两个方向都以延拓后首个样本的稳态作为初始条件，算法与 scipy.signal.filtfilt 一致，
+/// 结果幅度响应为原滤波器的平方且无相位延迟。带固定延迟的滤波器（见
+/// [`StreamingFilter::latency`]）会在每个方向扣除延迟，结果与无延迟实现一致。
+pub fn filtfilt<F: StreamingFilter + Clone>(
+	filter: &F,
+	signal: &SignalBuffer,
+	config: FiltfiltConfig,
+) -> Result<SignalBuffer, SignalProcessError> {
+	if signal.samples.is_empty() {
+		return Err(SignalProcessError::EmptyInput);
+	}
+
+	let pad_length = match config.pad_type {
+		PadType::None => 0,
+		_ => config.pad_length.unwrap_or(3 * (filter.order() + 1)),
+	};
+	if pad_length >= signal.samples.len() {
+		return Err(SignalProcessError::InvalidArgument(format!(
+			"输入长度 {} 必须大于边缘延拓长度 {pad_length}",
+			signal.samples.len()
+		)));
+	}
+
+	let mut extended = pad_signal(&signal.samples, config.pad_type, pad_length);
+	let mut filter = filter.clone();
+
+	filter_pass(&mut filter, &mut extended);
+	extended.reverse();
+	filter_pass(&mut filter, &mut extended);
+	extended.reverse();
+
+	Ok(SignalBuffer {
+		sample_rate: signal.sample_rate,
+		samples: extended[pad_length..pad_length + signal.samples.len()].to_vec(),
+	})
+}
+
+/// 以首个样本的稳态为初始条件单向滤波，并扣除滤波器的固定延迟使输出与输入对齐。
+///
+/// 末尾补 `latency` 个末值样本把最后的输出推出缓存，因果滤波下它们不影响保留的输出。
+fn filter_pass<F: StreamingFilter>(filter: &mut F, samples: &mut Vec<f32>) {
+	let latency = filter.latency();
+	let (first, last) = (samples[0], samples[samples.len() - 1]);
+	filter.reset();
+	filter.initialize_steady_state(first);
+	samples.extend(std::iter::repeat_n(last, latency));
+	filter.process_in_place(samples);
+	samples.drain(..latency);
+}
+
+/// 对序列执行零相位 FIR 滤波。
+pub fn filtfilt_fir(
+	signal: &SignalBuffer,
+	coeffs: &[f32],
+	config: FiltfiltConfig,
+) -> Result<SignalBuffer, SignalProcessError> {
+	filtfilt(&FirFilter::new(coeffs)?, signal, config)
+}
+
+/// 对序列执行零相位二阶 IIR 滤波。
+pub fn filtfilt_iir(
+	signal: &SignalBuffer,
+	coeffs: &IirCoefficients,
+	config: FiltfiltConfig,
+) -> Result<SignalBuffer, SignalProcessError> {
+	filtfilt(&IirFilter::new(coeffs), signal, config)
+}
+
+/// 对序列执行零相位二阶节级联 IIR 滤波。
+pub fn filtfilt_sos(
+	signal: &SignalBuffer,
+	sos: &SosCoefficients,
+	config: FiltfiltConfig,
+) -> Result<SignalBuffer, SignalProcessError> {
+	filtfilt(&SosFilter::new(sos)?, signal, config)
+}
+
+/// 按指定方式在序列两侧各延拓 `pad_length` 个样本。
+fn pad_signal(samples: &[f32], pad_type: PadType, pad_length: usize) -> Vec<f32> {
+	let first = samples[0];
+	let last = samples[samples.len() - 1];
+	let last_index = samples.len() - 1;
+	let mut extended = Vec::with_capacity(samples.len() + 2 * pad_length);
+
+	extended.extend((1..=pad_length).rev().map(|offset| match pad_type {
+		PadType::Odd => 2.0 * first - samples[offset],
+		PadType::Even => samples[offset],
+		PadType::Constant | PadType::None => first,
+	}));
+	extended.extend_from_slice(samples);
+	extended.extend((1..=pad_length).map(|offset| match pad_type {
+		PadType::Odd => 2.0 * last - samples[last_index - offset],
+		PadType::Even => samples[last_index - offset],
+		PadType::Constant | PadType::None => last,
+	}));
+	extended
+}
+
 #[cfg(test)]
 mod tests {
 	use crate::{
-		FilterKind, FirDesignConfig, IirDesignConfig, design_fir, design_iir,
-		generate_composite_signal,
+		FilterKind, FirDesignConfig, IirDesignConfig, IirPrototype, SosDesignConfig, WindowKind,
+		design_fir, design_iir, design_sos, generate_composite_signal,
 	};
 
 	use super::*;
 
+	/// 朴素的因果直接卷积，作为 `apply_fir` 与流式 FIR 的独立参考。
+	fn direct_convolution(samples: &[f32], coeffs: &[f32]) -> Vec<f32> {
+		(0..samples.len())
+			.map(|output_index| {
+				coeffs
+					.iter()
+					.enumerate()
+					.filter(|(coeff_index, _)| *coeff_index <= output_index)
+					.map(|(coeff_index, coeff)| coeff * samples[output_index - coeff_index])
+					.sum()
+			})
+			.collect()
+	}
+
+	fn max_abs_difference(left: &[f32], right: &[f32]) -> f32 {
+		assert_eq!(left.len(), right.len());
+		left.iter()
+			.zip(right)
+			.map(|(left, right)| (left - right).abs())
+			.fold(0.0, f32::max)
+	}
+
 	#[test]
 	fn fir_filter_should_keep_same_length() {
 		let signal = generate_composite_signal(
@@ -103,12 +545,15 @@ mod tests {
 			filter_kind: FilterKind::LowPass,
 			cutoff_hz: vec![20.0],
 			tap_count: 101,
+			window: WindowKind::Hamming,
 		})
 		.unwrap_or_else(|error| panic!("FIR 设计失败: {error}"));
 
 		let filtered =
 			apply_fir(&signal, &coeffs).unwrap_or_else(|error| panic!("FIR 滤波失败: {error}"));
 		assert_eq!(filtered.samples.len(), signal.samples.len());
+		let reference = direct_convolution(&signal.samples, &coeffs);
+		assert!(max_abs_difference(&filtered.samples, &reference) < 1e-5);
 	}
 
 	#[test]
@@ -129,4 +574,304 @@ mod tests {
 			apply_iir(&signal, &coeffs).unwrap_or_else(|error| panic!("IIR 滤波失败: {error}"));
 		assert_eq!(filtered.samples.len(), signal.samples.len());
 	}
+
+	#[test]
+	fn streaming_fir_should_match_one_shot_across_chunks() {
+		let signal = generate_composite_signal(
+			256.0,
+			2.0,
+			&[
+				crate::CompositeComponent {
+					frequency: 10.0,
+					amplitude: 1.0,
+					phase: 0.0,
+				},
+				crate::CompositeComponent {
+					frequency: 60.0,
+					amplitude: 0.5,
+					phase: 0.3,
+				},
+			],
+		)
+		.unwrap_or_else(|error| panic!("复合信号生成失败: {error}"));
+		let coeffs = design_fir(&FirDesignConfig {
+			sample_rate: 256.0,
+			filter_kind: FilterKind::LowPass,
+			cutoff_hz: vec![30.0],
+			tap_count: 31,
+			window: WindowKind::Hamming,
+		})
+		.unwrap_or_else(|error| panic!("FIR 设计失败: {error}"));
+		let one_shot =
+			apply_fir(&signal, &coeffs).unwrap_or_else(|error| panic!("FIR 滤波失败: {error}"));
+		let reference = direct_convolution(&signal.samples, &coeffs);
+
+		let mut filter = FirFilter::new(&coeffs).unwrap_or_else(|error| panic!("{error}"));
+		let mut streamed = Vec::with_capacity(signal.samples.len());
+		for chunk in signal.samples.chunks(7) {
+			let mut output = vec![0.0; chunk.len()];
+			filter
+				.process_block(chunk, &mut output)
+				.unwrap_or_else(|error| panic!("流式 FIR 失败: {error}"));
+			streamed.extend(output);
+		}
+
+		assert!(max_abs_difference(&one_shot.samples, &reference) < 1e-5);
+		assert!(max_abs_difference(&streamed, &reference) < 1e-5);
+	}
+
+	#[test]
+	fn iir_filter_bank_should_match_one_shot_per_channel() {
+		let coeffs = design_iir(&IirDesignConfig {
+			sample_rate: 256.0,
+			filter_kind: FilterKind::HighPass,
+			cutoff_hz: [5.0, 0.0],
+			q: 1.0 / 2.0_f32.sqrt(),
+		})
+		.unwrap_or_else(|error| panic!("IIR 设计失败: {error}"));
+		let channels: Vec<Vec<f32>> = (0..3)
+			.map(|channel| {
+				(0..200)
+					.map(|index| ((index * (channel + 1)) as f32 * 0.1).sin() + channel as f32)
+					.collect()
+			})
+			.collect();
+
+		let mut bank = FilterBank::new(&IirFilter::new(&coeffs), channels.len())
+			.unwrap_or_else(|error| panic!("{error}"));
+		let mut streamed = vec![Vec::new(); channels.len()];
+		for start in (0..200).step_by(64) {
+			let end = (start + 64).min(200);
+			let chunk: Vec<Vec<f32>> = channels
+				.iter()
+				.map(|channel| channel[start..end].to_vec())
+				.collect();
+			let output = bank
+				.process(&chunk)
+				.unwrap_or_else(|error| panic!("滤波器组处理失败: {error}"));
+			for (target, values) in streamed.iter_mut().zip(output) {
+				target.extend(values);
+			}
+		}
+
+		for (channel, streamed_channel) in channels.iter().zip(streamed.iter()) {
+			let expected = apply_iir(
+				&SignalBuffer {
+					sample_rate: 256.0,
+					samples: channel.clone(),
+				},
+				&coeffs,
+			)
+			.unwrap_or_else(|error| panic!("IIR 滤波失败: {error}"));
+			assert_eq!(streamed_channel, &expected.samples);
+		}
+	}
+
+	#[test]
+	fn sos_filter_should_attenuate_out_of_band_component() {
+		let signal = generate_composite_signal(
+			256.0,
+			4.0,
+			&[
+				crate::CompositeComponent {
+					frequency: 10.0,
+					amplitude: 1.0,
+					phase: 0.0,
+				},
+				crate::CompositeComponent {
+					frequency: 80.0,
+					amplitude: 1.0,
+					phase: 0.0,
+				},
+			],
+		)
+		.unwrap_or_else(|error| panic!("复合信号生成失败: {error}"));
+		let sos = design_sos(&SosDesignConfig {
+			sample_rate: 256.0,
+			filter_kind: FilterKind::LowPass,
+			cutoff_hz: [30.0, 0.0],
+			order: 6,
+			prototype: IirPrototype::Butterworth,
+		})
+		.unwrap_or_else(|error| panic!("SOS 设计失败: {error}"));
+
+		let filtered =
+			apply_sos(&signal, &sos).unwrap_or_else(|error| panic!("SOS 滤波失败: {error}"));
+		let reference = generate_composite_signal(
+			256.0,
+			4.0,
+			&[crate::CompositeComponent {
+				frequency: 10.0,
+				amplitude: 1.0,
+				phase: 0.0,
+			}],
+		)
+		.unwrap_or_else(|error| panic!("参考信号生成失败: {error}"));
+		let reference =
+			apply_sos(&reference, &sos).unwrap_or_else(|error| panic!("SOS 滤波失败: {error}"));
+
+		let max_error = filtered
+			.samples
+			.iter()
+			.zip(reference.samples.iter())
+			.skip(256)
+			.map(|(left, right)| (left - right).abs())
+			.fold(0.0, f32::max);
+		assert!(max_error < 1e-3);
+	}
+
+	#[test]
+	fn filtfilt_should_preserve_ramp_with_odd_padding() {
+		// 直流增益为 1 的对称 FIR 对线性序列只产生延迟，前后向滤波后应精确还原。
+		let signal = SignalBuffer {
+			sample_rate: 100.0,
+			samples: (0..64).map(|index| index as f32 * 0.5 - 3.0).collect(),
+		};
+		let coeffs = [0.25, 0.5, 0.25];
+
+		let filtered = filtfilt_fir(&signal, &coeffs, FiltfiltConfig::default())
+			.unwrap_or_else(|error| panic!("零相位 FIR 失败: {error}"));
+
+		for (actual, expected) in filtered.samples.iter().zip(signal.samples.iter()) {
+			assert!((actual - expected).abs() < 1e-4);
+		}
+	}
+
+	#[test]
+	fn filtfilt_should_match_scipy_reference_vectors() {
+		// 参考值按 scipy.signal.filtfilt 的默认参数计算：odd 延拓、
+		// padlen = 3 * max(len(a), len(b))、初始状态为 lfilter_zi 乘以首个延拓样本，双精度。
+		let signal = SignalBuffer {
+			sample_rate: 100.0,
+			samples: vec![
+				0.5000, 0.3917, 0.1435, 0.6850, 1.4692, 1.3939, 0.7066, 0.5638, 1.0157, 0.9414,
+				0.0455, -0.5802, -0.3164, -0.0500, -0.5774, -1.1755, -0.8439, -0.0469, 0.0909,
+				-0.2713, -0.0045, 0.9699, 1.4927, 1.1447, 0.9350, 1.5000, 1.9671, 1.4734, 0.6829,
+				0.6481, 1.0045, 0.6533, -0.2665, -0.5712, -0.0582, 0.1755, -0.3247, -0.5680,
+				0.1408, 0.9621,
+			],
+		};
+		let fir_expected = [
+			0.500000, 0.495855, 0.584673, 0.774897, 0.953161, 1.007129, 0.940390, 0.832920,
+			0.699450, 0.484083, 0.189830, -0.086649, -0.279778, -0.425435, -0.558180, -0.619053,
+			-0.538335, -0.350487, -0.140885, 0.083146, 0.373405, 0.710189, 0.992410, 1.170075,
+			1.291732, 1.387796, 1.396958, 1.266416, 1.051263, 0.838502, 0.626595, 0.368163,
+			0.097212, -0.086003, -0.150351, -0.156528, -0.122205, 0.040270, 0.419087, 0.962100,
+		];
+		let iir_expected = [
+			0.501761, 0.573534, 0.681615, 0.823029, 0.950445, 1.008908, 0.980402, 0.878818,
+			0.712259, 0.478737, 0.198583, -0.079165, -0.314022, -0.495866, -0.618522, -0.655738,
+			-0.584871, -0.418838, -0.191031, 0.081255, 0.393095, 0.717790, 1.006213, 1.225436,
+			1.373676, 1.449566, 1.432752, 1.311798, 1.111196, 0.869419, 0.604309, 0.323345,
+			0.057415, -0.144237, -0.251739, -0.261089, -0.160396, 0.080722, 0.470637, 0.957198,
+		];
+
+		let fir = filtfilt_fir(
+			&signal,
+			&[0.1, 0.2, 0.4, 0.2, 0.1],
+			FiltfiltConfig::default(),
+		)
+		.unwrap_or_else(|error| panic!("零相位 FIR 失败: {error}"));
+		let iir = filtfilt_iir(
+			&signal,
+			&IirCoefficients {
+				b: [0.0675, 0.135, 0.0675],
+				a: [1.0, -1.143, 0.4128],
+			},
+			FiltfiltConfig::default(),
+		)
+		.unwrap_or_else(|error| panic!("零相位 IIR 失败: {error}"));
+
+		assert!(max_abs_difference(&fir.samples, &fir_expected) < 1e-4);
+		assert!(max_abs_difference(&iir.samples, &iir_expected) < 1e-4);
+	}
+
+	#[test]
+	fn filtfilt_should_keep_constant_signal_without_transient() {
+		let signal = SignalBuffer {
+			sample_rate: 256.0,
+			samples: vec![3.0; 200],
+		};
+		let coeffs = design_iir(&IirDesignConfig {
+			sample_rate: 256.0,
+			filter_kind: FilterKind::LowPass,
+			cutoff_hz: [10.0, 0.0],
+			q: 1.0 / 2.0_f32.sqrt(),
+		})
+		.unwrap_or_else(|error| panic!("IIR 设计失败: {error}"));
+
+		for pad_type in [
+			PadType::Odd,
+			PadType::Even,
+			PadType::Constant,
+			PadType::None,
+		] {
+			let filtered = filtfilt_iir(
+				&signal,
+				&coeffs,
+				FiltfiltConfig {
+					pad_type,
+					pad_length: None,
+				},
+			)
+			.unwrap_or_else(|error| panic!("零相位 IIR 失败: {error}"));
+			assert!(
+				filtered
+					.samples
+					.iter()
+					.all(|value| (value - 3.0).abs() < 1e-3)
+			);
+		}
+	}
+
+	#[test]
+	fn filtfilt_sos_should_not_shift_phase() {
+		let signal = generate_composite_signal(
+			256.0,
+			4.0,
+			&[crate::CompositeComponent {
+				frequency: 10.0,
+				amplitude: 1.0,
+				phase: 0.0,
+			}],
+		)
+		.unwrap_or_else(|error| panic!("复合信号生成失败: {error}"));
+		let sos = design_sos(&SosDesignConfig {
+			sample_rate: 256.0,
+			filter_kind: FilterKind::BandPass,
+			cutoff_hz: [4.0, 16.0],
+			order: 4,
+			prototype: IirPrototype::Butterworth,
+		})
+		.unwrap_or_else(|error| panic!("SOS 设计失败: {error}"));
+
+		let filtered = filtfilt_sos(&signal, &sos, FiltfiltConfig::default())
+			.unwrap_or_else(|error| panic!("零相位 SOS 失败: {error}"));
+		let one_way =
+			apply_sos(&signal, &sos).unwrap_or_else(|error| panic!("SOS 滤波失败: {error}"));
+
+		let middle = 256..768;
+		let zero_phase_error = filtered.samples[middle.clone()]
+			.iter()
+			.zip(signal.samples[middle.clone()].iter())
+			.map(|(left, right)| (left - right).abs())
+			.fold(0.0, f32::max);
+		let one_way_error = one_way.samples[middle.clone()]
+			.iter()
+			.zip(signal.samples[middle].iter())
+			.map(|(left, right)| (left - right).abs())
+			.fold(0.0, f32::max);
+		assert!(zero_phase_error < 0.02);
+		assert!(one_way_error > 0.1);
+	}
+
+	#[test]
+	fn filtfilt_should_reject_too_short_input() {
+		let signal = SignalBuffer {
+			sample_rate: 256.0,
+			samples: vec![1.0; 8],
+		};
+
+		assert!(filtfilt_fir(&signal, &[0.2; 5], FiltfiltConfig::default()).is_err());
+	}
 }
@@ -2,21 +2,24 @@ use std::f32::consts::PI;
 
 use crate::{
 	error::SignalProcessError,
-	types::{FilterKind, FirDesignConfig},
+	types::{FilterKind, FirDesignConfig, KaiserFirSpec, KaiserParameters, WindowKind},
+	window::build_window,
 };
 
 /// 设计窗函数法 FIR 系数。
+///
+/// 截止频率按采样率归一化，`cutoff_hz` 处的幅度响应为 0.5 (-6 dB)。
 pub fn design_fir(config: &FirDesignConfig) -> Result<Vec<f32>, SignalProcessError> {
 	validate_fir_config(config)?;
 
 	let last_index = config.tap_count - 1;
 	let center = last_index as f32 / 2.0;
 	let cutoff = normalized_cutoff(config)?;
+	let window = build_window(config.window, config.tap_count);
 	let mut coeffs = Vec::with_capacity(config.tap_count);
 
-	for tap_index in 0..config.tap_count {
+	for (tap_index, window) in window.iter().enumerate() {
 		let n = tap_index as f32 - center;
-		let window = 0.54 - 0.46 * (2.0 * PI * tap_index as f32 / last_index.max(1) as f32).cos();
 
 		let ideal = match config.filter_kind {
 			FilterKind::LowPass => low_pass_kernel(cutoff[0], n),
@@ -37,6 +40,66 @@ pub fn design_fir(config: &FirDesignConfig) -> Result<Vec<f32>, SignalProcessErr
 	Ok(coeffs)
 }
 
+/// 按阻带衰减和过渡带宽估计 Kaiser 窗 FIR 的 tap 数与 β。
+///
+/// 采用 Kaiser, J. F. 1974 的经验公式，tap 数向上取为奇数以便支持高通与带阻。
+pub fn estimate_kaiser_parameters(
+	ripple_db: f32,
+	transition_width_hz: f32,
+	sample_rate: f32,
+) -> Result<KaiserParameters, SignalProcessError> {
+	if sample_rate <= 0.0 {
+		return Err(SignalProcessError::InvalidArgument(
+			"采样率必须大于 0".to_string(),
+		));
+	}
+	if ripple_db <= 0.0 {
+		return Err(SignalProcessError::InvalidArgument(
+			"波纹衰减必须大于 0 dB".to_string(),
+		));
+	}
+	if !(0.0 < transition_width_hz && transition_width_hz < sample_rate / 2.0) {
+		return Err(SignalProcessError::InvalidArgument(
+			"过渡带宽必须位于 (0, Nyquist) 内".to_string(),
+		));
+	}
+
+	let beta = if ripple_db > 50.0 {
+		0.1102 * (ripple_db - 8.7)
+	} else if ripple_db >= 21.0 {
+		0.5842 * (ripple_db - 21.0).powf(0.4) + 0.07886 * (ripple_db - 21.0)
+	} else {
+		0.0
+	};
+
+	let transition_width = 2.0 * PI * transition_width_hz / sample_rate;
+	let order = ((ripple_db - 7.95) / (2.285 * transition_width))
+		.ceil()
+		.max(2.0) as usize;
+	let tap_count = if order.is_multiple_of(2) {
+		order + 1
+	} else {
+		order + 2
+	};
+
+	Ok(KaiserParameters { tap_count, beta })
+}
+
+/// 按波纹与过渡带指标设计 Kaiser 窗 FIR 系数。
+pub fn design_fir_kaiser(spec: &KaiserFirSpec) -> Result<Vec<f32>, SignalProcessError> {
+	let parameters =
+		estimate_kaiser_parameters(spec.ripple_db, spec.transition_width_hz, spec.sample_rate)?;
+	design_fir(&FirDesignConfig {
+		sample_rate: spec.sample_rate,
+		filter_kind: spec.filter_kind,
+		cutoff_hz: spec.cutoff_hz.clone(),
+		tap_count: parameters.tap_count,
+		window: WindowKind::Kaiser {
+			beta: parameters.beta,
+		},
+	})
+}
+
 fn low_pass_kernel(cutoff: f32, n: f32) -> f32 {
 	if n == 0.0 {
 		2.0 * cutoff
@@ -45,13 +108,15 @@ fn low_pass_kernel(cutoff: f32, n: f32) -> f32 {
 	}
 }
 
+/// 截止频率归一化为每样本周期数，与 `low_pass_kernel` 的 sinc 定义一致。
 fn normalized_cutoff(config: &FirDesignConfig) -> Result<[f32; 2], SignalProcessError> {
-	let nyquist = config.sample_rate / 2.0;
+	let sample_rate = config.sample_rate;
 	match config.filter_kind {
-		FilterKind::LowPass | FilterKind::HighPass => Ok([config.cutoff_hz[0] / nyquist, 0.0]),
-		FilterKind::BandPass | FilterKind::BandStop => {
-			Ok([config.cutoff_hz[0] / nyquist, config.cutoff_hz[1] / nyquist])
-		}
+		FilterKind::LowPass | FilterKind::HighPass => Ok([config.cutoff_hz[0] / sample_rate, 0.0]),
+		FilterKind::BandPass | FilterKind::BandStop => Ok([
+			config.cutoff_hz[0] / sample_rate,
+			config.cutoff_hz[1] / sample_rate,
+		]),
 	}
 }
 
@@ -117,9 +182,83 @@ mod tests {
 			filter_kind: FilterKind::LowPass,
 			cutoff_hz: vec![20.0],
 			tap_count: 101,
+			window: WindowKind::Hamming,
 		})
 		.unwrap_or_else(|error| panic!("FIR 设计失败: {error}"));
 
 		assert_eq!(coeffs.len(), 101);
 	}
+
+	/// 直接计算 FIR 在给定频率处的幅度响应。
+	fn magnitude_at(coeffs: &[f32], frequency: f32, sample_rate: f32) -> f32 {
+		let omega = 2.0 * PI * frequency / sample_rate;
+		let (real, imag) =
+			coeffs
+				.iter()
+				.enumerate()
+				.fold((0.0, 0.0), |(real, imag), (index, coeff)| {
+					let phase = omega * index as f32;
+					(real + coeff * phase.cos(), imag - coeff * phase.sin())
+				});
+		(real * real + imag * imag).sqrt()
+	}
+
+	#[test]
+	fn kaiser_estimate_should_follow_textbook_formula() {
+		let parameters = estimate_kaiser_parameters(60.0, 50.0, 1000.0)
+			.unwrap_or_else(|error| panic!("Kaiser 估计失败: {error}"));
+
+		assert!((parameters.beta - 5.653).abs() < 1e-3);
+		assert_eq!(parameters.tap_count, 75);
+	}
+
+	#[test]
+	fn kaiser_design_should_meet_stopband_attenuation() {
+		let coeffs = design_fir_kaiser(&KaiserFirSpec {
+			sample_rate: 1000.0,
+			filter_kind: FilterKind::LowPass,
+			cutoff_hz: vec![100.0],
+			ripple_db: 60.0,
+			transition_width_hz: 40.0,
+		})
+		.unwrap_or_else(|error| panic!("Kaiser FIR 设计失败: {error}"));
+
+		let stopband_ceiling = 10f32.powf(-60.0 / 20.0) * 1.2;
+		for frequency in 120..500 {
+			assert!(magnitude_at(&coeffs, frequency as f32, 1000.0) < stopband_ceiling);
+		}
+		for frequency in 0..80 {
+			assert!((magnitude_at(&coeffs, frequency as f32, 1000.0) - 1.0).abs() < 2e-3);
+		}
+	}
+
+	#[test]
+	fn window_design_should_place_half_gain_at_cutoff() {
+		let coeffs = design_fir(&FirDesignConfig {
+			sample_rate: 256.0,
+			filter_kind: FilterKind::LowPass,
+			cutoff_hz: vec![20.0],
+			tap_count: 101,
+			window: WindowKind::Blackman,
+		})
+		.unwrap_or_else(|error| panic!("FIR 设计失败: {error}"));
+
+		assert!((magnitude_at(&coeffs, 20.0, 256.0) - 0.5).abs() < 0.02);
+		assert!(magnitude_at(&coeffs, 40.0, 256.0) < 1e-3);
+	}
+
+	#[test]
+	fn hamming_design_should_attenuate_cutoff_by_6_db() {
+		let coeffs = design_fir(&FirDesignConfig {
+			sample_rate: 256.0,
+			filter_kind: FilterKind::LowPass,
+			cutoff_hz: vec![20.0],
+			tap_count: 101,
+			window: WindowKind::Hamming,
+		})
+		.unwrap_or_else(|error| panic!("FIR 设计失败: {error}"));
+
+		let gain_db = 20.0 * magnitude_at(&coeffs, 20.0, 256.0).log10();
+		assert!((gain_db + 6.02).abs() < 0.2, "截止频率处增益 {gain_db} dB");
+	}
 }
@@ -0,0 +1,216 @@
+use std::f32::consts::{PI, TAU};
+
+use rustfft::FftPlanner;
+
+use crate::{
+	error::SignalProcessError,
+	fft::compute_fft,
+	types::{AnalyticSignal, Complex32, SignalBuffer},
+};
+
+/// 用 FFT 计算解析信号，算法与 scipy.signal.hilbert 一致。
+///
+/// 正频率分量加倍、负频率分量置零后做逆变换；直流与偶数长度时的奈奎斯特分量保持不变。
+pub fn compute_analytic_signal(
+	signal: &SignalBuffer,
+) -> Result<AnalyticSignal, SignalProcessError> {
+	let mut bins = compute_fft(signal)?.bins;
+	let length = bins.len();
+	let positive_end = length.div_ceil(2);
+	for bin in bins[1..positive_end].iter_mut() {
+		*bin *= 2.0;
+	}
+	for bin in bins[length / 2 + 1..].iter_mut() {
+		*bin = Complex32::new(0.0, 0.0);
+	}
+
+	let mut planner = FftPlanner::<f32>::new();
+	planner.plan_fft_inverse(length).process(&mut bins);
+	let scale = 1.0 / length as f32;
+	Ok(AnalyticSignal {
+		sample_rate: signal.sample_rate,
+		values: bins.into_iter().map(|value| value * scale).collect(),
+	})
+}
+
+/// Hilbert 变换，即解析信号的虚部。
+pub fn compute_hilbert(signal: &SignalBuffer) -> Result<SignalBuffer, SignalProcessError> {
+	map_analytic(signal, |value| value.im)
+}
+
+/// 幅度包络，即解析信号的模。
+pub fn compute_envelope(signal: &SignalBuffer) -> Result<SignalBuffer, SignalProcessError> {
+	map_analytic(signal, Complex32::norm)
+}
+
+/// 瞬时相位，取值范围 (-π, π]。
+pub fn compute_instantaneous_phase(
+	signal: &SignalBuffer,
+) -> Result<SignalBuffer, SignalProcessError> {
+	map_analytic(signal, Complex32::arg)
+}
+
+/// 展开后的瞬时相位，相邻样本间不再有 2π 跳变。
+pub fn compute_unwrapped_phase(signal: &SignalBuffer) -> Result<SignalBuffer, SignalProcessError> {
+	let wrapped = compute_instantaneous_phase(signal)?;
+	Ok(SignalBuffer {
+		sample_rate: wrapped.sample_rate,
+		samples: unwrap_phase(&wrapped.samples),
+	})
+}
+
+/// 瞬时频率 (Hz)，由展开相位的一阶差分得到。
+///
+/// 输出比输入少一个样本，第 n 个值对应样本 n 与 n + 1 之间。
+pub fn compute_instantaneous_frequency(
+	signal: &SignalBuffer,
+) -> Result<SignalBuffer, SignalProcessError> {
+	let phase = compute_unwrapped_phase(signal)?;
+	let scale = signal.sample_rate / TAU;
+	Ok(SignalBuffer {
+		sample_rate: signal.sample_rate,
+		samples: phase
+			.samples
+			.windows(2)
+			.map(|pair| (pair[1] - pair[0]) * scale)
+			.collect(),
+	})
+}
+
+/// 展开相位序列：相邻差值超过 π 时补偿 2π 的整数倍，与 numpy.unwrap 一致。
+pub fn unwrap_phase(phase: &[f32]) -> Vec<f32> {
+	let mut unwrapped = Vec::with_capacity(phase.len());
+	let mut offset = 0.0;
+	let mut previous: Option<f32> = None;
+	for value in phase.iter().copied() {
+		if let Some(previous) = previous {
+			let difference = value - previous;
+			if difference.abs() > PI {
+				offset -= TAU * (difference / TAU).round();
+			}
+		}
+		previous = Some(value);
+		unwrapped.push(value + offset);
+	}
+	unwrapped
+}
+
+/// 两个通道的锁相值 PLV = |mean(exp(j·(φa - φb)))|。
+///
+/// 取值 [0, 1]，1 表示相位差恒定；通常应先把两个通道带通到同一频段。
+/// 方法见 Lachaux, J. P. et al. 1999，参考文献：Measuring phase synchrony in brain signals,
+/// doi: 10.1002/(SICI)1097-0193(1999)8:4<194::AID-HBM4>3.0.CO;2-C
+pub fn compute_phase_locking_value(
+	first: &SignalBuffer,
+	second: &SignalBuffer,
+) -> Result<f32, SignalProcessError> {
+	if first.samples.len() != second.samples.len() {
+		return Err(SignalProcessError::InvalidArgument(format!(
+			"两个通道长度不一致: {} != {}",
+			first.samples.len(),
+			second.samples.len()
+		)));
+	}
+	if first.sample_rate != second.sample_rate {
+		return Err(SignalProcessError::InvalidArgument(format!(
+			"两个通道采样率不一致: {} != {}",
+			first.sample_rate, second.sample_rate
+		)));
+	}
+
+	let first = compute_analytic_signal(first)?;
+	let second = compute_analytic_signal(second)?;
+	let sum = first
+		.values
+		.iter()
+		.zip(second.values.iter())
+		.map(|(left, right)| Complex32::from_polar(1.0, left.arg() - right.arg()))
+		.sum::<Complex32>();
+	Ok(sum.norm() / first.values.len() as f32)
+}
+
+fn map_analytic(
+	signal: &SignalBuffer,
+	map: impl Fn(Complex32) -> f32,
+) -> Result<SignalBuffer, SignalProcessError> {
+	let analytic = compute_analytic_signal(signal)?;
+	Ok(SignalBuffer {
+		sample_rate: analytic.sample_rate,
+		samples: analytic.values.into_iter().map(map).collect(),
+	})
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	const SAMPLE_RATE: f32 = 256.0;
+
+	fn build_signal(sample_count: usize, sample: impl Fn(f32) -> f32) -> SignalBuffer {
+		SignalBuffer {
+			sample_rate: SAMPLE_RATE,
+			samples: (0..sample_count)
+				.map(|index| sample(index as f32 / SAMPLE_RATE))
+				.collect(),
+		}
+	}
+
+	#[test]
+	fn analytic_signal_should_yield_quadrature_and_envelope() {
+		let cosine = build_signal(512, |time| (TAU * 8.0 * time).cos());
+		let modulated = build_signal(511, |time| {
+			(1.0 + 0.5 * (TAU * 2.0 * time).cos()) * (TAU * 40.0 * time).cos()
+		});
+
+		let hilbert =
+			compute_hilbert(&cosine).unwrap_or_else(|error| panic!("Hilbert 变换失败: {error}"));
+		let envelope =
+			compute_envelope(&modulated).unwrap_or_else(|error| panic!("包络计算失败: {error}"));
+
+		for (index, value) in hilbert.samples.iter().enumerate() {
+			let expected = (TAU * 8.0 * index as f32 / SAMPLE_RATE).sin();
+			assert!((value - expected).abs() < 1e-3);
+		}
+		for (index, value) in envelope.samples[64..448].iter().enumerate() {
+			let time = (index + 64) as f32 / SAMPLE_RATE;
+			assert!((value - (1.0 + 0.5 * (TAU * 2.0 * time).cos())).abs() < 0.05);
+		}
+	}
+
+	#[test]
+	fn instantaneous_frequency_should_follow_unwrapped_phase() {
+		let sine = build_signal(1024, |time| (TAU * 12.0 * time).sin());
+
+		let wrapped = compute_instantaneous_phase(&sine)
+			.unwrap_or_else(|error| panic!("瞬时相位计算失败: {error}"));
+		let unwrapped =
+			compute_unwrapped_phase(&sine).unwrap_or_else(|error| panic!("相位展开失败: {error}"));
+		let frequency = compute_instantaneous_frequency(&sine)
+			.unwrap_or_else(|error| panic!("瞬时频率计算失败: {error}"));
+
+		assert!(wrapped.samples.iter().all(|value| value.abs() <= PI));
+		assert!(unwrapped.samples.windows(2).all(|pair| pair[1] > pair[0]));
+		assert_eq!(frequency.samples.len(), 1023);
+		assert!(
+			frequency.samples[32..992]
+				.iter()
+				.all(|value| (value - 12.0).abs() < 0.05)
+		);
+	}
+
+	#[test]
+	fn phase_locking_value_should_separate_locked_and_unrelated_channels() {
+		let reference = build_signal(1024, |time| (TAU * 10.0 * time).sin());
+		let lagged = build_signal(1024, |time| 0.3 * (TAU * 10.0 * time - 1.0).sin());
+		let unrelated = build_signal(1024, |time| (TAU * 13.0 * time).sin());
+
+		let locked = compute_phase_locking_value(&reference, &lagged)
+			.unwrap_or_else(|error| panic!("锁相值计算失败: {error}"));
+		let drifting = compute_phase_locking_value(&reference, &unrelated)
+			.unwrap_or_else(|error| panic!("锁相值计算失败: {error}"));
+
+		assert!(locked > 0.99);
+		assert!(drifting < 0.1);
+		assert!(compute_phase_locking_value(&reference, &build_signal(10, |_| 0.0)).is_err());
+	}
+}
@@ -0,0 +1,502 @@
+use crate::{
+	error::SignalProcessError,
+	linalg::{Matrix, multiply, symmetric_eigen, transpose},
+	types::{IcaAlgorithm, IcaConfig, IcaDecomposition, IcaNonlinearity},
+};
+
+/// 协方差特征值相对最大特征值低于该比例时视为秩亏。
+const RANK_TOLERANCE: f64 = 1e-10;
+
+/// 用 FastICA 把 `[通道][样本]` 数据分解为独立分量。
+///
+/// 先对去均值数据做 PCA 白化，再按 Hyvärinen, A. 1999 的定点迭代估计解混矩阵，
+/// 参考文献：Fast and robust fixed-point algorithms for independent component analysis,
+/// doi: 10.1109/72.761722
+pub fn fast_ica(
+	channels: &[Vec<f32>],
+	config: &IcaConfig,
+) -> Result<IcaDecomposition, SignalProcessError> {
+	let channel_count = channels.len();
+	let component_count = config.component_count.unwrap_or(channel_count);
+	validate_ica_arguments(channels, component_count, config)?;
+
+	let sample_count = channels[0].len();
+	let channel_means = channels
+		.iter()
+		.map(|channel| channel.iter().map(|value| *value as f64).sum::<f64>() / sample_count as f64)
+		.collect::<Vec<_>>();
+	let centered = channels
+		.iter()
+		.zip(channel_means.iter())
+		.map(|(channel, mean)| channel.iter().map(|value| *value as f64 - mean).collect())
+		.collect::<Matrix>();
+
+	let (whitening, dewhitening) = build_whitening(&centered, component_count)?;
+	let whitened = multiply(&whitening, &centered);
+	let mut rng_state = config.seed;
+	let (rotation, iterations) = match config.algorithm {
+		IcaAlgorithm::Symmetric => solve_symmetric(&whitened, config, &mut rng_state)?,
+		IcaAlgorithm::Deflation => solve_deflation(&whitened, config, &mut rng_state)?,
+	};
+
+	let unmixing = multiply(&rotation, &whitening);
+	let mixing = multiply(&dewhitening, &transpose(&rotation));
+	let sources = multiply(&rotation, &whitened);
+	Ok(IcaDecomposition {
+		channel_means: channel_means.iter().map(|value| *value as f32).collect(),
+		unmixing: to_f32(&unmixing),
+		mixing: to_f32(&mixing),
+		sources: to_f32(&sources),
+		iterations,
+	})
+}
+
+/// 把指定分量置零后重建 `[通道][样本]` 数据，常用于去除眼电等伪迹分量。
+///
+/// 分量数少于通道数时，被 PCA 丢弃的子空间不会出现在重建结果中。
+pub fn reconstruct_without_components(
+	decomposition: &IcaDecomposition,
+	excluded_components: &[usize],
+) -> Result<Vec<Vec<f32>>, SignalProcessError> {
+	let component_count = decomposition.sources.len();
+	if let Some(component) = excluded_components
+		.iter()
+		.find(|component| **component >= component_count)
+	{
+		return Err(SignalProcessError::InvalidArgument(format!(
+			"分量索引 {component} 超出分量数 {component_count}"
+		)));
+	}
+
+	let sample_count = decomposition.sources.first().map_or(0, Vec::len);
+	let reconstructed = decomposition
+		.mixing
+		.iter()
+		.zip(decomposition.channel_means.iter())
+		.map(|(weights, mean)| {
+			let mut channel = vec![*mean; sample_count];
+			for (component, (weight, source)) in
+				weights.iter().zip(decomposition.sources.iter()).enumerate()
+			{
+				if excluded_components.contains(&component) {
+					continue;
+				}
+				for (value, source_value) in channel.iter_mut().zip(source.iter()) {
+					*value += weight * source_value;
+				}
+			}
+			channel
+		})
+		.collect();
+	Ok(reconstructed)
+}
+
+/// 由协方差特征分解得到白化矩阵 [分量][通道] 与反白化矩阵 [通道][分量]。
+fn build_whitening(
+	centered: &[Vec<f64>],
+	component_count: usize,
+) -> Result<(Matrix, Matrix), SignalProcessError> {
+	let sample_count = centered[0].len() as f64;
+	let covariance = centered
+		.iter()
+		.map(|left| {
+			centered
+				.iter()
+				.map(|right| {
+					left.iter()
+						.zip(right.iter())
+						.map(|(left, right)| left * right)
+						.sum::<f64>() / sample_count
+				})
+				.collect()
+		})
+		.collect::<Matrix>();
+
+	let (eigenvalues, eigenvectors) = symmetric_eigen(&covariance);
+	let largest = eigenvalues[0].max(0.0);
+	if largest <= 0.0 || eigenvalues[component_count - 1] <= largest * RANK_TOLERANCE {
+		return Err(SignalProcessError::InvalidArgument(format!(
+			"数据协方差的秩小于分量数 {component_count}"
+		)));
+	}
+
+	let whitening = (0..component_count)
+		.map(|component| {
+			let scale = eigenvalues[component].sqrt();
+			eigenvectors
+				.iter()
+				.map(|row| row[component] / scale)
+				.collect()
+		})
+		.collect();
+	let dewhitening = eigenvectors
+		.iter()
+		.map(|row| {
+			(0..component_count)
+				.map(|component| row[component] * eigenvalues[component].sqrt())
+				.collect()
+		})
+		.collect();
+	Ok((whitening, dewhitening))
+}
+
+/// 同时更新全部分量，每步后做对称正交化 W ← (W·Wᵀ)^(-1/2)·W。
+fn solve_symmetric(
+	whitened: &[Vec<f64>],
+	config: &IcaConfig,
+	rng_state: &mut u64,
+) -> Result<(Matrix, usize), SignalProcessError> {
+	let component_count = whitened.len();
+	let mut rotation = symmetric_decorrelation(&random_matrix(component_count, rng_state));
+
+	for iteration in 1..=config.max_iterations {
+		let updated = symmetric_decorrelation(
+			&rotation
+				.iter()
+				.map(|row| fixed_point_update(row, whitened, config.nonlinearity))
+				.collect::<Matrix>(),
+		);
+		let change = updated
+			.iter()
+			.zip(rotation.iter())
+			.map(|(new_row, old_row)| (1.0 - dot(new_row, old_row).abs()).abs())
+			.fold(0.0, f64::max);
+		rotation = updated;
+		if change < config.tolerance as f64 {
+			return Ok((rotation, iteration));
+		}
+	}
+
+	Err(not_converged(config.max_iterations))
+}
+
+/// 逐个估计分量，每次迭代后与已得到的分量做 Gram-Schmidt 正交化。
+fn solve_deflation(
+	whitened: &[Vec<f64>],
+	config: &IcaConfig,
+	rng_state: &mut u64,
+) -> Result<(Matrix, usize), SignalProcessError> {
+	let component_count = whitened.len();
+	let mut rotation: Matrix = Vec::with_capacity(component_count);
+	let mut max_iterations_used = 0;
+
+	for _ in 0..component_count {
+		let mut vector = orthonormalize(
+			(0..component_count)
+				.map(|_| next_uniform(rng_state))
+				.collect(),
+			&rotation,
+		);
+		let mut converged = false;
+		for iteration in 1..=config.max_iterations {
+			let updated = orthonormalize(
+				fixed_point_update(&vector, whitened, config.nonlinearity),
+				&rotation,
+			);
+			let change = (1.0 - dot(&updated, &vector).abs()).abs();
+			vector = updated;
+			if change < config.tolerance as f64 {
+				max_iterations_used = max_iterations_used.max(iteration);
+				converged = true;
+				break;
+			}
+		}
+		if !converged {
+			return Err(not_converged(config.max_iterations));
+		}
+		rotation.push(vector);
+	}
+
+	Ok((rotation, max_iterations_used))
+}
+
+/// 单个分量的定点更新 w⁺ = E[z·g(wᵀz)] - E[g'(wᵀz)]·w。
+fn fixed_point_update(
+	vector: &[f64],
+	whitened: &[Vec<f64>],
+	nonlinearity: IcaNonlinearity,
+) -> Vec<f64> {
+	let sample_count = whitened[0].len();
+	let mut weighted = vec![0.0; vector.len()];
+	let mut derivative_sum = 0.0;
+	for sample_index in 0..sample_count {
+		let projection = vector
+			.iter()
+			.zip(whitened.iter())
+			.map(|(weight, row)| weight * row[sample_index])
+			.sum::<f64>();
+		let (value, derivative) = evaluate_nonlinearity(nonlinearity, projection);
+		for (accumulator, row) in weighted.iter_mut().zip(whitened.iter()) {
+			*accumulator += row[sample_index] * value;
+		}
+		derivative_sum += derivative;
+	}
+
+	let derivative_mean = derivative_sum / sample_count as f64;
+	weighted
+		.iter()
+		.zip(vector.iter())
+		.map(|(accumulator, weight)| accumulator / sample_count as f64 - derivative_mean * weight)
+		.collect()
+}
+
+/// 返回 (g(u), g'(u))。
+fn evaluate_nonlinearity(nonlinearity: IcaNonlinearity, value: f64) -> (f64, f64) {
+	match nonlinearity {
+		IcaNonlinearity::LogCosh => {
+			let tanh = value.tanh();
+			(tanh, 1.0 - tanh * tanh)
+		}
+		IcaNonlinearity::Exp => {
+			let gaussian = (-value * value / 2.0).exp();
+			(value * gaussian, (1.0 - value * value) * gaussian)
+		}
+		IcaNonlinearity::Cube => (value * value * value, 3.0 * value * value),
+	}
+}
+
+fn symmetric_decorrelation(matrix: &[Vec<f64>]) -> Matrix {
+	let gram = multiply(matrix, &transpose(matrix));
+	let (eigenvalues, eigenvectors) = symmetric_eigen(&gram);
+	let inverse_sqrt = eigenvectors
+		.iter()
+		.map(|row| {
+			row.iter()
+				.zip(eigenvalues.iter())
+				.map(|(value, eigenvalue)| value / eigenvalue.max(f64::MIN_POSITIVE).sqrt())
+				.collect()
+		})
+		.collect::<Matrix>();
+	multiply(&multiply(&inverse_sqrt, &transpose(&eigenvectors)), matrix)
+}
+
+fn orthonormalize(mut vector: Vec<f64>, basis: &[Vec<f64>]) -> Vec<f64> {
+	for base in basis {
+		let projection = dot(&vector, base);
+		for (value, base_value) in vector.iter_mut().zip(base.iter()) {
+			*value -= projection * base_value;
+		}
+	}
+	let norm = dot(&vector, &vector).sqrt().max(f64::MIN_POSITIVE);
+	vector.iter().map(|value| value / norm).collect()
+}
+
+fn dot(left: &[f64], right: &[f64]) -> f64 {
+	left.iter()
+		.zip(right.iter())
+		.map(|(left, right)| left * right)
+		.sum()
+}
+
+fn random_matrix(size: usize, rng_state: &mut u64) -> Matrix {
+	(0..size)
+		.map(|_| (0..size).map(|_| next_uniform(rng_state)).collect())
+		.collect()
+}
+
+/// SplitMix64 生成 [-1, 1) 内的均匀随机数。
+fn next_uniform(state: &mut u64) -> f64 {
+	*state = state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+	let mut mixed = *state;
+	mixed = (mixed ^ (mixed >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+	mixed = (mixed ^ (mixed >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+	mixed ^= mixed >> 31;
+	(mixed >> 11) as f64 / (1u64 << 53) as f64 * 2.0 - 1.0
+}
+
+fn to_f32(matrix: &[Vec<f64>]) -> Vec<Vec<f32>> {
+	matrix
+		.iter()
+		.map(|row| row.iter().map(|value| *value as f32).collect())
+		.collect()
+}
+
+fn not_converged(max_iterations: usize) -> SignalProcessError {
+	SignalProcessError::InvalidArgument(format!("FastICA 未在 {max_iterations} 次迭代内收敛"))
+}
+
+fn validate_ica_arguments(
+	channels: &[Vec<f32>],
+	component_count: usize,
+	config: &IcaConfig,
+) -> Result<(), SignalProcessError> {
+	let Some(first) = channels.first() else {
+		return Err(SignalProcessError::EmptyInput);
+	};
+	if first.is_empty() {
+		return Err(SignalProcessError::EmptyInput);
+	}
+	if channels.iter().any(|channel| channel.len() != first.len()) {
+		return Err(SignalProcessError::InvalidArgument(
+			"FastICA 要求各通道样本数相同".to_string(),
+		));
+	}
+	if component_count == 0 || component_count > channels.len() {
+		return Err(SignalProcessError::InvalidArgument(format!(
+			"分量数必须位于 [1, {}] 内",
+			channels.len()
+		)));
+	}
+	if first.len() <= channels.len() {
+		return Err(SignalProcessError::InvalidArgument(
+			"样本数必须大于通道数".to_string(),
+		));
+	}
+	if config.max_iterations == 0 || config.tolerance <= 0.0 {
+		return Err(SignalProcessError::InvalidArgument(
+			"最大迭代次数与收敛阈值必须大于 0".to_string(),
+		));
+	}
+	Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+	use crate::{CompositeComponent, generate_composite_signal};
+
+	use super::*;
+
+	fn composite(components: &[(f32, f32)]) -> Vec<f32> {
+		let components = components
+			.iter()
+			.map(|(frequency, amplitude)| CompositeComponent {
+				frequency: *frequency,
+				amplitude: *amplitude,
+				phase: 0.3 * frequency,
+			})
+			.collect::<Vec<_>>();
+		generate_composite_signal(128.0, 8.0, &components)
+			.unwrap_or_else(|error| panic!("复合信号生成失败: {error}"))
+			.samples
+	}
+
+	/// 三个独立源：慢波眨眼样成分、alpha 节律与 beta 节律。
+	///
+	/// 确定性正弦源的高阶矩只有在频率满足低阶整数关系时才不可分解，这里的频率避开了这类组合。
+	fn sources() -> Vec<Vec<f32>> {
+		vec![
+			composite(&[(0.5, 4.0)]),
+			composite(&[(10.0, 1.0)]),
+			composite(&[(23.0, 0.8)]),
+		]
+	}
+
+	fn mix(sources: &[Vec<f32>], mixing: &[[f32; 3]]) -> Vec<Vec<f32>> {
+		mixing
+			.iter()
+			.map(|weights| {
+				(0..sources[0].len())
+					.map(|index| {
+						weights
+							.iter()
+							.zip(sources.iter())
+							.map(|(weight, source)| weight * source[index])
+							.sum::<f32>() + 5.0
+					})
+					.collect()
+			})
+			.collect()
+	}
+
+	fn correlation(left: &[f32], right: &[f32]) -> f32 {
+		let mean = |values: &[f32]| values.iter().sum::<f32>() / values.len() as f32;
+		let (left_mean, right_mean) = (mean(left), mean(right));
+		let (mut covariance, mut left_power, mut right_power) = (0.0, 0.0, 0.0);
+		for (left, right) in left.iter().zip(right.iter()) {
+			covariance += (left - left_mean) * (right - right_mean);
+			left_power += (left - left_mean).powi(2);
+			right_power += (right - right_mean).powi(2);
+		}
+		covariance / (left_power * right_power).sqrt()
+	}
+
+	fn assert_sources_recovered(decomposition: &IcaDecomposition, sources: &[Vec<f32>]) {
+		for source in sources {
+			let best = decomposition
+				.sources
+				.iter()
+				.map(|component| correlation(component, source).abs())
+				.fold(0.0, f32::max);
+			assert!(best > 0.99, "最佳相关系数 {best}");
+		}
+	}
+
+	#[test]
+	fn symmetric_fast_ica_should_unmix_sources() {
+		let sources = sources();
+		let mixed = mix(
+			&sources,
+			&[[1.0, 0.4, 0.2], [0.6, 1.0, 0.5], [0.2, 0.7, 1.0]],
+		);
+
+		let decomposition = fast_ica(&mixed, &IcaConfig::default())
+			.unwrap_or_else(|error| panic!("FastICA 失败: {error}"));
+
+		assert_sources_recovered(&decomposition, &sources);
+		assert_eq!(decomposition.mixing.len(), 3);
+		assert_eq!(decomposition.unmixing[0].len(), 3);
+	}
+
+	#[test]
+	fn deflation_with_cube_should_unmix_sources() {
+		let sources = sources();
+		let mixed = mix(
+			&sources,
+			&[[0.9, 0.3, 0.1], [0.5, 1.0, 0.4], [0.1, 0.5, 1.0]],
+		);
+		let config = IcaConfig {
+			algorithm: IcaAlgorithm::Deflation,
+			nonlinearity: IcaNonlinearity::Cube,
+			seed: 7,
+			..IcaConfig::default()
+		};
+
+		let decomposition =
+			fast_ica(&mixed, &config).unwrap_or_else(|error| panic!("FastICA 失败: {error}"));
+
+		assert_sources_recovered(&decomposition, &sources);
+	}
+
+	#[test]
+	fn removing_blink_component_should_leave_brain_sources() {
+		let sources = sources();
+		let weights = [[2.0, 0.4, 0.2], [1.0, 1.0, 0.5], [0.3, 0.7, 1.0]];
+		let mixed = mix(&sources, &weights);
+		let clean = mix(
+			&sources,
+			&weights.map(|[_, alpha, beta]| [0.0, alpha, beta]),
+		);
+
+		let decomposition = fast_ica(&mixed, &IcaConfig::default())
+			.unwrap_or_else(|error| panic!("FastICA 失败: {error}"));
+		let full = reconstruct_without_components(&decomposition, &[])
+			.unwrap_or_else(|error| panic!("重建失败: {error}"));
+		let blink = decomposition
+			.sources
+			.iter()
+			.enumerate()
+			.max_by(|left, right| {
+				correlation(left.1, &sources[0])
+					.abs()
+					.total_cmp(&correlation(right.1, &sources[0]).abs())
+			})
+			.map(|(index, _)| index)
+			.unwrap_or_else(|| panic!("没有分量"));
+		let cleaned = reconstruct_without_components(&decomposition, &[blink])
+			.unwrap_or_else(|error| panic!("重建失败: {error}"));
+
+		for (rebuilt, original) in full.iter().zip(mixed.iter()) {
+			let max_error = rebuilt
+				.iter()
+				.zip(original.iter())
+				.map(|(left, right)| (left - right).abs())
+				.fold(0.0, f32::max);
+			assert!(max_error < 1e-3);
+		}
+		assert!(correlation(&mixed[0], &clean[0]) < 0.5);
+		for (rebuilt, expected) in cleaned.iter().zip(clean.iter()) {
+			assert!(correlation(rebuilt, expected) > 0.995);
+		}
+		assert!(reconstruct_without_components(&decomposition, &[3]).is_err());
+	}
+}
@@ -2,13 +2,19 @@ use std::f32::consts::PI;
 
 use crate::{
 	error::SignalProcessError,
+	response::ensure_iir_stable,
 	types::{FilterKind, IirCoefficients, IirDesignConfig},
 };
 
-/// 设计二阶 IIR 系数。
+/// 设计二阶 IIR 系数，极点不在单位圆内的设计会被拒绝。
 pub fn design_iir(config: &IirDesignConfig) -> Result<IirCoefficients, SignalProcessError> {
 	validate_iir_config(config)?;
+	let coeffs = design_biquad(config)?;
+	ensure_iir_stable(&coeffs)?;
+	Ok(coeffs)
+}
 
+fn design_biquad(config: &IirDesignConfig) -> Result<IirCoefficients, SignalProcessError> {
 	let sample_rate = config.sample_rate;
 	let q = config.q;
 	let omega = 2.0 * PI * config.cutoff_hz[0] / sample_rate;
@@ -124,4 +130,17 @@ mod tests {
 
 		assert!((coeffs.a[0] - 1.0).abs() < 1e-6);
 	}
+
+	#[test]
+	fn iir_should_reject_degenerate_q() {
+		for q in [1e-9, f32::NAN] {
+			let result = design_iir(&IirDesignConfig {
+				sample_rate: 256.0,
+				filter_kind: FilterKind::LowPass,
+				cutoff_hz: [20.0, 0.0],
+				q,
+			});
+			assert!(result.is_err());
+		}
+	}
 }
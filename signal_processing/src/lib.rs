@@ -1,23 +1,108 @@
 #![deny(clippy::unwrap_used)]
 #![deny(clippy::expect_used)]
 
+pub mod artifact;
+pub mod band_power;
+pub mod connectivity;
+pub mod convolution;
+pub mod epoch;
 pub mod error;
+pub mod features;
 pub mod fft;
 pub mod filter;
 pub mod fir;
 pub mod generator;
+pub mod hilbert;
+pub mod ica;
 pub mod iir;
+mod linalg;
+pub mod montage;
+pub mod multichannel;
+pub mod notch;
+pub mod remez;
+pub mod resample;
+pub mod response;
+pub mod sos;
 pub mod spectrum;
+pub mod stft;
+pub mod synthetic;
+#[cfg(test)]
+mod test_support;
 pub mod types;
+pub mod wavelet;
+pub mod window;
 
+pub use artifact::detect_artifacts;
+pub use band_power::{
+	compute_band_power, compute_band_power_from_spectrum, compute_band_power_per_channel,
+};
+pub use connectivity::{
+	compute_connectivity, compute_cross_spectral_density, compute_spectral_granger,
+};
+pub use convolution::{
+	ConvolutionMethod, FFT_CONVOLUTION_TAP_THRESHOLD, FftFirFilter, FirConvolver, apply_fir_fast,
+	apply_fir_with_method,
+};
+pub use epoch::{average_by_condition, average_epochs, difference_wave, extract_epochs};
 pub use error::SignalProcessError;
+pub use features::{
+	compute_approximate_entropy, compute_feature_vector, compute_features,
+	compute_features_per_channel, compute_higuchi_fractal_dimension, compute_hjorth_parameters,
+	compute_katz_fractal_dimension, compute_line_length, compute_permutation_entropy,
+	compute_sample_entropy, compute_spectral_entropy, compute_zero_crossing_rate,
+};
 pub use fft::compute_fft;
-pub use filter::{apply_fir, apply_iir};
-pub use fir::design_fir;
+pub use filter::{
+	FilterBank, FiltfiltConfig, FirFilter, IirFilter, PadType, SosFilter, StreamingFilter,
+	apply_fir, apply_iir, apply_sos, filtfilt, filtfilt_fir, filtfilt_iir, filtfilt_sos,
+};
+pub use fir::{design_fir, design_fir_kaiser, estimate_kaiser_parameters};
 pub use generator::{generate_composite_signal, generate_sine_wave};
+pub use hilbert::{
+	compute_analytic_signal, compute_envelope, compute_hilbert, compute_instantaneous_frequency,
+	compute_instantaneous_phase, compute_phase_locking_value, compute_unwrapped_phase,
+	unwrap_phase,
+};
+pub use ica::{fast_ica, reconstruct_without_components};
 pub use iir::design_iir;
+pub use montage::{
+	Montage, apply_montage, common_average_montage, laplacian_montage, linked_mastoid_montage,
+	load_montage_definition, longitudinal_bipolar_montage, parse_montage_definition,
+	transverse_bipolar_montage,
+};
+pub use multichannel::{ChannelView, MultiChannelSignal};
+pub use notch::{
+	AdaptiveNotchFilter, design_comb_notch, design_notch, detect_line_frequency, remove_line_noise,
+};
+pub use remez::design_fir_remez;
+pub use resample::{
+	MAX_RESAMPLE_FACTOR, PolyphaseResampler, decimate, interpolate, resample_channels,
+	resample_poly, resample_to_rate,
+};
+pub use response::{
+	ensure_iir_stable, ensure_sos_stable, freqz_fir, freqz_iir, freqz_sos, pole_radius,
+};
+pub use sos::design_sos;
 pub use spectrum::{compute_power_spectrum, compute_power_spectrum_with_config};
+pub use stft::{compute_inverse_stft, compute_spectrogram, compute_stft};
+pub use synthetic::{
+	EegSimulationConfig, EegSimulator, ErpComponent, NoiseColor, generate_eeg_like_signal,
+};
 pub use types::{
-	CompositeComponent, FftOutput, FilterKind, FirDesignConfig, IirCoefficients, IirDesignConfig,
-	SignalBuffer, SineWaveConfig, SpectrumPoint, WelchSpectrumConfig,
+	AnalyticSignal, ArtifactConfig, ArtifactInterval, ArtifactKind, ArtifactReport, BadChannel,
+	BadChannelReason, BandPowerConfig, BandPowerFeatures, BandPowerValue, BaselineMode,
+	ChannelInfo, Complex32, CompositeComponent, ConnectivityMatrix, ConnectivityMetric,
+	CrossSpectralDensity, Epoch, EpochConfig, EpochRejectReason, EpochSet, EventMarker,
+	EvokedResponse, FeatureConfig, FftOutput, FilterKind, FirDesignConfig, FrequencyBand,
+	FrequencyResponsePoint, GrangerConfig, IcaAlgorithm, IcaConfig, IcaDecomposition,
+	IcaNonlinearity, IirCoefficients, IirDesignConfig, IirPrototype, IntegrationMethod,
+	KaiserFirSpec, KaiserParameters, LineNoiseDetection, MontageDefinition, MontageDerivation,
+	MontageTerm, MorletConfig, MorletCycles, NotchConfig, RejectedEpoch, RemezBand,
+	RemezDesignConfig, SignalBuffer, SignalFeatures, SineWaveConfig, SosCoefficients,
+	SosDesignConfig, Spectrogram, SpectrogramScale, SpectrumPoint, StftConfig, StftOutput,
+	TimeFrequencyPower, WaveletTransform, WelchSpectrumConfig, WindowKind,
+};
+pub use wavelet::{
+	apply_baseline, compute_epoch_wavelet_power, compute_morlet_transform, compute_wavelet_power,
 };
+pub use window::{build_periodic_window, build_window};
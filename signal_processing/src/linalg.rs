@@ -0,0 +1,198 @@
+//! 小规模稠密矩阵运算，矩阵按行存储为 `Vec<Vec<f64>>`。
+
+/// Jacobi 旋转的最大轮数。
+const MAX_JACOBI_SWEEPS: usize = 100;
+
+pub(crate) type Matrix = Vec<Vec<f64>>;
+
+pub(crate) fn identity(size: usize) -> Matrix {
+	(0..size)
+		.map(|row| {
+			(0..size)
+				.map(|column| f64::from(u8::from(row == column)))
+				.collect()
+		})
+		.collect()
+}
+
+pub(crate) fn transpose(matrix: &[Vec<f64>]) -> Matrix {
+	let column_count = matrix.first().map_or(0, Vec::len);
+	(0..column_count)
+		.map(|column| matrix.iter().map(|row| row[column]).collect())
+		.collect()
+}
+
+pub(crate) fn multiply(left: &[Vec<f64>], right: &[Vec<f64>]) -> Matrix {
+	let column_count = right.first().map_or(0, Vec::len);
+	left.iter()
+		.map(|row| {
+			(0..column_count)
+				.map(|column| {
+					row.iter()
+						.zip(right.iter())
+						.map(|(value, right_row)| value * right_row[column])
+						.sum()
+				})
+				.collect()
+		})
+		.collect()
+}
+
+/// 用部分主元 Gauss 消元求解 A·X = B，A 奇异时返回 None。
+pub(crate) fn solve(matrix: &[Vec<f64>], rhs: &[Vec<f64>]) -> Option<Matrix> {
+	let size = matrix.len();
+	let scale = matrix
+		.iter()
+		.flatten()
+		.fold(0.0f64, |maximum, value| maximum.max(value.abs()));
+	let mut augmented = matrix
+		.iter()
+		.zip(rhs.iter())
+		.map(|(row, rhs_row)| row.iter().chain(rhs_row.iter()).copied().collect())
+		.collect::<Matrix>();
+
+	for column in 0..size {
+		let pivot_row = (column..size).max_by(|left, right| {
+			augmented[*left][column]
+				.abs()
+				.total_cmp(&augmented[*right][column].abs())
+		})?;
+		if augmented[pivot_row][column].abs() <= scale * size as f64 * f64::EPSILON {
+			return None;
+		}
+		augmented.swap(column, pivot_row);
+
+		let (upper_rows, lower_rows) = augmented.split_at_mut(column + 1);
+		let pivot = &upper_rows[column];
+		for row in lower_rows.iter_mut() {
+			let factor = row[column] / pivot[column];
+			for (value, pivot_value) in row.iter_mut().zip(pivot.iter()).skip(column) {
+				*value -= factor * pivot_value;
+			}
+		}
+	}
+
+	let rhs_width = augmented.first().map_or(0, |row| row.len() - size);
+	let mut solution = vec![vec![0.0; rhs_width]; size];
+	for row in (0..size).rev() {
+		for column in 0..rhs_width {
+			let known = ((row + 1)..size)
+				.map(|index| augmented[row][index] * solution[index][column])
+				.sum::<f64>();
+			solution[row][column] = (augmented[row][size + column] - known) / augmented[row][row];
+		}
+	}
+	Some(solution)
+}
+
+/// 用循环 Jacobi 法求实对称矩阵的特征分解。
+///
+/// 返回按特征值降序排列的 (特征值, 特征向量)，特征向量存放在返回矩阵的列中。
+pub(crate) fn symmetric_eigen(matrix: &[Vec<f64>]) -> (Vec<f64>, Matrix) {
+	let size = matrix.len();
+	let mut values = matrix.to_vec();
+	let mut vectors = identity(size);
+
+	for _ in 0..MAX_JACOBI_SWEEPS {
+		let off_diagonal = (0..size)
+			.flat_map(|row| ((row + 1)..size).map(move |column| (row, column)))
+			.map(|(row, column)| values[row][column] * values[row][column])
+			.sum::<f64>();
+		let diagonal = (0..size)
+			.map(|index| values[index][index].powi(2))
+			.sum::<f64>();
+		if off_diagonal <= f64::EPSILON * f64::EPSILON * diagonal.max(f64::MIN_POSITIVE) {
+			break;
+		}
+
+		for p in 0..size {
+			for q in (p + 1)..size {
+				if values[p][q] == 0.0 {
+					continue;
+				}
+
+				let theta = (values[q][q] - values[p][p]) / (2.0 * values[p][q]);
+				let tangent = theta.signum() / (theta.abs() + (theta * theta + 1.0).sqrt());
+				let cosine = 1.0 / (tangent * tangent + 1.0).sqrt();
+				let sine = tangent * cosine;
+
+				for row in values.iter_mut() {
+					let (left, right) = (row[p], row[q]);
+					row[p] = cosine * left - sine * right;
+					row[q] = sine * left + cosine * right;
+				}
+				let (upper_rows, lower_rows) = values.split_at_mut(q);
+				for (upper, lower) in upper_rows[p].iter_mut().zip(lower_rows[0].iter_mut()) {
+					let (upper_value, lower_value) = (*upper, *lower);
+					*upper = cosine * upper_value - sine * lower_value;
+					*lower = sine * upper_value + cosine * lower_value;
+				}
+				for row in vectors.iter_mut() {
+					let (left, right) = (row[p], row[q]);
+					row[p] = cosine * left - sine * right;
+					row[q] = sine * left + cosine * right;
+				}
+			}
+		}
+	}
+
+	let mut order = (0..size).collect::<Vec<_>>();
+	order.sort_by(|left, right| values[*right][*right].total_cmp(&values[*left][*left]));
+	let eigenvalues = order.iter().map(|index| values[*index][*index]).collect();
+	let eigenvectors = vectors
+		.iter()
+		.map(|row| order.iter().map(|index| row[*index]).collect())
+		.collect();
+	(eigenvalues, eigenvectors)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn eigen_decomposition_should_reconstruct_matrix() {
+		let matrix = vec![
+			vec![4.0, 1.0, 0.5],
+			vec![1.0, 3.0, 0.2],
+			vec![0.5, 0.2, 1.0],
+		];
+
+		let (values, vectors) = symmetric_eigen(&matrix);
+		let diagonal = (0..3)
+			.map(|row| {
+				(0..3)
+					.map(|column| if row == column { values[row] } else { 0.0 })
+					.collect()
+			})
+			.collect::<Matrix>();
+		let rebuilt = multiply(&multiply(&vectors, &diagonal), &transpose(&vectors));
+
+		assert!(values.windows(2).all(|pair| pair[0] >= pair[1]));
+		for (row, rebuilt_row) in matrix.iter().zip(rebuilt.iter()) {
+			for (value, rebuilt_value) in row.iter().zip(rebuilt_row.iter()) {
+				assert!((value - rebuilt_value).abs() < 1e-10);
+			}
+		}
+	}
+
+	#[test]
+	fn solve_should_invert_well_conditioned_system() {
+		let matrix = vec![
+			vec![0.0, 2.0, 1.0],
+			vec![1.0, 1.0, 0.0],
+			vec![3.0, 0.0, 1.0],
+		];
+		let rhs = vec![vec![5.0, 1.0], vec![3.0, 0.0], vec![6.0, 2.0]];
+
+		let solution = solve(&matrix, &rhs).unwrap_or_else(|| panic!("线性方程组求解失败"));
+		let rebuilt = multiply(&matrix, &solution);
+
+		for (row, rebuilt_row) in rhs.iter().zip(rebuilt.iter()) {
+			for (value, rebuilt_value) in row.iter().zip(rebuilt_row.iter()) {
+				assert!((value - rebuilt_value).abs() < 1e-12);
+			}
+		}
+		assert!(solve(&[vec![1.0, 2.0], vec![2.0, 4.0]], &[vec![1.0], vec![2.0]]).is_none());
+	}
+}
@@ -0,0 +1,606 @@
+use std::path::Path;
+
+use crate::{
+	error::SignalProcessError,
+	multichannel::MultiChannelSignal,
+	types::{ChannelInfo, MontageDefinition, MontageDerivation, MontageTerm},
+};
+
+/// 10-10 与 10-20 系统中同一位置的不同命名。
+const LABEL_ALIASES: [(&str, &str); 4] = [("t7", "t3"), ("t8", "t4"), ("p7", "t5"), ("p8", "t6")];
+
+/// 纵向双香蕉导联。
+const LONGITUDINAL_BIPOLAR_PAIRS: [(&str, &str); 18] = [
+	("Fp1", "F7"),
+	("F7", "T3"),
+	("T3", "T5"),
+	("T5", "O1"),
+	("Fp2", "F8"),
+	("F8", "T4"),
+	("T4", "T6"),
+	("T6", "O2"),
+	("Fp1", "F3"),
+	("F3", "C3"),
+	("C3", "P3"),
+	("P3", "O1"),
+	("Fp2", "F4"),
+	("F4", "C4"),
+	("C4", "P4"),
+	("P4", "O2"),
+	("Fz", "Cz"),
+	("Cz", "Pz"),
+];
+
+/// 横向双极导联，自左向右。
+const TRANSVERSE_BIPOLAR_PAIRS: [(&str, &str); 14] = [
+	("Fp1", "Fp2"),
+	("F7", "F3"),
+	("F3", "Fz"),
+	("Fz", "F4"),
+	("F4", "F8"),
+	("T3", "C3"),
+	("C3", "Cz"),
+	("Cz", "C4"),
+	("C4", "T4"),
+	("T5", "P3"),
+	("P3", "Pz"),
+	("Pz", "P4"),
+	("P4", "T6"),
+	("O1", "O2"),
+];
+
+/// 10-20 系统内部电极的最近邻，用于 Hjorth Laplacian。
+const LAPLACIAN_NEIGHBORS: [(&str, &[&str]); 9] = [
+	("F3", &["Fp1", "F7", "Fz", "C3"]),
+	("Fz", &["F3", "F4", "Cz"]),
+	("F4", &["Fp2", "Fz", "F8", "C4"]),
+	("C3", &["F3", "T3", "Cz", "P3"]),
+	("Cz", &["Fz", "C3", "C4", "Pz"]),
+	("C4", &["F4", "Cz", "T4", "P4"]),
+	("P3", &["C3", "T5", "Pz", "O1"]),
+	("Pz", &["Cz", "P3", "P4"]),
+	("P4", &["C4", "Pz", "T6", "O2"]),
+];
+
+/// 已按输入通道名解析的导联，可以反复用于整段信号或回放时的数据块。
+#[derive(Debug, Clone)]
+pub struct Montage {
+	name: String,
+	channels: Vec<DerivedChannel>,
+}
+
+/// 一个派生通道：若干输入通道的加权和。
+#[derive(Debug, Clone)]
+struct DerivedChannel {
+	label: String,
+	terms: Vec<(usize, f32)>,
+}
+
+impl Montage {
+	/// 按输入通道名解析导联定义。
+	///
+	/// 通道名比较忽略大小写、首尾空白、`EEG ` 前缀与 `-REF` 后缀，并把 T7/T8/P7/P8 视为 T3/T4/T5/T6。
+	pub fn resolve(
+		definition: &MontageDefinition,
+		input_labels: &[&str],
+	) -> Result<Self, SignalProcessError> {
+		if input_labels.is_empty() {
+			return Err(SignalProcessError::EmptyInput);
+		}
+
+		let normalized = input_labels
+			.iter()
+			.map(|label| normalize_label(label))
+			.collect::<Vec<_>>();
+		let find = |label: &str| -> Result<usize, SignalProcessError> {
+			let target = normalize_label(label);
+			normalized
+				.iter()
+				.position(|candidate| *candidate == target)
+				.ok_or_else(|| {
+					SignalProcessError::InvalidArgument(format!("输入中缺少通道 {label}"))
+				})
+		};
+		let select =
+			|channels: &[String], exclude: &[String]| -> Result<Vec<usize>, SignalProcessError> {
+				let selected = if channels.is_empty() {
+					(0..input_labels.len()).collect()
+				} else {
+					channels
+						.iter()
+						.map(|channel| find(channel))
+						.collect::<Result<Vec<_>, _>>()?
+				};
+				let excluded = exclude
+					.iter()
+					.map(|label| normalize_label(label))
+					.collect::<Vec<_>>();
+				Ok(selected
+					.into_iter()
+					.filter(|index| !excluded.contains(&normalized[*index]))
+					.collect())
+			};
+
+		let mut channels = Vec::new();
+		for derivation in &definition.derivations {
+			match derivation {
+				MontageDerivation::Bipolar {
+					positive,
+					negative,
+					label,
+				} => channels.push(DerivedChannel {
+					label: label
+						.clone()
+						.unwrap_or_else(|| format!("{positive}-{negative}")),
+					terms: vec![(find(positive)?, 1.0), (find(negative)?, -1.0)],
+				}),
+				MontageDerivation::Reference {
+					references,
+					channels: selected,
+					exclude,
+				} => {
+					if references.is_empty() {
+						return Err(SignalProcessError::InvalidArgument(
+							"参考导联至少需要一个参考通道".to_string(),
+						));
+					}
+					let reference_indices = references
+						.iter()
+						.map(|reference| find(reference))
+						.collect::<Result<Vec<_>, _>>()?;
+					let reference_label = if references.len() == 1 {
+						references[0].clone()
+					} else {
+						format!("({})/{}", references.join("+"), references.len())
+					};
+					for index in select(selected, exclude)? {
+						if reference_indices.contains(&index) {
+							continue;
+						}
+						channels.push(DerivedChannel {
+							label: format!("{}-{reference_label}", input_labels[index].trim()),
+							terms: subtract_mean(index, &reference_indices),
+						});
+					}
+				}
+				MontageDerivation::CommonAverage {
+					channels: selected,
+					exclude,
+				} => {
+					let average = select(selected, exclude)?;
+					if average.is_empty() {
+						return Err(SignalProcessError::InvalidArgument(
+							"平均参考没有可用通道".to_string(),
+						));
+					}
+					for index in average.iter().copied() {
+						channels.push(DerivedChannel {
+							label: format!("{}-AVG", input_labels[index].trim()),
+							terms: subtract_mean(index, &average),
+						});
+					}
+				}
+				MontageDerivation::Laplacian {
+					channel,
+					neighbors,
+					label,
+				} => {
+					if neighbors.is_empty() {
+						return Err(SignalProcessError::InvalidArgument(format!(
+							"Laplacian 通道 {channel} 至少需要一个相邻电极"
+						)));
+					}
+					let neighbor_indices = neighbors
+						.iter()
+						.map(|neighbor| find(neighbor))
+						.collect::<Result<Vec<_>, _>>()?;
+					channels.push(DerivedChannel {
+						label: label.clone().unwrap_or_else(|| format!("{channel}-LAP")),
+						terms: subtract_mean(find(channel)?, &neighbor_indices),
+					});
+				}
+				MontageDerivation::Weighted { label, terms } => {
+					if terms.is_empty() {
+						return Err(SignalProcessError::InvalidArgument(format!(
+							"加权导联 {label} 至少需要一项"
+						)));
+					}
+					channels.push(DerivedChannel {
+						label: label.clone(),
+						terms: terms
+							.iter()
+							.map(|term| Ok((find(&term.channel)?, term.weight)))
+							.collect::<Result<Vec<_>, SignalProcessError>>()?,
+					});
+				}
+			}
+		}
+
+		if channels.is_empty() {
+			return Err(SignalProcessError::InvalidArgument(format!(
+				"导联 {} 没有派生出任何通道",
+				definition.name
+			)));
+		}
+		Ok(Self {
+			name: definition.name.clone(),
+			channels,
+		})
+	}
+
+	pub fn name(&self) -> &str {
+		&self.name
+	}
+
+	pub fn channel_count(&self) -> usize {
+		self.channels.len()
+	}
+
+	pub fn labels(&self) -> impl Iterator<Item = &str> {
+		self.channels.iter().map(|channel| channel.label.as_str())
+	}
+
+	/// 对 `[通道][样本]` 数据块计算派生通道，适用于回放时逐块处理。
+	///
+	/// 每个派生通道的长度取参与计算的输入通道中最短者。
+	pub fn apply_channels(&self, input: &[Vec<f32>]) -> Result<Vec<Vec<f32>>, SignalProcessError> {
+		self.check_input_count(input.len())?;
+
+		Ok(self
+			.channels
+			.iter()
+			.map(|channel| {
+				let length = channel
+					.terms
+					.iter()
+					.map(|(index, _)| input[*index].len())
+					.min()
+					.unwrap_or(0);
+				let mut output = vec![0.0; length];
+				for (index, weight) in channel.terms.iter() {
+					for (value, sample) in output.iter_mut().zip(input[*index].iter()) {
+						*value += weight * sample;
+					}
+				}
+				output
+			})
+			.collect())
+	}
+
+	/// 对多通道信号计算派生通道，单位取第一项的输入通道。
+	pub fn apply(
+		&self,
+		signal: &MultiChannelSignal,
+	) -> Result<MultiChannelSignal, SignalProcessError> {
+		self.check_input_count(signal.channel_count())?;
+		let infos = signal.infos();
+		let derived_infos = self
+			.channels
+			.iter()
+			.map(|channel| {
+				let first = channel
+					.terms
+					.first()
+					.and_then(|(index, _)| infos.get(*index))
+					.ok_or_else(|| {
+						SignalProcessError::InvalidArgument(format!(
+							"派生通道 {} 引用了不存在的输入通道",
+							channel.label
+						))
+					})?;
+				if channel
+					.terms
+					.iter()
+					.any(|(index, _)| infos[*index].sample_rate != first.sample_rate)
+				{
+					return Err(SignalProcessError::InvalidArgument(format!(
+						"派生通道 {} 的输入通道采样率不一致",
+						channel.label
+					)));
+				}
+				Ok(ChannelInfo {
+					label: channel.label.clone(),
+					unit: first.unit.clone(),
+					sample_rate: first.sample_rate,
+				})
+			})
+			.collect::<Result<Vec<_>, SignalProcessError>>()?;
+
+		MultiChannelSignal::new(
+			derived_infos,
+			self.apply_channels(signal.channel_data())?,
+			signal.start_time_secs(),
+		)
+	}
+
+	fn check_input_count(&self, available: usize) -> Result<(), SignalProcessError> {
+		let input_count = self.input_count();
+		if available < input_count {
+			return Err(SignalProcessError::InvalidArgument(format!(
+				"导联需要至少 {input_count} 个输入通道，实际 {available}"
+			)));
+		}
+		Ok(())
+	}
+
+	fn input_count(&self) -> usize {
+		self.channels
+			.iter()
+			.flat_map(|channel| channel.terms.iter().map(|(index, _)| index + 1))
+			.max()
+			.unwrap_or(0)
+	}
+}
+
+/// 解析导联定义后应用到多通道信号。
+pub fn apply_montage(
+	signal: &MultiChannelSignal,
+	definition: &MontageDefinition,
+) -> Result<MultiChannelSignal, SignalProcessError> {
+	let labels = signal.labels().collect::<Vec<_>>();
+	Montage::resolve(definition, &labels)?.apply(signal)
+}
+
+/// 从 JSON 文本解析导联定义。
+pub fn parse_montage_definition(json: &str) -> Result<MontageDefinition, SignalProcessError> {
+	Ok(serde_json::from_str(json)?)
+}
+
+/// 从 JSON 文件加载导联定义。
+pub fn load_montage_definition(path: &Path) -> Result<MontageDefinition, SignalProcessError> {
+	let file = std::fs::File::open(path)?;
+	Ok(serde_json::from_reader(std::io::BufReader::new(file))?)
+}
+
+/// 全部通道的平均参考。
+pub fn common_average_montage() -> MontageDefinition {
+	MontageDefinition {
+		name: "Common average".to_string(),
+		derivations: vec![MontageDerivation::CommonAverage {
+			channels: Vec::new(),
+			exclude: Vec::new(),
+		}],
+	}
+}
+
+/// 双侧乳突 (或耳垂) 连接参考，参考通道本身不输出。
+pub fn linked_mastoid_montage(left: &str, right: &str) -> MontageDefinition {
+	MontageDefinition {
+		name: "Linked mastoid".to_string(),
+		derivations: vec![MontageDerivation::Reference {
+			references: vec![left.to_string(), right.to_string()],
+			channels: Vec::new(),
+			exclude: Vec::new(),
+		}],
+	}
+}
+
+/// 10-20 系统纵向双极 (双香蕉) 导联。
+pub fn longitudinal_bipolar_montage() -> MontageDefinition {
+	bipolar_montage("Longitudinal bipolar", &LONGITUDINAL_BIPOLAR_PAIRS)
+}
+
+/// 10-20 系统横向双极导联。
+pub fn transverse_bipolar_montage() -> MontageDefinition {
+	bipolar_montage("Transverse bipolar", &TRANSVERSE_BIPOLAR_PAIRS)
+}
+
+/// 10-20 系统内部电极的最近邻 Hjorth Laplacian。
+pub fn laplacian_montage() -> MontageDefinition {
+	MontageDefinition {
+		name: "Surface Laplacian".to_string(),
+		derivations: LAPLACIAN_NEIGHBORS
+			.iter()
+			.map(|(channel, neighbors)| MontageDerivation::Laplacian {
+				channel: channel.to_string(),
+				neighbors: neighbors
+					.iter()
+					.map(|neighbor| neighbor.to_string())
+					.collect(),
+				label: None,
+			})
+			.collect(),
+	}
+}
+
+fn bipolar_montage(name: &str, pairs: &[(&str, &str)]) -> MontageDefinition {
+	MontageDefinition {
+		name: name.to_string(),
+		derivations: pairs
+			.iter()
+			.map(|(positive, negative)| MontageDerivation::Bipolar {
+				positive: positive.to_string(),
+				negative: negative.to_string(),
+				label: None,
+			})
+			.collect(),
+	}
+}
+
+/// 通道本身减去一组通道的平均，组内包含该通道时合并系数。
+fn subtract_mean(channel: usize, group: &[usize]) -> Vec<(usize, f32)> {
+	let weight = 1.0 / group.len() as f32;
+	let mut terms = vec![(channel, 1.0)];
+	for index in group.iter().copied() {
+		match terms.iter_mut().find(|(existing, _)| *existing == index) {
+			Some((_, existing_weight)) => *existing_weight -= weight,
+			None => terms.push((index, -weight)),
+		}
+	}
+	terms
+}
+
+fn normalize_label(label: &str) -> String {
+	let mut label = label.trim().to_lowercase();
+	if let Some(stripped) = label.strip_prefix("eeg ") {
+		label = stripped.trim().to_string();
+	}
+	if let Some(stripped) = label.strip_suffix("-ref") {
+		label = stripped.trim().to_string();
+	}
+	LABEL_ALIASES
+		.iter()
+		.find(|(alias, _)| *alias == label)
+		.map_or(label, |(_, canonical)| canonical.to_string())
+}
+
+impl MontageTerm {
+	pub fn new(channel: &str, weight: f32) -> Self {
+		Self {
+			channel: channel.to_string(),
+			weight,
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use crate::SignalBuffer;
+
+	use super::*;
+
+	const LABELS: [&str; 21] = [
+		"EEG Fp1-REF",
+		"EEG Fp2-REF",
+		"EEG F7-REF",
+		"EEG F3-REF",
+		"EEG Fz-REF",
+		"EEG F4-REF",
+		"EEG F8-REF",
+		"EEG T7-REF",
+		"EEG C3-REF",
+		"EEG Cz-REF",
+		"EEG C4-REF",
+		"EEG T8-REF",
+		"EEG P7-REF",
+		"EEG P3-REF",
+		"EEG Pz-REF",
+		"EEG P4-REF",
+		"EEG P8-REF",
+		"EEG O1-REF",
+		"EEG O2-REF",
+		"EEG A1-REF",
+		"EEG A2-REF",
+	];
+
+	/// 每个通道是常数 index，便于直接核对派生结果。
+	fn indexed_signal() -> MultiChannelSignal {
+		MultiChannelSignal::from_signal_buffers(
+			(0..LABELS.len())
+				.map(|index| SignalBuffer {
+					sample_rate: 256.0,
+					samples: vec![index as f32; 8],
+				})
+				.collect(),
+			&LABELS,
+			"uV",
+		)
+		.unwrap_or_else(|error| panic!("多通道信号创建失败: {error}"))
+	}
+
+	#[test]
+	fn builtin_montages_should_resolve_against_edf_labels() {
+		let signal = indexed_signal();
+
+		let longitudinal = apply_montage(&signal, &longitudinal_bipolar_montage())
+			.unwrap_or_else(|error| panic!("纵向双极导联失败: {error}"));
+		let transverse = apply_montage(&signal, &transverse_bipolar_montage())
+			.unwrap_or_else(|error| panic!("横向双极导联失败: {error}"));
+		let laplacian = apply_montage(&signal, &laplacian_montage())
+			.unwrap_or_else(|error| panic!("Laplacian 导联失败: {error}"));
+		let common_average = apply_montage(&signal, &common_average_montage())
+			.unwrap_or_else(|error| panic!("平均参考失败: {error}"));
+		let linked = apply_montage(&signal, &linked_mastoid_montage("A1", "A2"))
+			.unwrap_or_else(|error| panic!("乳突参考失败: {error}"));
+
+		assert_eq!(longitudinal.channel_count(), 18);
+		let first = longitudinal
+			.channel(0)
+			.unwrap_or_else(|| panic!("缺少通道"));
+		assert_eq!(first.info.label, "Fp1-F7");
+		assert_eq!(first.samples[0], -2.0);
+		assert_eq!(transverse.channel_count(), 14);
+		let cz = laplacian
+			.channel_by_label("Cz-LAP")
+			.unwrap_or_else(|| panic!("缺少 Cz-LAP"));
+		assert!((cz.samples[0] - (9.0 - (4.0 + 8.0 + 10.0 + 14.0) / 4.0)).abs() < 1e-6);
+		let average_sum = common_average
+			.channels()
+			.map(|channel| channel.samples[0])
+			.sum::<f32>();
+		assert!(average_sum.abs() < 1e-4);
+		assert_eq!(linked.channel_count(), 19);
+		let o2 = linked
+			.channel_by_label("EEG O2-REF-(A1+A2)/2")
+			.unwrap_or_else(|| panic!("缺少 O2 通道"));
+		assert!((o2.samples[0] - (18.0 - 19.5)).abs() < 1e-6);
+	}
+
+	#[test]
+	fn json_montage_should_support_all_derivation_kinds() {
+		let definition = parse_montage_definition(
+			r#"{
+				"name": "custom",
+				"derivations": [
+					{"kind": "bipolar", "positive": "C3", "negative": "C4", "label": "C3-C4"},
+					{"kind": "laplacian", "channel": "Cz", "neighbors": ["C3", "C4"]},
+					{"kind": "weighted", "label": "2Fz", "terms": [{"channel": "Fz", "weight": 2.0}]},
+					{"kind": "common_average", "channels": ["Fp1", "Fp2", "A1"], "exclude": ["A1"]}
+				]
+			}"#,
+		)
+		.unwrap_or_else(|error| panic!("导联 JSON 解析失败: {error}"));
+		let montage = Montage::resolve(&definition, &LABELS)
+			.unwrap_or_else(|error| panic!("导联解析失败: {error}"));
+
+		let output = montage
+			.apply_channels(indexed_signal().channel_data())
+			.unwrap_or_else(|error| panic!("导联计算失败: {error}"));
+
+		assert_eq!(
+			montage.labels().collect::<Vec<_>>(),
+			[
+				"C3-C4",
+				"Cz-LAP",
+				"2Fz",
+				"EEG Fp1-REF-AVG",
+				"EEG Fp2-REF-AVG"
+			]
+		);
+		assert_eq!(output[0][0], -2.0);
+		assert_eq!(output[1][0], 0.0);
+		assert_eq!(output[2][0], 8.0);
+		assert_eq!(output[3][0], -0.5);
+		assert_eq!(output[4][0], 0.5);
+	}
+
+	#[test]
+	fn missing_channel_should_be_reported() {
+		let result = Montage::resolve(&longitudinal_bipolar_montage(), &["Fp1", "F7"]);
+
+		assert!(result.is_err());
+		assert!(
+			parse_montage_definition(r#"{"name": "x", "derivations": [{"kind": "?"}]}"#).is_err()
+		);
+	}
+
+	#[test]
+	fn montage_applied_to_fewer_channels_should_fail() {
+		let montage = Montage::resolve(&common_average_montage(), &["Fp1", "Cz", "O1"])
+			.unwrap_or_else(|error| panic!("导联解析失败: {error}"));
+		let signal = MultiChannelSignal::from_signal_buffers(
+			vec![
+				SignalBuffer {
+					sample_rate: 256.0,
+					samples: vec![1.0; 8],
+				};
+				2
+			],
+			&["Fp1", "Cz"],
+			"uV",
+		)
+		.unwrap_or_else(|error| panic!("多通道信号创建失败: {error}"));
+
+		assert!(montage.apply(&signal).is_err());
+	}
+}
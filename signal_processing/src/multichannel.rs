@@ -0,0 +1,351 @@
+use crate::{
+	error::SignalProcessError,
+	types::{ChannelInfo, SignalBuffer},
+};
+
+/// 带通道名、单位与采样率的多通道信号。
+///
+/// 各通道可以有不同的采样率和长度，`start_time_secs` 为首个样本相对记录起点的时间。
+#[derive(Debug, Clone)]
+pub struct MultiChannelSignal {
+	infos: Vec<ChannelInfo>,
+	channels: Vec<Vec<f32>>,
+	start_time_secs: f64,
+}
+
+/// 单个通道的只读视图，不复制样本。
+#[derive(Debug, Clone, Copy)]
+pub struct ChannelView<'a> {
+	pub info: &'a ChannelInfo,
+	pub samples: &'a [f32],
+}
+
+impl ChannelView<'_> {
+	/// 复制为单通道缓冲区，供只接受 `SignalBuffer` 的处理函数使用。
+	pub fn to_signal_buffer(&self) -> SignalBuffer {
+		SignalBuffer {
+			sample_rate: self.info.sample_rate,
+			samples: self.samples.to_vec(),
+		}
+	}
+
+	/// 通道时长 (秒)。
+	pub fn duration_secs(&self) -> f32 {
+		self.samples.len() as f32 / self.info.sample_rate
+	}
+}
+
+impl MultiChannelSignal {
+	/// 由通道元数据与样本创建多通道信号。
+	pub fn new(
+		infos: Vec<ChannelInfo>,
+		channels: Vec<Vec<f32>>,
+		start_time_secs: f64,
+	) -> Result<Self, SignalProcessError> {
+		if channels.is_empty() {
+			return Err(SignalProcessError::EmptyInput);
+		}
+		if infos.len() != channels.len() {
+			return Err(SignalProcessError::InvalidArgument(format!(
+				"通道元数据数量 {} 与通道数量 {} 不一致",
+				infos.len(),
+				channels.len()
+			)));
+		}
+		if let Some(info) = infos.iter().find(|info| info.sample_rate <= 0.0) {
+			return Err(SignalProcessError::InvalidArgument(format!(
+				"通道 {} 的采样率必须大于 0",
+				info.label
+			)));
+		}
+		if !start_time_secs.is_finite() {
+			return Err(SignalProcessError::InvalidArgument(
+				"起始时间必须为有限值".to_string(),
+			));
+		}
+
+		Ok(Self {
+			infos,
+			channels,
+			start_time_secs,
+		})
+	}
+
+	/// 由若干单通道缓冲区创建，单位统一为 `unit`。
+	pub fn from_signal_buffers(
+		buffers: Vec<SignalBuffer>,
+		labels: &[&str],
+		unit: &str,
+	) -> Result<Self, SignalProcessError> {
+		if labels.len() != buffers.len() {
+			return Err(SignalProcessError::InvalidArgument(format!(
+				"通道名数量 {} 与通道数量 {} 不一致",
+				labels.len(),
+				buffers.len()
+			)));
+		}
+
+		let (infos, channels) = buffers
+			.into_iter()
+			.zip(labels.iter())
+			.map(|(buffer, label)| {
+				(
+					ChannelInfo {
+						label: label.to_string(),
+						unit: unit.to_string(),
+						sample_rate: buffer.sample_rate,
+					},
+					buffer.samples,
+				)
+			})
+			.unzip();
+		Self::new(infos, channels, 0.0)
+	}
+
+	pub fn channel_count(&self) -> usize {
+		self.channels.len()
+	}
+
+	pub fn start_time_secs(&self) -> f64 {
+		self.start_time_secs
+	}
+
+	pub fn infos(&self) -> &[ChannelInfo] {
+		&self.infos
+	}
+
+	pub fn labels(&self) -> impl Iterator<Item = &str> {
+		self.infos.iter().map(|info| info.label.as_str())
+	}
+
+	/// 所有通道采样率相同时返回该采样率。
+	pub fn uniform_sample_rate(&self) -> Option<f32> {
+		let first = self.infos.first()?.sample_rate;
+		self.infos
+			.iter()
+			.all(|info| info.sample_rate == first)
+			.then_some(first)
+	}
+
+	pub fn channel(&self, index: usize) -> Option<ChannelView<'_>> {
+		Some(ChannelView {
+			info: self.infos.get(index)?,
+			samples: self.channels.get(index)?,
+		})
+	}
+
+	/// 按通道名查找，名称比较忽略首尾空白。
+	pub fn channel_by_label(&self, label: &str) -> Option<ChannelView<'_>> {
+		let index = self
+			.infos
+			.iter()
+			.position(|info| info.label.trim() == label.trim())?;
+		self.channel(index)
+	}
+
+	pub fn channels(&self) -> impl Iterator<Item = ChannelView<'_>> {
+		self.infos
+			.iter()
+			.zip(self.channels.iter())
+			.map(|(info, samples)| ChannelView { info, samples })
+	}
+
+	/// 按 [通道][样本] 排列的原始数据，可直接交给 `FilterBank`。
+	pub fn channel_data(&self) -> &[Vec<f32>] {
+		&self.channels
+	}
+
+	pub fn channel_data_mut(&mut self) -> &mut [Vec<f32>] {
+		&mut self.channels
+	}
+
+	/// 对每个通道执行处理并保留元数据，返回的新样本可以改变长度。
+	pub fn map_channels<F>(&self, mut process: F) -> Result<Self, SignalProcessError>
+	where
+		F: FnMut(ChannelView<'_>) -> Result<Vec<f32>, SignalProcessError>,
+	{
+		let channels = self
+			.channels()
+			.map(&mut process)
+			.collect::<Result<Vec<_>, SignalProcessError>>()?;
+		Ok(Self {
+			infos: self.infos.clone(),
+			channels,
+			start_time_secs: self.start_time_secs,
+		})
+	}
+
+	/// 拆分为单通道缓冲区，通道名与单位丢弃。
+	pub fn into_signal_buffers(self) -> Vec<SignalBuffer> {
+		self.infos
+			.into_iter()
+			.zip(self.channels)
+			.map(|(info, samples)| SignalBuffer {
+				sample_rate: info.sample_rate,
+				samples,
+			})
+			.collect()
+	}
+}
+
+/// 起始时间取 EDF+/BDF+ 首个记录计时注释中的偏移，普通 EDF/BDF 为 0。
+#[cfg(feature = "edf")]
+impl TryFrom<&edf_io::EdfLoader> for MultiChannelSignal {
+	type Error = SignalProcessError;
+
+	fn try_from(loader: &edf_io::EdfLoader) -> Result<Self, Self::Error> {
+		let infos = loader
+			.labels()
+			.iter()
+			.zip(loader.physical_dimensions())
+			.zip(loader.channel_sample_rates())
+			.map(|((label, unit), sample_rate)| ChannelInfo {
+				label: label.clone(),
+				unit: unit.clone(),
+				sample_rate: *sample_rate,
+			})
+			.collect();
+		Self::new(
+			infos,
+			loader.channels().to_vec(),
+			loader.start_offset_secs(),
+		)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use crate::{FilterBank, FirFilter};
+
+	use super::*;
+
+	fn test_signal() -> MultiChannelSignal {
+		MultiChannelSignal::new(
+			vec![
+				ChannelInfo {
+					label: "Fp1".to_string(),
+					unit: "uV".to_string(),
+					sample_rate: 256.0,
+				},
+				ChannelInfo {
+					label: "ECG".to_string(),
+					unit: "mV".to_string(),
+					sample_rate: 128.0,
+				},
+			],
+			vec![vec![1.0; 512], vec![2.0; 256]],
+			1.5,
+		)
+		.unwrap_or_else(|error| panic!("多通道信号创建失败: {error}"))
+	}
+
+	#[test]
+	fn views_should_borrow_channel_samples_with_metadata() {
+		let signal = test_signal();
+		let view = signal
+			.channel_by_label(" ECG ")
+			.unwrap_or_else(|| panic!("缺少 ECG 通道"));
+
+		assert!(std::ptr::eq(
+			view.samples,
+			signal.channel_data()[1].as_slice()
+		));
+		assert_eq!(view.info.unit, "mV");
+		assert!((view.duration_secs() - 2.0).abs() < 1e-6);
+		assert_eq!(signal.uniform_sample_rate(), None);
+		assert_eq!(signal.labels().collect::<Vec<_>>(), ["Fp1", "ECG"]);
+	}
+
+	#[test]
+	fn mismatched_metadata_should_be_rejected() {
+		let result = MultiChannelSignal::from_signal_buffers(
+			vec![SignalBuffer {
+				sample_rate: 256.0,
+				samples: vec![0.0; 8],
+			}],
+			&["C3", "C4"],
+			"uV",
+		);
+
+		assert!(result.is_err());
+	}
+
+	#[test]
+	fn filter_bank_and_map_should_keep_metadata() {
+		let mut signal = MultiChannelSignal::from_signal_buffers(
+			vec![
+				SignalBuffer {
+					sample_rate: 100.0,
+					samples: vec![1.0; 16],
+				},
+				SignalBuffer {
+					sample_rate: 100.0,
+					samples: vec![3.0; 16],
+				},
+			],
+			&["C3", "C4"],
+			"uV",
+		)
+		.unwrap_or_else(|error| panic!("多通道信号创建失败: {error}"));
+		let prototype = FirFilter::new(&[0.5, 0.5])
+			.unwrap_or_else(|error| panic!("FIR 滤波器创建失败: {error}"));
+		let mut bank = FilterBank::new(&prototype, signal.channel_count())
+			.unwrap_or_else(|error| panic!("滤波器组创建失败: {error}"));
+		bank.process_in_place(signal.channel_data_mut())
+			.unwrap_or_else(|error| panic!("滤波失败: {error}"));
+		let doubled = signal
+			.map_channels(|view| Ok(view.samples.iter().map(|value| value * 2.0).collect()))
+			.unwrap_or_else(|error| panic!("通道映射失败: {error}"));
+
+		assert_eq!(doubled.uniform_sample_rate(), Some(100.0));
+		let buffers = doubled.into_signal_buffers();
+		assert!((buffers[0].samples[0] - 1.0).abs() < 1e-6);
+		assert!((buffers[1].samples[15] - 6.0).abs() < 1e-6);
+	}
+
+	#[cfg(feature = "edf")]
+	#[test]
+	fn edf_loader_should_convert_with_start_offset() {
+		let path = std::env::temp_dir().join("signal_processing_multichannel_from_edf.edf");
+		let signal = |label: &str, sample_rate: f64| edf_io::EdfSignalParam {
+			label: label.to_string(),
+			transducer: "AgAgCl electrode".to_string(),
+			physical_dimension: "uV".to_string(),
+			physical_min: -100.0,
+			physical_max: 100.0,
+			digital_min: -32768,
+			digital_max: 32767,
+			prefilter: String::new(),
+			sample_rate,
+		};
+		let mut writer = edf_io::EdfWriter::create(
+			&path,
+			vec![signal("EEG Cz", 8.0), signal("EEG Pz", 4.0)],
+			edf_io::EdfWriterOptions::default(),
+		)
+		.unwrap_or_else(|error| panic!("EDF 写入器创建失败: {error}"));
+		writer
+			.write_record_at(0.5, &[vec![10.0; 8], vec![-10.0; 4]])
+			.unwrap_or_else(|error| panic!("EDF 记录写入失败: {error}"));
+		writer
+			.write_samples(&[vec![20.0; 8], vec![-20.0; 4]])
+			.unwrap_or_else(|error| panic!("EDF 记录写入失败: {error}"));
+		writer
+			.finalize()
+			.unwrap_or_else(|error| panic!("EDF 文件写入失败: {error}"));
+		let loader = edf_io::EdfLoader::from_file(&path.to_string_lossy())
+			.unwrap_or_else(|error| panic!("EDF 加载失败: {error}"));
+		std::fs::remove_file(&path).ok();
+
+		let signal = MultiChannelSignal::try_from(&loader)
+			.unwrap_or_else(|error| panic!("多通道信号转换失败: {error}"));
+
+		assert_eq!(signal.start_time_secs(), 0.5);
+		assert_eq!(signal.channel_count(), 2);
+		let pz = signal.channel(1).unwrap_or_else(|| panic!("缺少第 1 通道"));
+		assert_eq!(pz.info.label, "EEG Pz");
+		assert_eq!(pz.info.sample_rate, 4.0);
+		assert_eq!(pz.samples.len(), 8);
+		assert!((pz.samples[7] + 20.0).abs() < 0.01);
+	}
+}
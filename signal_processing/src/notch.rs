@@ -0,0 +1,402 @@
+use std::f64::consts::TAU;
+
+use crate::{
+	error::SignalProcessError,
+	filter::{FiltfiltConfig, StreamingFilter, filtfilt_sos},
+	response::ensure_sos_stable,
+	spectrum::compute_power_spectrum_with_config,
+	types::{
+		IirCoefficients, LineNoiseDetection, NotchConfig, SignalBuffer, SosCoefficients,
+		WelchSpectrumConfig,
+	},
+};
+
+/// 候选工频 (Hz)。
+const LINE_FREQUENCIES_HZ: [f32; 2] = [50.0, 60.0];
+
+/// 工频峰值的搜索半宽 (Hz)。
+const LINE_PEAK_HALF_WIDTH_HZ: f32 = 1.0;
+
+/// 估计噪声基底的邻近频段半宽 (Hz)，其中峰值搜索区间附近 ±2 Hz 不参与统计。
+const LINE_FLOOR_HALF_WIDTH_HZ: f32 = 5.0;
+const LINE_FLOOR_GUARD_HZ: f32 = 2.0;
+
+/// 峰值与基底之比低于该值时认为不存在工频干扰。
+const MIN_LINE_PEAK_RATIO: f32 = 10.0;
+
+/// 设计单个二阶陷波节，系数与 scipy.signal.iirnotch 一致。
+pub fn design_notch(
+	sample_rate: f32,
+	center_hz: f32,
+	quality_factor: f32,
+) -> Result<IirCoefficients, SignalProcessError> {
+	if sample_rate <= 0.0 {
+		return Err(SignalProcessError::InvalidArgument(
+			"采样率必须大于 0".to_string(),
+		));
+	}
+	if center_hz <= 0.0 || center_hz >= sample_rate / 2.0 {
+		return Err(SignalProcessError::InvalidArgument(format!(
+			"陷波频率 {center_hz} Hz 必须位于 (0, {}) Hz 内",
+			sample_rate / 2.0
+		)));
+	}
+	if !quality_factor.is_finite() || quality_factor <= 0.0 {
+		return Err(SignalProcessError::InvalidArgument(
+			"品质因数必须为正的有限值".to_string(),
+		));
+	}
+
+	let center = TAU * center_hz as f64 / sample_rate as f64;
+	let bandwidth = center / quality_factor as f64;
+	let gain = 1.0 / (1.0 + (bandwidth / 2.0).tan());
+	let cosine = center.cos();
+	Ok(IirCoefficients {
+		b: [gain as f32, (-2.0 * gain * cosine) as f32, gain as f32],
+		a: [
+			1.0,
+			(-2.0 * gain * cosine) as f32,
+			(2.0 * gain - 1.0) as f32,
+		],
+	})
+}
+
+/// 在工频基频与各次谐波处级联陷波节，得到梳状陷波器。
+///
+/// 各谐波使用相同的品质因数，因此高次谐波的陷波带宽按频率成比例变宽。
+pub fn design_comb_notch(config: &NotchConfig) -> Result<SosCoefficients, SignalProcessError> {
+	let harmonics = harmonic_frequencies(
+		config.sample_rate,
+		config.line_frequency_hz,
+		config.harmonic_count,
+	)?;
+	let sos = SosCoefficients {
+		sections: harmonics
+			.into_iter()
+			.map(|frequency| design_notch(config.sample_rate, frequency, config.quality_factor))
+			.collect::<Result<Vec<_>, _>>()?,
+	};
+	ensure_sos_stable(&sos)?;
+	Ok(sos)
+}
+
+/// 比较 Welch 功率谱中 50 Hz 与 60 Hz 处的峰值，判断工频。
+///
+/// 峰值与邻近频段中位数之比都不足 10 倍时返回 None；
+/// 频率分辨率取 0.5 Hz，信号不足 2 秒时按实际长度降低分辨率。
+pub fn detect_line_frequency(
+	signal: &SignalBuffer,
+) -> Result<Option<LineNoiseDetection>, SignalProcessError> {
+	if signal.samples.is_empty() {
+		return Err(SignalProcessError::EmptyInput);
+	}
+	let nyquist = signal.sample_rate / 2.0;
+	let candidates = LINE_FREQUENCIES_HZ
+		.into_iter()
+		.filter(|frequency| frequency + LINE_FLOOR_HALF_WIDTH_HZ < nyquist)
+		.collect::<Vec<_>>();
+	if candidates.is_empty() {
+		return Err(SignalProcessError::InvalidArgument(format!(
+			"采样率 {} Hz 过低，无法检测工频",
+			signal.sample_rate
+		)));
+	}
+
+	let segment_length = ((2.0 * signal.sample_rate).round() as usize).min(signal.samples.len());
+	let spectrum = compute_power_spectrum_with_config(
+		signal,
+		WelchSpectrumConfig {
+			segment_length,
+			overlap_length: segment_length / 2,
+		},
+	)?;
+
+	let detection = candidates
+		.into_iter()
+		.map(|frequency_hz| {
+			let peak = spectrum
+				.iter()
+				.filter(|point| (point.frequency - frequency_hz).abs() <= LINE_PEAK_HALF_WIDTH_HZ)
+				.map(|point| point.value)
+				.fold(0.0, f32::max);
+			let mut floor = spectrum
+				.iter()
+				.filter(|point| {
+					let distance = (point.frequency - frequency_hz).abs();
+					distance > LINE_FLOOR_GUARD_HZ && distance <= LINE_FLOOR_HALF_WIDTH_HZ
+				})
+				.map(|point| point.value)
+				.collect::<Vec<_>>();
+			floor.sort_by(f32::total_cmp);
+			let median = floor
+				.get(floor.len() / 2)
+				.copied()
+				.unwrap_or(0.0)
+				.max(f32::MIN_POSITIVE);
+			LineNoiseDetection {
+				frequency_hz,
+				peak_ratio: peak / median,
+			}
+		})
+		.max_by(|left, right| left.peak_ratio.total_cmp(&right.peak_ratio))
+		.filter(|detection| detection.peak_ratio >= MIN_LINE_PEAK_RATIO);
+	Ok(detection)
+}
+
+/// 自动检测工频并做零相位梳状陷波，未检测到工频时原样返回信号。
+pub fn remove_line_noise(
+	signal: &SignalBuffer,
+	harmonic_count: usize,
+	quality_factor: f32,
+) -> Result<(SignalBuffer, Option<LineNoiseDetection>), SignalProcessError> {
+	let Some(detection) = detect_line_frequency(signal)? else {
+		return Ok((signal.clone(), None));
+	};
+
+	let sos = design_comb_notch(&NotchConfig {
+		sample_rate: signal.sample_rate,
+		line_frequency_hz: detection.frequency_hz,
+		harmonic_count,
+		quality_factor,
+	})?;
+	let filtered = filtfilt_sos(signal, &sos, FiltfiltConfig::default())?;
+	Ok((filtered, Some(detection)))
+}
+
+/// 基于 LMS 的自适应工频陷波器。
+///
+/// 以工频各次谐波的正弦、余弦作为参考输入，自适应估计干扰的幅度与相位并从输入中减去，
+/// 能跟踪工频干扰幅度的缓慢漂移，方法见 Widrow, B. et al. 1975，
+/// 参考文献：Adaptive noise cancelling: Principles and applications,
+/// doi: 10.1109/PROC.1975.10036
+#[derive(Debug, Clone)]
+pub struct AdaptiveNotchFilter {
+	/// 每个采样点参考信号相位的增量 (rad)。
+	phase_step: f64,
+	phase: f64,
+
+	/// 各谐波的 (余弦, 正弦) 权重。
+	weights: Vec<[f64; 2]>,
+	/// 当前采样点各谐波的 (余弦, 正弦) 参考信号，预先分配以免逐样本分配。
+	references: Vec<[f64; 2]>,
+	step_size: f64,
+}
+
+impl AdaptiveNotchFilter {
+	/// 创建自适应陷波器。
+	///
+	/// `step_size` 为 LMS 步长，越大收敛越快但对有用信号的损伤越大，
+	/// 对幅值约为 1 的参考信号常取 0.001 到 0.05。
+	pub fn new(
+		sample_rate: f32,
+		line_frequency_hz: f32,
+		harmonic_count: usize,
+		step_size: f32,
+	) -> Result<Self, SignalProcessError> {
+		let harmonics = harmonic_frequencies(sample_rate, line_frequency_hz, harmonic_count)?;
+		if !step_size.is_finite() || step_size <= 0.0 {
+			return Err(SignalProcessError::InvalidArgument(
+				"LMS 步长必须为正的有限值".to_string(),
+			));
+		}
+
+		Ok(Self {
+			phase_step: TAU * line_frequency_hz as f64 / sample_rate as f64,
+			phase: 0.0,
+			weights: vec![[0.0; 2]; harmonics.len()],
+			references: vec![[0.0; 2]; harmonics.len()],
+			step_size: step_size as f64,
+		})
+	}
+
+	pub fn harmonic_count(&self) -> usize {
+		self.weights.len()
+	}
+
+	/// 当前估计的各次谐波干扰幅度。
+	pub fn harmonic_amplitudes(&self) -> Vec<f32> {
+		self.weights
+			.iter()
+			.map(|[cosine, sine]| cosine.hypot(*sine) as f32)
+			.collect()
+	}
+}
+
+impl StreamingFilter for AdaptiveNotchFilter {
+	fn process_sample(&mut self, input: f32) -> f32 {
+		// 由基波相位按和角公式递推各次谐波，每个采样点只求一次三角函数
+		let (fundamental_sine, fundamental_cosine) = self.phase.sin_cos();
+		let mut harmonic = [1.0, 0.0];
+		for reference in self.references.iter_mut() {
+			harmonic = [
+				harmonic[0] * fundamental_cosine - harmonic[1] * fundamental_sine,
+				harmonic[1] * fundamental_cosine + harmonic[0] * fundamental_sine,
+			];
+			*reference = harmonic;
+		}
+
+		let estimate = self
+			.weights
+			.iter()
+			.zip(self.references.iter())
+			.map(|(weight, reference)| weight[0] * reference[0] + weight[1] * reference[1])
+			.sum::<f64>();
+		let error = input as f64 - estimate;
+		for (weight, reference) in self.weights.iter_mut().zip(self.references.iter()) {
+			weight[0] += 2.0 * self.step_size * error * reference[0];
+			weight[1] += 2.0 * self.step_size * error * reference[1];
+		}
+		self.phase = (self.phase + self.phase_step) % TAU;
+		error as f32
+	}
+
+	fn reset(&mut self) {
+		self.phase = 0.0;
+		self.weights.fill([0.0; 2]);
+	}
+
+	fn order(&self) -> usize {
+		2 * self.weights.len()
+	}
+
+	/// 恒定输入不含工频成分，稳态即权重为 0。
+	fn initialize_steady_state(&mut self, _value: f32) {
+		self.weights.fill([0.0; 2]);
+	}
+}
+
+/// 低于奈奎斯特频率的各次谐波频率。
+fn harmonic_frequencies(
+	sample_rate: f32,
+	line_frequency_hz: f32,
+	harmonic_count: usize,
+) -> Result<Vec<f32>, SignalProcessError> {
+	if sample_rate <= 0.0 {
+		return Err(SignalProcessError::InvalidArgument(
+			"采样率必须大于 0".to_string(),
+		));
+	}
+	if harmonic_count == 0 {
+		return Err(SignalProcessError::InvalidArgument(
+			"谐波个数必须大于 0".to_string(),
+		));
+	}
+	if line_frequency_hz <= 0.0 || line_frequency_hz >= sample_rate / 2.0 {
+		return Err(SignalProcessError::InvalidArgument(format!(
+			"工频 {line_frequency_hz} Hz 必须位于 (0, {}) Hz 内",
+			sample_rate / 2.0
+		)));
+	}
+
+	Ok((1..=harmonic_count)
+		.map(|harmonic| harmonic as f32 * line_frequency_hz)
+		.take_while(|frequency| *frequency < sample_rate / 2.0)
+		.collect())
+}
+
+#[cfg(test)]
+mod tests {
+	use std::f32::consts::TAU;
+
+	use super::*;
+	use crate::{response::freqz_sos, test_support::white_noise};
+
+	const SAMPLE_RATE: f32 = 500.0;
+
+	/// 10 Hz 节律叠加工频干扰与确定性的宽带噪声。
+	fn contaminated(line_frequency_hz: f32, line_amplitude: f32) -> SignalBuffer {
+		let samples = white_noise(12345, 5000)
+			.into_iter()
+			.enumerate()
+			.map(|(index, noise)| {
+				let time = index as f32 / SAMPLE_RATE;
+				(TAU * 10.0 * time).sin()
+					+ line_amplitude * (TAU * line_frequency_hz * time).sin()
+					+ 0.2 * noise
+			})
+			.collect();
+		SignalBuffer {
+			sample_rate: SAMPLE_RATE,
+			samples,
+		}
+	}
+
+	#[test]
+	fn comb_notch_should_reject_line_harmonics_only() {
+		let sos = design_comb_notch(&NotchConfig {
+			sample_rate: SAMPLE_RATE,
+			line_frequency_hz: 50.0,
+			harmonic_count: 10,
+			quality_factor: 30.0,
+		})
+		.unwrap_or_else(|error| panic!("梳状陷波设计失败: {error}"));
+		let response = freqz_sos(&sos, SAMPLE_RATE, 1000)
+			.unwrap_or_else(|error| panic!("频率响应计算失败: {error}"));
+		let magnitude_at = |frequency: f32| {
+			response
+				.iter()
+				.min_by(|left, right| {
+					(left.frequency - frequency)
+						.abs()
+						.total_cmp(&(right.frequency - frequency).abs())
+				})
+				.map_or(0.0, |point| point.magnitude_db)
+		};
+
+		assert_eq!(sos.sections.len(), 4);
+		for frequency in [50.0, 100.0, 150.0, 200.0] {
+			assert!(magnitude_at(frequency) < -40.0);
+		}
+		for frequency in [10.0, 30.0, 75.0, 125.0] {
+			assert!(magnitude_at(frequency).abs() < 0.5);
+		}
+	}
+
+	#[test]
+	fn line_frequency_should_be_detected_and_removed() {
+		let european = contaminated(50.0, 2.0);
+		let american = contaminated(60.0, 2.0);
+		let clean = contaminated(50.0, 0.0);
+
+		let detect = |signal: &SignalBuffer| {
+			detect_line_frequency(signal)
+				.unwrap_or_else(|error| panic!("工频检测失败: {error}"))
+				.map(|detection| detection.frequency_hz)
+		};
+		assert_eq!(detect(&european), Some(50.0));
+		assert_eq!(detect(&american), Some(60.0));
+		assert_eq!(detect(&clean), None);
+
+		let (filtered, detection) = remove_line_noise(&american, 3, 30.0)
+			.unwrap_or_else(|error| panic!("工频去除失败: {error}"));
+		assert_eq!(
+			detection.map(|detection| detection.frequency_hz),
+			Some(60.0)
+		);
+		let max_error = filtered.samples[500..4500]
+			.iter()
+			.zip(clean.samples[500..4500].iter())
+			.map(|(left, right)| (left - right).abs())
+			.fold(0.0, f32::max);
+		assert!(max_error < 0.1);
+	}
+
+	#[test]
+	fn adaptive_notch_should_track_line_interference() {
+		let signal = contaminated(50.0, 2.0);
+		let clean = contaminated(50.0, 0.0);
+		let mut filter = AdaptiveNotchFilter::new(SAMPLE_RATE, 50.0, 2, 0.005)
+			.unwrap_or_else(|error| panic!("自适应陷波器创建失败: {error}"));
+
+		let mut output = signal.samples.clone();
+		filter.process_in_place(&mut output);
+
+		let max_error = output[2500..]
+			.iter()
+			.zip(clean.samples[2500..].iter())
+			.map(|(left, right)| (left - right).abs())
+			.fold(0.0, f32::max);
+		assert!(max_error < 0.15);
+		assert!((filter.harmonic_amplitudes()[0] - 2.0).abs() < 0.1);
+	}
+}
@@ -0,0 +1,427 @@
+use std::f64::consts::PI;
+
+use crate::{
+	error::SignalProcessError,
+	types::{RemezBand, RemezDesignConfig},
+};
+
+/// 每个余弦基函数对应的频率网格点数。
+const GRID_DENSITY: usize = 16;
+
+/// 最大误差与交错误差 |δ| 的相对差小于该值时认为收敛。
+const CONVERGENCE_TOLERANCE: f64 = 1e-6;
+
+/// 频率网格上的一个点，频率按采样率归一化到 [0, 0.5]。
+#[derive(Debug, Clone, Copy)]
+struct GridPoint {
+	frequency: f64,
+	desired: f64,
+	weight: f64,
+	band_index: usize,
+}
+
+/// 在一组极值点上求得的交错多项式。
+struct ExtremalFit {
+	nodes: Vec<f64>,
+	values: Vec<f64>,
+	weights: Vec<f64>,
+	delta: f64,
+}
+
+impl ExtremalFit {
+	/// 以重心 Lagrange 插值计算 x = cos(ω) 处的幅度响应。
+	fn evaluate(&self, x: f64) -> f64 {
+		let mut numerator = 0.0;
+		let mut denominator = 0.0;
+		for ((node, value), weight) in self
+			.nodes
+			.iter()
+			.zip(self.values.iter())
+			.zip(self.weights.iter())
+		{
+			let difference = x - node;
+			if difference.abs() < 1e-14 {
+				return *value;
+			}
+			let term = weight / difference;
+			numerator += term * value;
+			denominator += term;
+		}
+		numerator / denominator
+	}
+}
+
+/// 设计 I 型线性相位等波纹 FIR 系数。
+///
+/// 采用 Parks, T. W. & McClellan, J. H. 1972 的 Remez 交换算法，
+/// 在各频带内使加权误差的最大值最小。
+pub fn design_fir_remez(config: &RemezDesignConfig) -> Result<Vec<f32>, SignalProcessError> {
+	validate_remez_config(config)?;
+
+	let basis_count = config.tap_count.div_ceil(2);
+	let grid = build_grid(config, basis_count);
+	if grid.len() <= basis_count {
+		return Err(SignalProcessError::InvalidArgument(
+			"频带过窄，网格点数不足".to_string(),
+		));
+	}
+
+	let mut extremal: Vec<usize> = (0..=basis_count)
+		.map(|index| index * (grid.len() - 1) / basis_count)
+		.collect();
+	let mut converged_fit = None;
+	for _ in 0..config.max_iterations {
+		let fit = fit_extremal_set(&grid, &extremal);
+		let errors: Vec<f64> = grid
+			.iter()
+			.map(|point| {
+				point.weight * (point.desired - fit.evaluate((2.0 * PI * point.frequency).cos()))
+			})
+			.collect();
+
+		extremal = find_extremal_set(&grid, &errors, basis_count + 1)?;
+		let max_error = extremal
+			.iter()
+			.map(|index| errors[*index].abs())
+			.fold(0.0, f64::max);
+		if max_error - fit.delta.abs() <= CONVERGENCE_TOLERANCE * fit.delta.abs().max(f64::EPSILON)
+		{
+			converged_fit = Some(fit);
+			break;
+		}
+	}
+
+	let fit = converged_fit.ok_or_else(|| {
+		SignalProcessError::InvalidArgument(format!(
+			"Remez 迭代在 {} 次内未收敛",
+			config.max_iterations
+		))
+	})?;
+	Ok(impulse_response(&fit, config.tap_count))
+}
+
+/// 在各频带内按等间距构造频率网格。
+fn build_grid(config: &RemezDesignConfig, basis_count: usize) -> Vec<GridPoint> {
+	let spacing = 0.5 / (GRID_DENSITY * basis_count) as f64;
+	let sample_rate = config.sample_rate as f64;
+	let mut grid = Vec::new();
+	for (band_index, band) in config.bands.iter().enumerate() {
+		let start = band.start_hz as f64 / sample_rate;
+		let end = band.end_hz as f64 / sample_rate;
+		let intervals = ((end - start) / spacing).ceil().max(1.0) as usize;
+		grid.extend((0..=intervals).map(|index| GridPoint {
+			frequency: start + (end - start) * index as f64 / intervals as f64,
+			desired: band.desired_gain as f64,
+			weight: band.weight as f64,
+			band_index,
+		}));
+	}
+	grid
+}
+
+/// 求解极值点上误差等幅交错的多项式。
+fn fit_extremal_set(grid: &[GridPoint], extremal: &[usize]) -> ExtremalFit {
+	let nodes: Vec<f64> = extremal
+		.iter()
+		.map(|index| (2.0 * PI * grid[*index].frequency).cos())
+		.collect();
+	let barycentric = barycentric_weights(&nodes);
+
+	let mut numerator = 0.0;
+	let mut denominator = 0.0;
+	for (index, (point_index, weight)) in extremal.iter().zip(barycentric.iter()).enumerate() {
+		let point = grid[*point_index];
+		let sign = if index % 2 == 0 { 1.0 } else { -1.0 };
+		numerator += weight * point.desired;
+		denominator += sign * weight / point.weight;
+	}
+	let delta = numerator / denominator;
+
+	// 插值只需前 r 个点，最后一个点的重心权重可由全部点的权重换算得到。
+	let last = nodes.len() - 1;
+	let values = extremal[..last]
+		.iter()
+		.enumerate()
+		.map(|(index, point_index)| {
+			let point = grid[*point_index];
+			let sign = if index % 2 == 0 { 1.0 } else { -1.0 };
+			point.desired - sign * delta / point.weight
+		})
+		.collect();
+	let weights = barycentric[..last]
+		.iter()
+		.zip(nodes[..last].iter())
+		.map(|(weight, node)| weight * (node - nodes[last]))
+		.collect();
+
+	ExtremalFit {
+		nodes: nodes[..last].to_vec(),
+		values,
+		weights,
+		delta,
+	}
+}
+
+/// 在对数域计算重心权重，避免大量连乘造成上溢或下溢；所有权重共享同一缩放因子。
+fn barycentric_weights(nodes: &[f64]) -> Vec<f64> {
+	let logs: Vec<(f64, f64)> = nodes
+		.iter()
+		.enumerate()
+		.map(|(index, node)| {
+			let mut log_sum = 0.0;
+			let mut sign = 1.0;
+			for (other_index, other) in nodes.iter().enumerate() {
+				if other_index != index {
+					let difference = node - other;
+					log_sum -= difference.abs().ln();
+					if difference < 0.0 {
+						sign = -sign;
+					}
+				}
+			}
+			(log_sum, sign)
+		})
+		.collect();
+	let shift = logs
+		.iter()
+		.map(|(log_sum, _)| *log_sum)
+		.fold(f64::NEG_INFINITY, f64::max);
+	logs.into_iter()
+		.map(|(log_sum, sign)| sign * (log_sum - shift).exp())
+		.collect()
+}
+
+/// 从加权误差中选出新的交错极值点集。
+fn find_extremal_set(
+	grid: &[GridPoint],
+	errors: &[f64],
+	count: usize,
+) -> Result<Vec<usize>, SignalProcessError> {
+	let is_extremum = |index: usize| {
+		let error = errors[index];
+		let dominates = |neighbor: usize| {
+			grid[neighbor].band_index != grid[index].band_index
+				|| if error >= 0.0 {
+					error >= errors[neighbor]
+				} else {
+					error <= errors[neighbor]
+				}
+		};
+		(index == 0 || dominates(index - 1)) && (index + 1 == grid.len() || dominates(index + 1))
+	};
+
+	let mut candidates: Vec<usize> = Vec::new();
+	for index in (0..grid.len()).filter(|index| is_extremum(*index)) {
+		match candidates.last().copied() {
+			Some(last) if errors[last].signum() == errors[index].signum() => {
+				if errors[index].abs() > errors[last].abs()
+					&& let Some(slot) = candidates.last_mut()
+				{
+					*slot = index;
+				}
+			}
+			_ => candidates.push(index),
+		}
+	}
+
+	while candidates.len() > count {
+		let last = candidates.len() - 1;
+		if candidates.len() == count + 1 {
+			let remove = if errors[candidates[0]].abs() < errors[candidates[last]].abs() {
+				0
+			} else {
+				last
+			};
+			candidates.remove(remove);
+			continue;
+		}
+
+		let smallest = (0..candidates.len())
+			.min_by(|left, right| {
+				errors[candidates[*left]]
+					.abs()
+					.total_cmp(&errors[candidates[*right]].abs())
+			})
+			.unwrap_or(0);
+		if smallest == 0 || smallest == last {
+			candidates.remove(smallest);
+		} else {
+			// 去掉内部点后两侧邻点同号，只保留其中误差较大的一个以维持交错。
+			let neighbor = if errors[candidates[smallest - 1]].abs()
+				< errors[candidates[smallest + 1]].abs()
+			{
+				smallest - 1
+			} else {
+				smallest + 1
+			};
+			candidates.remove(smallest.max(neighbor));
+			candidates.remove(smallest.min(neighbor));
+		}
+	}
+
+	if candidates.len() < count {
+		return Err(SignalProcessError::InvalidArgument(
+			"Remez 迭代未能找到足够的交错极值点".to_string(),
+		));
+	}
+	Ok(candidates)
+}
+
+/// 由幅度响应在 N 个等间隔频点上的取值反求对称冲激响应。
+fn impulse_response(fit: &ExtremalFit, tap_count: usize) -> Vec<f32> {
+	let half = (tap_count - 1) / 2;
+	let amplitudes: Vec<f64> = (0..=half)
+		.map(|index| fit.evaluate((2.0 * PI * index as f64 / tap_count as f64).cos()))
+		.collect();
+
+	(0..tap_count)
+		.map(|tap_index| {
+			let offset = tap_index as f64 - half as f64;
+			let sum = amplitudes[1..]
+				.iter()
+				.enumerate()
+				.map(|(index, amplitude)| {
+					2.0 * amplitude
+						* (2.0 * PI * (index + 1) as f64 * offset / tap_count as f64).cos()
+				})
+				.sum::<f64>();
+			((amplitudes[0] + sum) / tap_count as f64) as f32
+		})
+		.collect()
+}
+
+fn validate_remez_config(config: &RemezDesignConfig) -> Result<(), SignalProcessError> {
+	if config.sample_rate <= 0.0 {
+		return Err(SignalProcessError::InvalidArgument(
+			"采样率必须大于 0".to_string(),
+		));
+	}
+	if config.tap_count < 3 || config.tap_count.is_multiple_of(2) {
+		return Err(SignalProcessError::InvalidArgument(
+			"等波纹 FIR 的 tap 数必须为不小于 3 的奇数".to_string(),
+		));
+	}
+	if config.max_iterations == 0 {
+		return Err(SignalProcessError::InvalidArgument(
+			"最大迭代次数必须大于 0".to_string(),
+		));
+	}
+	if config.bands.is_empty() {
+		return Err(SignalProcessError::InvalidArgument(
+			"至少需要一个频带".to_string(),
+		));
+	}
+
+	let nyquist = config.sample_rate / 2.0;
+	let mut previous_end: Option<f32> = None;
+	for band in &config.bands {
+		validate_band(band, nyquist)?;
+		if previous_end.is_some_and(|end| band.start_hz <= end) {
+			return Err(SignalProcessError::InvalidArgument(
+				"频带必须按频率升序排列且互不重叠".to_string(),
+			));
+		}
+		previous_end = Some(band.end_hz);
+	}
+	Ok(())
+}
+
+fn validate_band(band: &RemezBand, nyquist: f32) -> Result<(), SignalProcessError> {
+	if !(0.0 <= band.start_hz && band.start_hz < band.end_hz && band.end_hz <= nyquist) {
+		return Err(SignalProcessError::InvalidArgument(
+			"频带边缘必须满足 0 <= start < end <= Nyquist".to_string(),
+		));
+	}
+	if band.weight <= 0.0 {
+		return Err(SignalProcessError::InvalidArgument(
+			"频带权重必须大于 0".to_string(),
+		));
+	}
+	Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn magnitude_at(coeffs: &[f32], frequency: f64, sample_rate: f64) -> f64 {
+		let omega = 2.0 * PI * frequency / sample_rate;
+		let (real, imag) =
+			coeffs
+				.iter()
+				.enumerate()
+				.fold((0.0, 0.0), |(real, imag), (index, coeff)| {
+					let phase = omega * index as f64;
+					(
+						real + *coeff as f64 * phase.cos(),
+						imag - *coeff as f64 * phase.sin(),
+					)
+				});
+		(real * real + imag * imag).sqrt()
+	}
+
+	fn band_deviation(coeffs: &[f32], start: f64, end: f64, desired: f64) -> f64 {
+		(0..=200)
+			.map(|index| {
+				let frequency = start + (end - start) * index as f64 / 200.0;
+				(magnitude_at(coeffs, frequency, 1000.0) - desired).abs()
+			})
+			.fold(0.0, f64::max)
+	}
+
+	fn lowpass_config(stopband_weight: f32) -> RemezDesignConfig {
+		RemezDesignConfig {
+			sample_rate: 1000.0,
+			tap_count: 41,
+			bands: vec![
+				RemezBand {
+					start_hz: 0.0,
+					end_hz: 100.0,
+					desired_gain: 1.0,
+					weight: 1.0,
+				},
+				RemezBand {
+					start_hz: 150.0,
+					end_hz: 500.0,
+					desired_gain: 0.0,
+					weight: stopband_weight,
+				},
+			],
+			max_iterations: 40,
+		}
+	}
+
+	#[test]
+	fn remez_lowpass_should_be_symmetric_and_equiripple() {
+		let coeffs = design_fir_remez(&lowpass_config(1.0))
+			.unwrap_or_else(|error| panic!("等波纹 FIR 设计失败: {error}"));
+
+		assert_eq!(coeffs.len(), 41);
+		for index in 0..20 {
+			assert!((coeffs[index] - coeffs[40 - index]).abs() < 1e-6);
+		}
+
+		let passband = band_deviation(&coeffs, 0.0, 100.0, 1.0);
+		let stopband = band_deviation(&coeffs, 150.0, 500.0, 0.0);
+		assert!(passband < 0.02);
+		assert!((passband - stopband).abs() / passband < 0.05);
+	}
+
+	#[test]
+	fn remez_weights_should_trade_ripple_between_bands() {
+		let coeffs = design_fir_remez(&lowpass_config(10.0))
+			.unwrap_or_else(|error| panic!("等波纹 FIR 设计失败: {error}"));
+
+		let passband = band_deviation(&coeffs, 0.0, 100.0, 1.0);
+		let stopband = band_deviation(&coeffs, 150.0, 500.0, 0.0);
+		assert!((passband / stopband - 10.0).abs() < 0.5);
+	}
+
+	#[test]
+	fn remez_should_reject_overlapping_bands() {
+		let mut config = lowpass_config(1.0);
+		config.bands[1].start_hz = 90.0;
+
+		assert!(design_fir_remez(&config).is_err());
+	}
+}
@@ -0,0 +1,363 @@
+use crate::{
+	error::SignalProcessError,
+	fir::design_fir,
+	multichannel::MultiChannelSignal,
+	types::{ChannelInfo, FilterKind, FirDesignConfig, SignalBuffer, WindowKind},
+};
+
+/// 抗混叠 FIR 每侧的 tap 数相对 max(L, M) 的倍数。
+const HALF_LENGTH_PER_FACTOR: usize = 10;
+
+/// 抗混叠 FIR 使用的 Kaiser 窗 β。
+const ANTI_ALIAS_KAISER_BETA: f32 = 5.0;
+
+/// 按采样率换算 L/M 时保留的小数精度。
+const RATE_SCALE: f64 = 1000.0;
+
+/// 允许的最大 L 或 M，避免抗混叠 FIR 过长。
+pub const MAX_RESAMPLE_FACTOR: usize = 4096;
+
+/// 有理数倍率 L/M 的多相重采样器，可分块流式处理。
+///
+/// 等价于先插入 L-1 个零、经抗混叠 FIR 低通后每 M 个样本取一个，
+/// 但只计算被保留下来的输出，每个输出只用到 FIR 的一个相位分量。
+#[derive(Debug, Clone)]
+pub struct PolyphaseResampler {
+	up: usize,
+	down: usize,
+	taps: Vec<f32>,
+	/// 环形延迟线，`history[position]` 为最近一次输入。
+	history: Vec<f32>,
+	position: usize,
+	input_count: u64,
+	output_count: u64,
+}
+
+impl PolyphaseResampler {
+	/// 以默认 Kaiser 窗抗混叠 FIR 创建 L/M 重采样器，L 与 M 会先约分。
+	pub fn new(up: usize, down: usize) -> Result<Self, SignalProcessError> {
+		let (up, down) = reduce_factors(up, down)?;
+		Self::with_taps(up, down, design_anti_alias_taps(up, down)?)
+	}
+
+	/// 以自定义 FIR 创建重采样器，系数按上采样后的速率设计且已包含 L 倍增益。
+	pub fn with_taps(up: usize, down: usize, taps: Vec<f32>) -> Result<Self, SignalProcessError> {
+		if up == 0 || down == 0 {
+			return Err(SignalProcessError::InvalidArgument(
+				"重采样倍率 L 与 M 必须大于 0".to_string(),
+			));
+		}
+		if taps.is_empty() {
+			return Err(SignalProcessError::InvalidArgument(
+				"重采样 FIR 系数不能为空".to_string(),
+			));
+		}
+
+		let history_length = taps.len().div_ceil(up);
+		Ok(Self {
+			up,
+			down,
+			taps,
+			history: vec![0.0; history_length],
+			position: 0,
+			input_count: 0,
+			output_count: 0,
+		})
+	}
+
+	pub fn up(&self) -> usize {
+		self.up
+	}
+
+	pub fn down(&self) -> usize {
+		self.down
+	}
+
+	pub fn taps(&self) -> &[f32] {
+		&self.taps
+	}
+
+	/// 处理一块输入，返回这一块新产生的输出样本。
+	pub fn process(&mut self, chunk: &[f32]) -> Vec<f32> {
+		let mut output = Vec::with_capacity(chunk.len() * self.up / self.down + 1);
+		let up = self.up as u64;
+		let down = self.down as u64;
+
+		let history_length = self.history.len();
+
+		for sample in chunk.iter().copied() {
+			self.position = if self.position == 0 {
+				history_length - 1
+			} else {
+				self.position - 1
+			};
+			self.history[self.position] = sample;
+			self.input_count += 1;
+
+			// 第 m 个输出对应上采样序列中的 m·M，只要它落在当前输入之前就可以输出。
+			while self.output_count * down < self.input_count * up {
+				let phase = ((self.output_count * down) % up) as usize;
+				let mut value = 0.0;
+				let mut history_index = self.position;
+				for tap in self.taps.iter().skip(phase).step_by(self.up) {
+					value += tap * self.history[history_index];
+					history_index += 1;
+					if history_index == history_length {
+						history_index = 0;
+					}
+				}
+				output.push(value);
+				self.output_count += 1;
+			}
+		}
+		output
+	}
+
+	/// 清空历史样本与计数。
+	pub fn reset(&mut self) {
+		self.history.fill(0.0);
+		self.position = 0;
+		self.input_count = 0;
+		self.output_count = 0;
+	}
+}
+
+/// 以 L/M 倍率重采样整段信号，并补偿抗混叠 FIR 的群延迟。
+///
+/// 输出长度为 ceil(N·L/M)，采样率为原采样率乘以 L/M。
+pub fn resample_poly(
+	signal: &SignalBuffer,
+	up: usize,
+	down: usize,
+) -> Result<SignalBuffer, SignalProcessError> {
+	if signal.samples.is_empty() {
+		return Err(SignalProcessError::EmptyInput);
+	}
+	if signal.sample_rate <= 0.0 {
+		return Err(SignalProcessError::InvalidArgument(
+			"采样率必须大于 0".to_string(),
+		));
+	}
+
+	let (up, down) = reduce_factors(up, down)?;
+	let sample_rate = signal.sample_rate * up as f32 / down as f32;
+	if up == down {
+		return Ok(SignalBuffer {
+			sample_rate,
+			samples: signal.samples.clone(),
+		});
+	}
+
+	// 在 FIR 前补零，使群延迟 (上采样速率下) 恰好是 M 的整数倍，便于整样本丢弃。
+	let taps = design_anti_alias_taps(up, down)?;
+	let half_length = (taps.len() - 1) / 2;
+	let pre_pad = down - half_length % down;
+	let leading_outputs = (half_length + pre_pad) / down;
+	let padded_taps = std::iter::repeat_n(0.0, pre_pad)
+		.chain(taps)
+		.collect::<Vec<_>>();
+
+	let output_length = (signal.samples.len() * up).div_ceil(down);
+	let mut resampler = PolyphaseResampler::with_taps(up, down, padded_taps)?;
+	let mut samples = resampler.process(&signal.samples);
+	while samples.len() < leading_outputs + output_length {
+		samples.extend(resampler.process(&vec![0.0; resampler.history.len()]));
+	}
+
+	Ok(SignalBuffer {
+		sample_rate,
+		samples: samples
+			.into_iter()
+			.skip(leading_outputs)
+			.take(output_length)
+			.collect(),
+	})
+}
+
+/// 重采样到目标采样率，L/M 由两个采样率按 0.001 Hz 精度约分得到。
+pub fn resample_to_rate(
+	signal: &SignalBuffer,
+	target_rate: f32,
+) -> Result<SignalBuffer, SignalProcessError> {
+	let (up, down) = rate_factors(signal.sample_rate, target_rate)?;
+	let mut output = resample_poly(signal, up, down)?;
+	output.sample_rate = target_rate;
+	Ok(output)
+}
+
+/// 抗混叠低通后按整数倍抽取。
+pub fn decimate(signal: &SignalBuffer, factor: usize) -> Result<SignalBuffer, SignalProcessError> {
+	resample_poly(signal, 1, factor)
+}
+
+/// 按整数倍插值并低通去除镜像频率。
+pub fn interpolate(
+	signal: &SignalBuffer,
+	factor: usize,
+) -> Result<SignalBuffer, SignalProcessError> {
+	resample_poly(signal, factor, 1)
+}
+
+/// 将多通道信号的每个通道重采样到同一采样率。
+pub fn resample_channels(
+	signal: &MultiChannelSignal,
+	target_rate: f32,
+) -> Result<MultiChannelSignal, SignalProcessError> {
+	let mut infos = Vec::with_capacity(signal.channel_count());
+	let mut channels = Vec::with_capacity(signal.channel_count());
+	for channel in signal.channels() {
+		let resampled = resample_to_rate(&channel.to_signal_buffer(), target_rate)?;
+		infos.push(ChannelInfo {
+			sample_rate: target_rate,
+			..channel.info.clone()
+		});
+		channels.push(resampled.samples);
+	}
+	MultiChannelSignal::new(infos, channels, signal.start_time_secs())
+}
+
+/// 设计 Kaiser 窗抗混叠 FIR，截止频率取输入与输出 Nyquist 中较小者。
+fn design_anti_alias_taps(up: usize, down: usize) -> Result<Vec<f32>, SignalProcessError> {
+	let max_factor = up.max(down);
+	let upsampled_rate = up as f32;
+	let mut taps = design_fir(&FirDesignConfig {
+		sample_rate: upsampled_rate,
+		filter_kind: FilterKind::LowPass,
+		cutoff_hz: vec![0.5 * upsampled_rate / max_factor as f32],
+		tap_count: 2 * HALF_LENGTH_PER_FACTOR * max_factor + 1,
+		window: WindowKind::Kaiser {
+			beta: ANTI_ALIAS_KAISER_BETA,
+		},
+	})?;
+	for tap in taps.iter_mut() {
+		*tap *= up as f32;
+	}
+	Ok(taps)
+}
+
+fn reduce_factors(up: usize, down: usize) -> Result<(usize, usize), SignalProcessError> {
+	if up == 0 || down == 0 {
+		return Err(SignalProcessError::InvalidArgument(
+			"重采样倍率 L 与 M 必须大于 0".to_string(),
+		));
+	}
+
+	let divisor = gcd(up as u64, down as u64) as usize;
+	let (up, down) = (up / divisor, down / divisor);
+	if up.max(down) > MAX_RESAMPLE_FACTOR {
+		return Err(SignalProcessError::InvalidArgument(format!(
+			"约分后的重采样倍率 {up}/{down} 超过上限 {MAX_RESAMPLE_FACTOR}"
+		)));
+	}
+	Ok((up, down))
+}
+
+fn rate_factors(source_rate: f32, target_rate: f32) -> Result<(usize, usize), SignalProcessError> {
+	if source_rate <= 0.0 || target_rate <= 0.0 {
+		return Err(SignalProcessError::InvalidArgument(
+			"采样率必须大于 0".to_string(),
+		));
+	}
+
+	let source = (source_rate as f64 * RATE_SCALE).round() as u64;
+	let target = (target_rate as f64 * RATE_SCALE).round() as u64;
+	let divisor = gcd(source, target);
+	reduce_factors((target / divisor) as usize, (source / divisor) as usize)
+}
+
+fn gcd(mut left: u64, mut right: u64) -> u64 {
+	while right != 0 {
+		(left, right) = (right, left % right);
+	}
+	left
+}
+
+#[cfg(test)]
+mod tests {
+	use std::f32::consts::TAU;
+
+	use crate::{CompositeComponent, generate_composite_signal};
+
+	use super::*;
+
+	fn sine(sample_rate: f32, frequency: f32, sample_count: usize) -> SignalBuffer {
+		SignalBuffer {
+			sample_rate,
+			samples: (0..sample_count)
+				.map(|index| (TAU * frequency * index as f32 / sample_rate).sin())
+				.collect(),
+		}
+	}
+
+	/// 去掉首尾过渡段后与理想正弦比较的最大误差。
+	fn max_error_against_sine(signal: &SignalBuffer, frequency: f32, margin: usize) -> f32 {
+		signal
+			.samples
+			.iter()
+			.enumerate()
+			.skip(margin)
+			.take(signal.samples.len() - 2 * margin)
+			.map(|(index, value)| {
+				(value - (TAU * frequency * index as f32 / signal.sample_rate).sin()).abs()
+			})
+			.fold(0.0, f32::max)
+	}
+
+	#[test]
+	fn rational_resample_should_preserve_in_band_sine() {
+		let input = sine(441.0, 12.0, 1764);
+
+		let output =
+			resample_to_rate(&input, 160.0).unwrap_or_else(|error| panic!("重采样失败: {error}"));
+
+		assert_eq!(output.samples.len(), (1764usize * 160).div_ceil(441));
+		assert!((output.sample_rate - 160.0).abs() < 1e-6);
+		assert!(max_error_against_sine(&output, 12.0, 40) < 1e-2);
+	}
+
+	#[test]
+	fn decimate_should_suppress_aliasing_component() {
+		let input = generate_composite_signal(
+			256.0,
+			4.0,
+			&[
+				CompositeComponent {
+					frequency: 10.0,
+					amplitude: 1.0,
+					phase: 0.0,
+				},
+				CompositeComponent {
+					frequency: 100.0,
+					amplitude: 1.0,
+					phase: 0.0,
+				},
+			],
+		)
+		.unwrap_or_else(|error| panic!("复合信号生成失败: {error}"));
+
+		let output = decimate(&input, 4).unwrap_or_else(|error| panic!("抽取失败: {error}"));
+		let upsampled = interpolate(&output, 2).unwrap_or_else(|error| panic!("插值失败: {error}"));
+
+		assert!((output.sample_rate - 64.0).abs() < 1e-6);
+		assert!(max_error_against_sine(&output, 10.0, 16) < 2e-2);
+		assert!(max_error_against_sine(&upsampled, 10.0, 32) < 3e-2);
+	}
+
+	#[test]
+	fn streaming_chunks_should_match_single_pass() {
+		let input = sine(100.0, 7.0, 300);
+		let mut whole = PolyphaseResampler::new(3, 2)
+			.unwrap_or_else(|error| panic!("重采样器创建失败: {error}"));
+		let mut chunked = whole.clone();
+
+		let expected = whole.process(&input.samples);
+		let actual = input
+			.samples
+			.chunks(37)
+			.flat_map(|chunk| chunked.process(chunk))
+			.collect::<Vec<_>>();
+
+		assert_eq!(expected.len(), 450);
+		assert_eq!(actual, expected);
+	}
+}
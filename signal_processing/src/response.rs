@@ -0,0 +1,255 @@
+use std::f64::consts::PI;
+
+use rustfft::num_complex::Complex64;
+
+use crate::{
+	error::SignalProcessError,
+	types::{FrequencyResponsePoint, IirCoefficients, SosCoefficients},
+};
+
+/// 判定 IIR 稳定时允许的最大极点半径。
+pub const STABILITY_RADIUS_LIMIT: f32 = 1.0 - 1e-6;
+
+/// 多项式在单个频点处的取值及其对群延迟的贡献。
+struct PolynomialResponse {
+	value: Complex64,
+	/// Σ k·c_k·e^{-jωk}，用于计算群延迟。
+	weighted: Complex64,
+}
+
+/// 计算 FIR 系数的频率响应，频点均匀分布在 [0, Nyquist) 内。
+pub fn freqz_fir(
+	coeffs: &[f32],
+	sample_rate: f32,
+	point_count: usize,
+) -> Result<Vec<FrequencyResponsePoint>, SignalProcessError> {
+	if coeffs.is_empty() {
+		return Err(SignalProcessError::InvalidArgument(
+			"FIR 系数不能为空".to_string(),
+		));
+	}
+	compute_response(sample_rate, point_count, |omega| {
+		vec![(
+			evaluate_polynomial(coeffs, omega),
+			evaluate_polynomial(&[1.0], omega),
+		)]
+	})
+}
+
+/// 计算二阶 IIR 的频率响应。
+pub fn freqz_iir(
+	coeffs: &IirCoefficients,
+	sample_rate: f32,
+	point_count: usize,
+) -> Result<Vec<FrequencyResponsePoint>, SignalProcessError> {
+	compute_response(sample_rate, point_count, |omega| {
+		vec![(
+			evaluate_polynomial(&coeffs.b, omega),
+			evaluate_polynomial(&coeffs.a, omega),
+		)]
+	})
+}
+
+/// 计算二阶节级联 IIR 的频率响应。
+pub fn freqz_sos(
+	sos: &SosCoefficients,
+	sample_rate: f32,
+	point_count: usize,
+) -> Result<Vec<FrequencyResponsePoint>, SignalProcessError> {
+	if sos.sections.is_empty() {
+		return Err(SignalProcessError::InvalidArgument(
+			"SOS 二阶节不能为空".to_string(),
+		));
+	}
+	compute_response(sample_rate, point_count, |omega| {
+		sos.sections
+			.iter()
+			.map(|section| {
+				(
+					evaluate_polynomial(&section.b, omega),
+					evaluate_polynomial(&section.a, omega),
+				)
+			})
+			.collect()
+	})
+}
+
+/// 二阶 IIR 的最大极点半径。
+pub fn pole_radius(coeffs: &IirCoefficients) -> f32 {
+	let a0 = coeffs.a[0] as f64;
+	let a1 = coeffs.a[1] as f64 / a0;
+	let a2 = coeffs.a[2] as f64 / a0;
+	let discriminant = a1 * a1 - 4.0 * a2;
+	let radius = if discriminant < 0.0 {
+		a2.abs().sqrt()
+	} else {
+		let root = discriminant.sqrt();
+		((-a1 + root).abs() / 2.0).max((-a1 - root).abs() / 2.0)
+	};
+	radius as f32
+}
+
+/// 校验二阶 IIR 的全部极点位于单位圆内。
+pub fn ensure_iir_stable(coeffs: &IirCoefficients) -> Result<(), SignalProcessError> {
+	if coeffs.a[0] == 0.0 {
+		return Err(SignalProcessError::InvalidArgument(
+			"IIR 分母首项不能为 0".to_string(),
+		));
+	}
+
+	let radius = pole_radius(coeffs);
+	if radius.is_nan() || radius >= STABILITY_RADIUS_LIMIT {
+		return Err(SignalProcessError::InvalidArgument(format!(
+			"IIR 不稳定: 极点半径 {radius} 不小于 1"
+		)));
+	}
+	Ok(())
+}
+
+/// 校验二阶节级联 IIR 每一节都稳定。
+pub fn ensure_sos_stable(sos: &SosCoefficients) -> Result<(), SignalProcessError> {
+	for section in &sos.sections {
+		ensure_iir_stable(section)?;
+	}
+	Ok(())
+}
+
+/// 按频点汇总各个 (分子, 分母) 对的响应。
+fn compute_response<F>(
+	sample_rate: f32,
+	point_count: usize,
+	evaluate: F,
+) -> Result<Vec<FrequencyResponsePoint>, SignalProcessError>
+where
+	F: Fn(f64) -> Vec<(PolynomialResponse, PolynomialResponse)>,
+{
+	if sample_rate <= 0.0 {
+		return Err(SignalProcessError::InvalidArgument(
+			"采样率必须大于 0".to_string(),
+		));
+	}
+	if point_count == 0 {
+		return Err(SignalProcessError::InvalidArgument(
+			"频点数必须大于 0".to_string(),
+		));
+	}
+
+	let mut points = Vec::with_capacity(point_count);
+	let mut previous_phase: Option<f64> = None;
+	let mut phase_offset = 0.0;
+	for index in 0..point_count {
+		let omega = PI * index as f64 / point_count as f64;
+		let mut response = Complex64::new(1.0, 0.0);
+		let mut group_delay = 0.0;
+		for (numerator, denominator) in evaluate(omega) {
+			response *= numerator.value / denominator.value;
+			group_delay +=
+				polynomial_group_delay(&numerator) - polynomial_group_delay(&denominator);
+		}
+
+		// 相邻频点相位跳变超过 π 时补偿 2π，得到展开后的相位。
+		let wrapped = response.arg();
+		if let Some(previous) = previous_phase {
+			let jump = wrapped - previous;
+			if jump > PI {
+				phase_offset -= 2.0 * PI;
+			} else if jump < -PI {
+				phase_offset += 2.0 * PI;
+			}
+		}
+		previous_phase = Some(wrapped);
+
+		points.push(FrequencyResponsePoint {
+			frequency: (omega / (2.0 * PI)) as f32 * sample_rate,
+			magnitude_db: (20.0 * response.norm().max(f64::MIN_POSITIVE).log10()) as f32,
+			phase_rad: (wrapped + phase_offset) as f32,
+			group_delay_samples: group_delay as f32,
+		});
+	}
+	Ok(points)
+}
+
+fn evaluate_polynomial(coeffs: &[f32], omega: f64) -> PolynomialResponse {
+	let mut value = Complex64::new(0.0, 0.0);
+	let mut weighted = Complex64::new(0.0, 0.0);
+	for (order, coeff) in coeffs.iter().enumerate() {
+		let term = Complex64::from_polar(*coeff as f64, -omega * order as f64);
+		value += term;
+		weighted += term * order as f64;
+	}
+	PolynomialResponse { value, weighted }
+}
+
+/// 多项式群延迟 Re(Σ k·c_k·z^{-k} / Σ c_k·z^{-k})，多项式在该频点为零时记为 0。
+fn polynomial_group_delay(response: &PolynomialResponse) -> f64 {
+	if response.value.norm() <= 1e-12 {
+		return 0.0;
+	}
+	(response.weighted / response.value).re
+}
+
+#[cfg(test)]
+mod tests {
+	use crate::{FilterKind, IirPrototype, SosDesignConfig, design_sos};
+
+	use super::*;
+
+	#[test]
+	fn symmetric_fir_should_have_constant_group_delay_and_linear_phase() {
+		let coeffs = [0.1, 0.2, 0.4, 0.2, 0.1];
+		let response =
+			freqz_fir(&coeffs, 100.0, 64).unwrap_or_else(|error| panic!("频率响应失败: {error}"));
+
+		assert_eq!(response.len(), 64);
+		assert!((response[0].magnitude_db - 0.0).abs() < 1e-4);
+		for point in response.iter().take(20) {
+			assert!((point.group_delay_samples - 2.0).abs() < 1e-4);
+			let expected_phase = -2.0 * std::f32::consts::TAU * point.frequency / 100.0;
+			assert!((point.phase_rad - expected_phase).abs() < 1e-3);
+		}
+	}
+
+	#[test]
+	fn sos_response_should_report_cutoff_attenuation() {
+		let sos = design_sos(&SosDesignConfig {
+			sample_rate: 256.0,
+			filter_kind: FilterKind::LowPass,
+			cutoff_hz: [32.0, 0.0],
+			order: 4,
+			prototype: IirPrototype::Butterworth,
+		})
+		.unwrap_or_else(|error| panic!("SOS 设计失败: {error}"));
+
+		let response =
+			freqz_sos(&sos, 256.0, 128).unwrap_or_else(|error| panic!("频率响应失败: {error}"));
+		let cutoff = response
+			.iter()
+			.find(|point| (point.frequency - 32.0).abs() < 1e-3)
+			.unwrap_or_else(|| panic!("缺少 32 Hz 频点"));
+
+		assert!((cutoff.magnitude_db + 3.0103).abs() < 1e-2);
+		assert!(response.iter().all(|point| point.group_delay_samples > 0.0));
+		assert!(
+			response
+				.windows(2)
+				.all(|pair| pair[1].phase_rad <= pair[0].phase_rad + 1e-4)
+		);
+	}
+
+	#[test]
+	fn stability_check_should_reject_poles_outside_unit_circle() {
+		let stable = IirCoefficients {
+			b: [1.0, 0.0, 0.0],
+			a: [1.0, -1.0, 0.5],
+		};
+		let unstable = IirCoefficients {
+			b: [1.0, 0.0, 0.0],
+			a: [1.0, -2.5, 1.0],
+		};
+
+		assert!((pole_radius(&stable) - 0.5_f32.sqrt()).abs() < 1e-6);
+		assert!(ensure_iir_stable(&stable).is_ok());
+		assert!((pole_radius(&unstable) - 2.0).abs() < 1e-6);
+		assert!(ensure_iir_stable(&unstable).is_err());
+	}
+}
@@ -0,0 +1,782 @@
+use std::f64::consts::PI;
+
+use rustfft::num_complex::Complex64;
+
+use crate::{
+	error::SignalProcessError,
+	response::ensure_sos_stable,
+	types::{FilterKind, IirCoefficients, IirPrototype, SosCoefficients, SosDesignConfig},
+};
+
+/// 判断零极点是否为实数的相对容差。
+const REAL_TOLERANCE: f64 = 1e-9;
+
+/// Landen 变换的最大迭代次数。
+const LANDEN_MAX_ITERATIONS: usize = 64;
+
+/// 零极点增益表示。
+#[derive(Debug, Clone)]
+struct Zpk {
+	zeros: Vec<Complex64>,
+	poles: Vec<Complex64>,
+	gain: f64,
+}
+
+/// 设计高阶 IIR，输出二阶节级联系数。
+///
+/// 先构造归一化模拟原型，再做频率变换，最后经预畸变双线性变换得到数字滤波器，
+/// 流程与 scipy.signal.iirfilter 一致。
+pub fn design_sos(config: &SosDesignConfig) -> Result<SosCoefficients, SignalProcessError> {
+	validate_sos_config(config)?;
+
+	let prototype = analog_prototype(config.order, config.prototype)?;
+	let sample_rate = config.sample_rate as f64;
+	let warp = |frequency: f32| 2.0 * sample_rate * (PI * frequency as f64 / sample_rate).tan();
+	let analog = match config.filter_kind {
+		FilterKind::LowPass => lowpass_to_lowpass(prototype, warp(config.cutoff_hz[0])),
+		FilterKind::HighPass => lowpass_to_highpass(prototype, warp(config.cutoff_hz[0])),
+		FilterKind::BandPass => {
+			let low = warp(config.cutoff_hz[0]);
+			let high = warp(config.cutoff_hz[1]);
+			lowpass_to_bandpass(prototype, (low * high).sqrt(), high - low)
+		}
+		FilterKind::BandStop => {
+			let low = warp(config.cutoff_hz[0]);
+			let high = warp(config.cutoff_hz[1]);
+			lowpass_to_bandstop(prototype, (low * high).sqrt(), high - low)
+		}
+	};
+
+	let sos = zpk_to_sos(&bilinear(analog, sample_rate))?;
+	ensure_sos_stable(&sos)?;
+	Ok(sos)
+}
+
+/// 构造通带边缘（Chebyshev II 为阻带边缘）归一化到 1 rad/s 的模拟低通原型。
+fn analog_prototype(order: usize, prototype: IirPrototype) -> Result<Zpk, SignalProcessError> {
+	match prototype {
+		IirPrototype::Butterworth => Ok(butterworth_prototype(order)),
+		IirPrototype::ChebyshevI { passband_ripple_db } => {
+			Ok(chebyshev1_prototype(order, passband_ripple_db as f64))
+		}
+		IirPrototype::ChebyshevII {
+			stopband_attenuation_db,
+		} => Ok(chebyshev2_prototype(order, stopband_attenuation_db as f64)),
+		IirPrototype::Elliptic {
+			passband_ripple_db,
+			stopband_attenuation_db,
+		} => elliptic_prototype(
+			order,
+			passband_ripple_db as f64,
+			stopband_attenuation_db as f64,
+		),
+	}
+}
+
+fn butterworth_prototype(order: usize) -> Zpk {
+	let poles = symmetric_indices(order)
+		.map(|m| -Complex64::from_polar(1.0, PI * m / (2.0 * order as f64)))
+		.collect();
+	Zpk {
+		zeros: Vec::new(),
+		poles,
+		gain: 1.0,
+	}
+}
+
+fn chebyshev1_prototype(order: usize, passband_ripple_db: f64) -> Zpk {
+	let epsilon = (10f64.powf(0.1 * passband_ripple_db) - 1.0).sqrt();
+	let mu = (1.0 / epsilon).asinh() / order as f64;
+	let poles: Vec<Complex64> = symmetric_indices(order)
+		.map(|m| -(Complex64::new(mu, PI * m / (2.0 * order as f64))).sinh())
+		.collect();
+
+	let mut gain = product(poles.iter().map(|pole| -pole)).re;
+	if order.is_multiple_of(2) {
+		gain /= (1.0 + epsilon * epsilon).sqrt();
+	}
+	Zpk {
+		zeros: Vec::new(),
+		poles,
+		gain,
+	}
+}
+
+fn chebyshev2_prototype(order: usize, stopband_attenuation_db: f64) -> Zpk {
+	let delta = 1.0 / (10f64.powf(0.1 * stopband_attenuation_db) - 1.0).sqrt();
+	let mu = (1.0 / delta).asinh() / order as f64;
+
+	// 奇数阶时中间的零点位于无穷远处，需要跳过 m = 0。
+	let zeros: Vec<Complex64> = symmetric_indices(order)
+		.filter(|m| *m != 0.0)
+		.map(|m| Complex64::new(0.0, 1.0 / (PI * m / (2.0 * order as f64)).sin()))
+		.collect();
+	let poles: Vec<Complex64> = symmetric_indices(order)
+		.map(|m| {
+			let pole = -Complex64::from_polar(1.0, PI * m / (2.0 * order as f64));
+			let pole = Complex64::new(mu.sinh() * pole.re, mu.cosh() * pole.im);
+			1.0 / pole
+		})
+		.collect();
+
+	let gain =
+		(product(poles.iter().map(|pole| -pole)) / product(zeros.iter().map(|zero| -zero))).re;
+	Zpk { zeros, poles, gain }
+}
+
+/// 椭圆原型，采用 Orfanidis 基于 Landen 变换的零极点闭式解。
+///
+/// 参考文献：S. J. Orfanidis, Lecture Notes on Elliptic Filter Design, 2006.
+fn elliptic_prototype(
+	order: usize,
+	passband_ripple_db: f64,
+	stopband_attenuation_db: f64,
+) -> Result<Zpk, SignalProcessError> {
+	let passband_epsilon = (10f64.powf(0.1 * passband_ripple_db) - 1.0).sqrt();
+	let stopband_epsilon = (10f64.powf(0.1 * stopband_attenuation_db) - 1.0).sqrt();
+	let k1 = passband_epsilon / stopband_epsilon;
+	let k = elliptic_degree(order, k1);
+
+	let half = order / 2;
+	let j = Complex64::new(0.0, 1.0);
+	let v0 = -j * asne(j / passband_epsilon, k1) / order as f64;
+
+	let mut zeros = Vec::with_capacity(2 * half);
+	let mut poles = Vec::with_capacity(order);
+	for index in 1..=half {
+		let u = (2 * index - 1) as f64 / order as f64;
+		let zeta = cde(Complex64::new(u, 0.0), k);
+		let zero = j / (k * zeta);
+		let pole = j * cde(u - j * v0, k);
+		zeros.extend([zero, zero.conj()]);
+		poles.extend([pole, pole.conj()]);
+	}
+	if !order.is_multiple_of(2) {
+		let pole = j * sne(j * v0, k);
+		poles.push(Complex64::new(pole.re, 0.0));
+	}
+
+	let dc_gain = if order.is_multiple_of(2) {
+		1.0 / (1.0 + passband_epsilon * passband_epsilon).sqrt()
+	} else {
+		1.0
+	};
+	let gain = dc_gain
+		* (product(poles.iter().map(|pole| -pole)) / product(zeros.iter().map(|zero| -zero))).re;
+	if !gain.is_finite() {
+		return Err(SignalProcessError::InvalidArgument(
+			"椭圆滤波器原型设计失败".to_string(),
+		));
+	}
+	Ok(Zpk { zeros, poles, gain })
+}
+
+/// 求解椭圆滤波器的度方程，返回选择性模数 k。
+fn elliptic_degree(order: usize, k1: f64) -> f64 {
+	let k1_complement = (1.0 - k1 * k1).sqrt();
+	let product = (1..=order / 2)
+		.map(|index| {
+			let u = (2 * index - 1) as f64 / order as f64;
+			sne(Complex64::new(u, 0.0), k1_complement).re
+		})
+		.product::<f64>();
+	let k_complement = k1_complement.powi(order as i32) * product.powi(4);
+	(1.0 - k_complement * k_complement).sqrt()
+}
+
+/// 模数 k 的 Landen 降阶序列。
+fn landen(mut k: f64) -> Vec<f64> {
+	let mut sequence = Vec::new();
+	for _ in 0..LANDEN_MAX_ITERATIONS {
+		let k_complement = (1.0 - k * k).sqrt();
+		k = (1.0 - k_complement) / (1.0 + k_complement);
+		sequence.push(k);
+		if k <= f64::EPSILON {
+			break;
+		}
+	}
+	sequence
+}
+
+/// 第一类完全椭圆积分 K(k) 与 K'(k)。
+fn elliptic_k(k: f64) -> (f64, f64) {
+	let complete = |modulus: f64| {
+		landen(modulus)
+			.into_iter()
+			.map(|value| 1.0 + value)
+			.product::<f64>()
+			* PI / 2.0
+	};
+	(complete(k), complete((1.0 - k * k).sqrt()))
+}
+
+/// Jacobi cd 函数，自变量以 K 为单位。
+fn cde(u: Complex64, k: f64) -> Complex64 {
+	ascend_landen((u * PI / 2.0).cos(), k)
+}
+
+/// Jacobi sn 函数，自变量以 K 为单位。
+fn sne(u: Complex64, k: f64) -> Complex64 {
+	ascend_landen((u * PI / 2.0).sin(), k)
+}
+
+fn ascend_landen(mut w: Complex64, k: f64) -> Complex64 {
+	for value in landen(k).into_iter().rev() {
+		w = (1.0 + value) * w / (1.0 + value * w * w);
+	}
+	w
+}
+
+/// cd 的反函数，结果以 K 为单位。
+fn acde(mut w: Complex64, k: f64) -> Complex64 {
+	let sequence = landen(k);
+	let mut previous = k;
+	for value in sequence {
+		w = w / (1.0 + (1.0 - w * w * previous * previous).sqrt()) * 2.0 / (1.0 + value);
+		previous = value;
+	}
+
+	let u = w.acos() * 2.0 / PI;
+	let (complete, complete_complement) = elliptic_k(k);
+	let ratio = complete_complement / complete;
+	Complex64::new(
+		symmetric_remainder(u.re, 4.0),
+		symmetric_remainder(u.im, 2.0 * ratio),
+	)
+}
+
+/// sn 的反函数，结果以 K 为单位。
+fn asne(w: Complex64, k: f64) -> Complex64 {
+	1.0 - acde(w, k)
+}
+
+fn symmetric_remainder(value: f64, period: f64) -> f64 {
+	value - period * (value / period).round()
+}
+
+/// 生成 -N+1, -N+3, ..., N-1 的对称下标。
+fn symmetric_indices(order: usize) -> impl Iterator<Item = f64> {
+	(0..order).map(move |index| (2 * index) as f64 - order as f64 + 1.0)
+}
+
+fn product(values: impl Iterator<Item = Complex64>) -> Complex64 {
+	values.fold(Complex64::new(1.0, 0.0), |accumulator, value| {
+		accumulator * value
+	})
+}
+
+fn relative_degree(zpk: &Zpk) -> usize {
+	zpk.poles.len() - zpk.zeros.len()
+}
+
+fn lowpass_to_lowpass(zpk: Zpk, cutoff: f64) -> Zpk {
+	let degree = relative_degree(&zpk);
+	Zpk {
+		zeros: zpk.zeros.iter().map(|zero| zero * cutoff).collect(),
+		poles: zpk.poles.iter().map(|pole| pole * cutoff).collect(),
+		gain: zpk.gain * cutoff.powi(degree as i32),
+	}
+}
+
+fn lowpass_to_highpass(zpk: Zpk, cutoff: f64) -> Zpk {
+	let degree = relative_degree(&zpk);
+	let mut zeros: Vec<Complex64> = zpk.zeros.iter().map(|zero| cutoff / zero).collect();
+	zeros.extend(std::iter::repeat_n(Complex64::new(0.0, 0.0), degree));
+	let gain = zpk.gain
+		* (product(zpk.zeros.iter().map(|zero| -zero))
+			/ product(zpk.poles.iter().map(|pole| -pole)))
+		.re;
+	Zpk {
+		zeros,
+		poles: zpk.poles.iter().map(|pole| cutoff / pole).collect(),
+		gain,
+	}
+}
+
+fn lowpass_to_bandpass(zpk: Zpk, center: f64, bandwidth: f64) -> Zpk {
+	let degree = relative_degree(&zpk);
+	let mut zeros = split_roots(zpk.zeros.iter().map(|zero| zero * bandwidth / 2.0), center);
+	zeros.extend(std::iter::repeat_n(Complex64::new(0.0, 0.0), degree));
+	Zpk {
+		zeros,
+		poles: split_roots(zpk.poles.iter().map(|pole| pole * bandwidth / 2.0), center),
+		gain: zpk.gain * bandwidth.powi(degree as i32),
+	}
+}
+
+fn lowpass_to_bandstop(zpk: Zpk, center: f64, bandwidth: f64) -> Zpk {
+	let degree = relative_degree(&zpk);
+	let mut zeros = split_roots(zpk.zeros.iter().map(|zero| bandwidth / 2.0 / zero), center);
+	zeros.extend(std::iter::repeat_n(Complex64::new(0.0, center), degree));
+	zeros.extend(std::iter::repeat_n(Complex64::new(0.0, -center), degree));
+	let gain = zpk.gain
+		* (product(zpk.zeros.iter().map(|zero| -zero))
+			/ product(zpk.poles.iter().map(|pole| -pole)))
+		.re;
+	Zpk {
+		zeros,
+		poles: split_roots(zpk.poles.iter().map(|pole| bandwidth / 2.0 / pole), center),
+		gain,
+	}
+}
+
+/// 将每个根 r 映射为 r ± sqrt(r² - center²)。
+fn split_roots(roots: impl Iterator<Item = Complex64>, center: f64) -> Vec<Complex64> {
+	let roots: Vec<Complex64> = roots.collect();
+	let offsets: Vec<Complex64> = roots
+		.iter()
+		.map(|root| (root * root - center * center).sqrt())
+		.collect();
+	let mut split = Vec::with_capacity(roots.len() * 2);
+	split.extend(
+		roots
+			.iter()
+			.zip(offsets.iter())
+			.map(|(root, offset)| root + offset),
+	);
+	split.extend(
+		roots
+			.iter()
+			.zip(offsets.iter())
+			.map(|(root, offset)| root - offset),
+	);
+	split
+}
+
+fn bilinear(zpk: Zpk, sample_rate: f64) -> Zpk {
+	let degree = relative_degree(&zpk);
+	let double_rate = 2.0 * sample_rate;
+	let mut zeros: Vec<Complex64> = zpk
+		.zeros
+		.iter()
+		.map(|zero| (double_rate + zero) / (double_rate - zero))
+		.collect();
+	zeros.extend(std::iter::repeat_n(Complex64::new(-1.0, 0.0), degree));
+	let gain = zpk.gain
+		* (product(zpk.zeros.iter().map(|zero| double_rate - zero))
+			/ product(zpk.poles.iter().map(|pole| double_rate - pole)))
+		.re;
+	Zpk {
+		zeros,
+		poles: zpk
+			.poles
+			.iter()
+			.map(|pole| (double_rate + pole) / (double_rate - pole))
+			.collect(),
+		gain,
+	}
+}
+
+/// 数字零极点分组为二阶节。
+///
+/// 每个二阶节包含一对共轭极点或两个实极点，并就近分配零点；
+/// 二阶节按极点模值升序排列，最靠近单位圆的放在最后，总增益并入第一节。
+fn zpk_to_sos(zpk: &Zpk) -> Result<SosCoefficients, SignalProcessError> {
+	if zpk.zeros.len() != zpk.poles.len() {
+		return Err(SignalProcessError::InvalidArgument(
+			"数字滤波器零点与极点数量不一致".to_string(),
+		));
+	}
+
+	let (mut real_poles, complex_poles) = split_conjugates(&zpk.poles);
+	let (mut real_zeros, mut complex_zeros) = split_conjugates(&zpk.zeros);
+	real_poles.sort_by(|left, right| left.abs().total_cmp(&right.abs()));
+
+	let mut pole_groups: Vec<Vec<Complex64>> = complex_poles
+		.iter()
+		.map(|pole| vec![*pole, pole.conj()])
+		.collect();
+	let single_pole = if real_poles.len() % 2 == 1 {
+		Some(real_poles.remove(0))
+	} else {
+		None
+	};
+	pole_groups.extend(
+		real_poles
+			.chunks(2)
+			.map(|pair| pair.iter().map(|pole| Complex64::new(*pole, 0.0)).collect()),
+	);
+	pole_groups.sort_by(|left, right| max_magnitude(right).total_cmp(&max_magnitude(left)));
+
+	let mut sections = Vec::with_capacity(pole_groups.len() + 1);
+	if let Some(pole) = single_pole {
+		let zero = take_nearest_real(&mut real_zeros, Complex64::new(pole, 0.0))
+			.ok_or_else(|| SignalProcessError::InvalidArgument("二阶节零点分配失败".to_string()))?;
+		sections.push((
+			vec![Complex64::new(zero, 0.0)],
+			vec![Complex64::new(pole, 0.0)],
+		));
+	}
+
+	for poles in pole_groups {
+		let reference = poles[0];
+		let complex_distance = nearest_index(&complex_zeros, reference)
+			.map(|index| (index, (complex_zeros[index] - reference).norm()));
+		let use_complex = match complex_distance {
+			Some((_, distance)) => {
+				real_zeros.len() < 2
+					|| real_zeros
+						.iter()
+						.all(|zero| (Complex64::new(*zero, 0.0) - reference).norm() >= distance)
+			}
+			None => false,
+		};
+
+		let zeros = if let (true, Some((index, _))) = (use_complex, complex_distance) {
+			let zero = complex_zeros.remove(index);
+			vec![zero, zero.conj()]
+		} else {
+			let first = take_nearest_real(&mut real_zeros, reference);
+			let second = take_nearest_real(&mut real_zeros, reference);
+			match (first, second) {
+				(Some(first), Some(second)) => {
+					vec![Complex64::new(first, 0.0), Complex64::new(second, 0.0)]
+				}
+				_ => {
+					return Err(SignalProcessError::InvalidArgument(
+						"二阶节零点分配失败".to_string(),
+					));
+				}
+			}
+		};
+		sections.push((zeros, poles));
+	}
+
+	sections.sort_by(|left, right| max_magnitude(&left.1).total_cmp(&max_magnitude(&right.1)));
+	let coefficients = sections
+		.iter()
+		.enumerate()
+		.map(|(index, (zeros, poles))| {
+			let gain = if index == 0 { zpk.gain } else { 1.0 };
+			let b = polynomial_from_roots(zeros);
+			let a = polynomial_from_roots(poles);
+			IirCoefficients {
+				b: [
+					(gain * b[0]) as f32,
+					(gain * b[1]) as f32,
+					(gain * b[2]) as f32,
+				],
+				a: [a[0] as f32, a[1] as f32, a[2] as f32],
+			}
+		})
+		.collect();
+
+	Ok(SosCoefficients {
+		sections: coefficients,
+	})
+}
+
+/// 拆分实根与共轭复根，复根只保留虚部为正的一半。
+fn split_conjugates(roots: &[Complex64]) -> (Vec<f64>, Vec<Complex64>) {
+	let mut real_roots = Vec::new();
+	let mut complex_roots = Vec::new();
+	for root in roots {
+		if root.im.abs() <= REAL_TOLERANCE * root.norm().max(1.0) {
+			real_roots.push(root.re);
+		} else if root.im > 0.0 {
+			complex_roots.push(*root);
+		}
+	}
+	(real_roots, complex_roots)
+}
+
+fn nearest_index(roots: &[Complex64], target: Complex64) -> Option<usize> {
+	roots
+		.iter()
+		.enumerate()
+		.min_by(|left, right| {
+			(left.1 - target)
+				.norm()
+				.total_cmp(&(right.1 - target).norm())
+		})
+		.map(|(index, _)| index)
+}
+
+fn take_nearest_real(roots: &mut Vec<f64>, target: Complex64) -> Option<f64> {
+	let candidates: Vec<Complex64> = roots
+		.iter()
+		.map(|root| Complex64::new(*root, 0.0))
+		.collect();
+	nearest_index(&candidates, target).map(|index| roots.remove(index))
+}
+
+fn max_magnitude(roots: &[Complex64]) -> f64 {
+	roots.iter().map(|root| root.norm()).fold(0.0, f64::max)
+}
+
+/// 由至多两个根展开为 [1, c1, c2] 形式的实系数多项式。
+fn polynomial_from_roots(roots: &[Complex64]) -> [f64; 3] {
+	match roots {
+		[] => [1.0, 0.0, 0.0],
+		[root] => [1.0, -root.re, 0.0],
+		[first, second, ..] => [1.0, -(first + second).re, (first * second).re],
+	}
+}
+
+fn validate_sos_config(config: &SosDesignConfig) -> Result<(), SignalProcessError> {
+	if config.sample_rate <= 0.0 {
+		return Err(SignalProcessError::InvalidArgument(
+			"采样率必须大于 0".to_string(),
+		));
+	}
+	if config.order == 0 {
+		return Err(SignalProcessError::InvalidArgument(
+			"IIR 阶数必须大于 0".to_string(),
+		));
+	}
+
+	match config.prototype {
+		IirPrototype::Butterworth => {}
+		IirPrototype::ChebyshevI { passband_ripple_db } => {
+			if passband_ripple_db <= 0.0 {
+				return Err(SignalProcessError::InvalidArgument(
+					"通带波纹必须大于 0 dB".to_string(),
+				));
+			}
+		}
+		IirPrototype::ChebyshevII {
+			stopband_attenuation_db,
+		} => {
+			if stopband_attenuation_db <= 0.0 {
+				return Err(SignalProcessError::InvalidArgument(
+					"阻带衰减必须大于 0 dB".to_string(),
+				));
+			}
+		}
+		IirPrototype::Elliptic {
+			passband_ripple_db,
+			stopband_attenuation_db,
+		} => {
+			if !(0.0 < passband_ripple_db && passband_ripple_db < stopband_attenuation_db) {
+				return Err(SignalProcessError::InvalidArgument(
+					"椭圆滤波器需要满足 0 < 通带波纹 < 阻带衰减".to_string(),
+				));
+			}
+		}
+	}
+
+	let nyquist = config.sample_rate / 2.0;
+	match config.filter_kind {
+		FilterKind::LowPass | FilterKind::HighPass => {
+			let cutoff = config.cutoff_hz[0];
+			if !(0.0 < cutoff && cutoff < nyquist) {
+				return Err(SignalProcessError::InvalidArgument(
+					"截止频率必须位于 (0, Nyquist) 内".to_string(),
+				));
+			}
+		}
+		FilterKind::BandPass | FilterKind::BandStop => {
+			if !(0.0 < config.cutoff_hz[0]
+				&& config.cutoff_hz[0] < config.cutoff_hz[1]
+				&& config.cutoff_hz[1] < nyquist)
+			{
+				return Err(SignalProcessError::InvalidArgument(
+					"两个截止频率必须满足 0 < low < high < Nyquist".to_string(),
+				));
+			}
+		}
+	}
+
+	Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	/// 计算 SOS 在给定频率处的幅度响应。
+	fn magnitude_at(sos: &SosCoefficients, frequency: f64, sample_rate: f64) -> f64 {
+		let z = Complex64::from_polar(1.0, -2.0 * PI * frequency / sample_rate);
+		sos.sections
+			.iter()
+			.map(|section| {
+				let numerator =
+					section.b[0] as f64 + section.b[1] as f64 * z + section.b[2] as f64 * z * z;
+				let denominator =
+					section.a[0] as f64 + section.a[1] as f64 * z + section.a[2] as f64 * z * z;
+				(numerator / denominator).norm()
+			})
+			.product()
+	}
+
+	fn design(
+		filter_kind: FilterKind,
+		cutoff_hz: [f32; 2],
+		order: usize,
+		prototype: IirPrototype,
+	) -> SosCoefficients {
+		design_sos(&SosDesignConfig {
+			sample_rate: 256.0,
+			filter_kind,
+			cutoff_hz,
+			order,
+			prototype,
+		})
+		.unwrap_or_else(|error| panic!("SOS 设计失败: {error}"))
+	}
+
+	/// 数字化后的归一化频率 tan(ω/2) / tan(ωc/2)。
+	fn warped_ratio(frequency: f64, cutoff: f64) -> f64 {
+		(PI * frequency / 256.0).tan() / (PI * cutoff / 256.0).tan()
+	}
+
+	fn chebyshev_polynomial(order: usize, x: f64) -> f64 {
+		if x.abs() <= 1.0 {
+			(order as f64 * x.acos()).cos()
+		} else {
+			(order as f64 * x.abs().acosh()).cosh() * x.signum().powi(order as i32)
+		}
+	}
+
+	#[test]
+	fn second_order_butterworth_should_match_reference_coefficients() {
+		// scipy.signal.butter(2, 0.5, output="sos")
+		let sos = design(
+			FilterKind::LowPass,
+			[64.0, 0.0],
+			2,
+			IirPrototype::Butterworth,
+		);
+
+		assert_eq!(sos.sections.len(), 1);
+		let expected_b = [0.292_893_22, 0.585_786_44, 0.292_893_22];
+		let expected_a = [1.0, 0.0, 0.171_572_88];
+		for index in 0..3 {
+			assert!((sos.sections[0].b[index] - expected_b[index]).abs() < 1e-6);
+			assert!((sos.sections[0].a[index] - expected_a[index]).abs() < 1e-6);
+		}
+	}
+
+	#[test]
+	fn butterworth_should_follow_analytic_magnitude() {
+		let sos = design(
+			FilterKind::LowPass,
+			[30.0, 0.0],
+			5,
+			IirPrototype::Butterworth,
+		);
+
+		assert_eq!(sos.sections.len(), 3);
+		for frequency in [1.0, 10.0, 30.0, 45.0, 80.0] {
+			let expected = 1.0 / (1.0 + warped_ratio(frequency, 30.0).powi(10)).sqrt();
+			assert!((magnitude_at(&sos, frequency, 256.0) - expected).abs() < 1e-4);
+		}
+	}
+
+	#[test]
+	fn chebyshev1_should_follow_analytic_magnitude() {
+		let sos = design(
+			FilterKind::LowPass,
+			[40.0, 0.0],
+			4,
+			IirPrototype::ChebyshevI {
+				passband_ripple_db: 1.0,
+			},
+		);
+		let epsilon2 = 10f64.powf(0.1) - 1.0;
+
+		for frequency in [0.0, 12.0, 25.0, 40.0, 60.0, 100.0] {
+			let chebyshev = chebyshev_polynomial(4, warped_ratio(frequency, 40.0));
+			let expected = 1.0 / (1.0 + epsilon2 * chebyshev * chebyshev).sqrt();
+			assert!((magnitude_at(&sos, frequency, 256.0) - expected).abs() < 1e-4);
+		}
+	}
+
+	#[test]
+	fn chebyshev2_should_follow_analytic_magnitude() {
+		let sos = design(
+			FilterKind::LowPass,
+			[40.0, 0.0],
+			5,
+			IirPrototype::ChebyshevII {
+				stopband_attenuation_db: 40.0,
+			},
+		);
+		let delta2 = 1.0 / (10f64.powf(4.0) - 1.0);
+
+		for frequency in [5.0, 20.0, 35.0, 50.0, 90.0] {
+			let chebyshev = chebyshev_polynomial(5, 1.0 / warped_ratio(frequency, 40.0));
+			let expected = 1.0 / (1.0 + 1.0 / (delta2 * chebyshev * chebyshev)).sqrt();
+			assert!((magnitude_at(&sos, frequency, 256.0) - expected).abs() < 1e-4);
+		}
+	}
+
+	#[test]
+	fn elliptic_should_meet_ripple_and_attenuation() {
+		let sos = design(
+			FilterKind::LowPass,
+			[40.0, 0.0],
+			4,
+			IirPrototype::Elliptic {
+				passband_ripple_db: 0.5,
+				stopband_attenuation_db: 50.0,
+			},
+		);
+
+		let passband_floor = 10f64.powf(-0.5 / 20.0) - 1e-4;
+		for index in 0..=40 {
+			let magnitude = magnitude_at(&sos, index as f64, 256.0);
+			assert!(magnitude <= 1.0 + 1e-4 && magnitude >= passband_floor);
+		}
+
+		// 椭圆低通在通带边缘处恰好等于波纹下限。
+		assert!((magnitude_at(&sos, 40.0, 256.0) - 10f64.powf(-0.5 / 20.0)).abs() < 1e-4);
+		// 阻带边缘由度方程给出：tan(πfs/Fs) = tan(πfp/Fs) / k。
+		let passband_epsilon = (10f64.powf(0.05) - 1.0).sqrt();
+		let stopband_epsilon = (10f64.powf(5.0) - 1.0).sqrt();
+		let k = elliptic_degree(4, passband_epsilon / stopband_epsilon);
+		let stopband_edge = ((PI * 40.0 / 256.0).tan() / k).atan() * 256.0 / PI;
+		let stopband_ceiling = 10f64.powf(-50.0 / 20.0) + 1e-5;
+		for index in 0..=100 {
+			let frequency = stopband_edge + (128.0 - stopband_edge) * index as f64 / 100.0;
+			assert!(magnitude_at(&sos, frequency, 256.0) <= stopband_ceiling);
+		}
+	}
+
+	#[test]
+	fn bandpass_should_pass_center_and_reject_edges() {
+		let sos = design(
+			FilterKind::BandPass,
+			[0.5, 40.0],
+			4,
+			IirPrototype::Butterworth,
+		);
+
+		assert_eq!(sos.sections.len(), 4);
+		assert!((magnitude_at(&sos, 10.0, 256.0) - 1.0).abs() < 1e-3);
+		let edge = 1.0 / 2.0_f64.sqrt();
+		assert!((magnitude_at(&sos, 0.5, 256.0) - edge).abs() < 1e-3);
+		assert!((magnitude_at(&sos, 40.0, 256.0) - edge).abs() < 1e-3);
+		assert!(magnitude_at(&sos, 100.0, 256.0) < 2e-3);
+	}
+
+	#[test]
+	fn bandstop_should_reject_center() {
+		let sos = design(
+			FilterKind::BandStop,
+			[45.0, 55.0],
+			3,
+			IirPrototype::Butterworth,
+		);
+
+		assert_eq!(sos.sections.len(), 3);
+		assert!(magnitude_at(&sos, 50.0, 256.0) < 0.05);
+		assert!((magnitude_at(&sos, 10.0, 256.0) - 1.0).abs() < 1e-3);
+	}
+
+	#[test]
+	fn elliptic_should_reject_attenuation_below_ripple() {
+		let result = design_sos(&SosDesignConfig {
+			sample_rate: 256.0,
+			filter_kind: FilterKind::LowPass,
+			cutoff_hz: [40.0, 0.0],
+			order: 4,
+			prototype: IirPrototype::Elliptic {
+				passband_ripple_db: 3.0,
+				stopband_attenuation_db: 1.0,
+			},
+		});
+
+		assert!(result.is_err());
+	}
+}
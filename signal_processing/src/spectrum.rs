@@ -2,7 +2,8 @@ use rustfft::{FftPlanner, num_complex::Complex32};
 
 use crate::{
 	error::SignalProcessError,
-	types::{SignalBuffer, SpectrumPoint, WelchSpectrumConfig},
+	types::{SignalBuffer, SpectrumPoint, WelchSpectrumConfig, WindowKind},
+	window::build_window,
 };
 
 /// 计算 Welch 单边功率谱。
@@ -27,7 +28,7 @@ pub fn compute_power_spectrum_with_config(
 	let step = config.segment_length - config.overlap_length;
 	let segment_starts = build_segment_starts(signal.samples.len(), config.segment_length, step);
 	let half = config.segment_length / 2;
-	let window = build_hann_window(config.segment_length);
+	let window = build_window(WindowKind::Hann, config.segment_length);
 	let window_power = window.iter().map(|value| value * value).sum::<f32>();
 	let normalization = signal.sample_rate * window_power;
 	let mut planner = FftPlanner::<f32>::new();
@@ -112,7 +113,11 @@ fn validate_welch_arguments(
 }
 
 /// 构造分段起点列表。
-fn build_segment_starts(sample_count: usize, segment_length: usize, step: usize) -> Vec<usize> {
+pub(crate) fn build_segment_starts(
+	sample_count: usize,
+	segment_length: usize,
+	step: usize,
+) -> Vec<usize> {
 	let mut starts = Vec::new();
 	let mut start = 0usize;
 	while start + segment_length <= sample_count {
@@ -122,20 +127,6 @@ fn build_segment_starts(sample_count: usize, segment_length: usize, step: usize)
 	starts
 }
 
-/// 构造 Hann 窗。
-fn build_hann_window(length: usize) -> Vec<f32> {
-	if length == 1 {
-		return vec![1.0];
-	}
-
-	(0..length)
-		.map(|index| {
-			let phase = 2.0 * std::f32::consts::PI * index as f32 / (length - 1) as f32;
-			0.5 - 0.5 * phase.cos()
-		})
-		.collect()
-}
-
 #[cfg(test)]
 mod tests {
 	use crate::{
@@ -0,0 +1,371 @@
+use rustfft::{FftPlanner, num_complex::Complex32};
+
+use crate::{
+	error::SignalProcessError,
+	spectrum::build_segment_starts,
+	types::{SignalBuffer, Spectrogram, SpectrogramScale, StftConfig, StftOutput},
+	window::build_window,
+};
+
+/// dB 刻度下功率的下限，避免对 0 取对数。
+const DECIBEL_FLOOR: f32 = 1e-20;
+
+/// 重建时窗平方和相对最大值的下限，低于该值的样本在分析时已被窗函数抹去。
+const WINDOW_SUM_FLOOR: f32 = 1e-8;
+
+/// 计算短时傅里叶变换。
+///
+/// 分段方式与 Welch 功率谱一致，每帧加窗后补零到 FFT 长度，只保留单边频点。
+pub fn compute_stft(
+	signal: &SignalBuffer,
+	config: &StftConfig,
+) -> Result<StftOutput, SignalProcessError> {
+	validate_stft_arguments(signal, config)?;
+
+	let padded = pad_for_center(&signal.samples, config);
+	if padded.len() < config.window_length {
+		return Err(SignalProcessError::InvalidArgument(
+			"STFT 窗长不能超过输入样本数量".to_string(),
+		));
+	}
+
+	let window = build_window(config.window, config.window_length);
+	let mut planner = FftPlanner::<f32>::new();
+	let fft = planner.plan_fft_forward(config.fft_size);
+	let bin_count = config.fft_size / 2 + 1;
+	let frames = build_segment_starts(padded.len(), config.window_length, config.hop_length)
+		.into_iter()
+		.map(|start| {
+			let mut bins: Vec<Complex32> = padded[start..start + config.window_length]
+				.iter()
+				.zip(window.iter())
+				.map(|(sample, weight)| Complex32::new(sample * weight, 0.0))
+				.chain(std::iter::repeat(Complex32::new(0.0, 0.0)))
+				.take(config.fft_size)
+				.collect();
+			fft.process(&mut bins);
+			bins.truncate(bin_count);
+			bins
+		})
+		.collect();
+
+	Ok(StftOutput {
+		sample_rate: signal.sample_rate,
+		config: *config,
+		signal_length: signal.samples.len(),
+		frames,
+	})
+}
+
+/// 以加权重叠相加重建时域信号。
+///
+/// 每帧逆变换后再乘一次分析窗并累加，最后除以窗平方和，因此对任意满足覆盖条件的窗都能精确重建。
+/// 若某个输出样本只落在窗函数为 0 的位置（如 `center = false` 时 Hann 窗的首尾样本），
+/// 该样本无法恢复，返回错误而不是输出 0；此时应使用 `center = true` 或端点非零的窗。
+pub fn compute_inverse_stft(stft: &StftOutput) -> Result<SignalBuffer, SignalProcessError> {
+	let config = &stft.config;
+	validate_stft_config(config)?;
+	if stft.frames.is_empty() {
+		return Err(SignalProcessError::EmptyInput);
+	}
+	let bin_count = config.fft_size / 2 + 1;
+	if stft.frames.iter().any(|frame| frame.len() != bin_count) {
+		return Err(SignalProcessError::InvalidArgument(format!(
+			"STFT 帧的频点数必须为 {bin_count}"
+		)));
+	}
+
+	let window = build_window(config.window, config.window_length);
+	let mut planner = FftPlanner::<f32>::new();
+	let inverse = planner.plan_fft_inverse(config.fft_size);
+	let output_length = (stft.frames.len() - 1) * config.hop_length + config.window_length;
+	let mut output = vec![0.0f32; output_length];
+	let mut window_sum = vec![0.0f32; output_length];
+	let scale = 1.0 / config.fft_size as f32;
+
+	for (frame_index, frame) in stft.frames.iter().enumerate() {
+		let mut bins = vec![Complex32::new(0.0, 0.0); config.fft_size];
+		bins[..bin_count].copy_from_slice(frame);
+		for index in bin_count..config.fft_size {
+			bins[index] = bins[config.fft_size - index].conj();
+		}
+		inverse.process(&mut bins);
+
+		let start = frame_index * config.hop_length;
+		for (offset, weight) in window.iter().enumerate() {
+			output[start + offset] += bins[offset].re * scale * weight;
+			window_sum[start + offset] += weight * weight;
+		}
+	}
+
+	let offset = if config.center {
+		config.window_length / 2
+	} else {
+		0
+	};
+	let end = offset + stft.signal_length;
+	if end > output_length {
+		return Err(SignalProcessError::InvalidArgument(format!(
+			"STFT 帧只覆盖 {} 个样本，少于原信号长度 {}",
+			output_length.saturating_sub(offset),
+			stft.signal_length
+		)));
+	}
+	let floor = window_sum.iter().copied().fold(0.0, f32::max) * WINDOW_SUM_FLOOR;
+	if let Some(index) = window_sum[offset..end]
+		.iter()
+		.position(|weight| *weight <= floor)
+	{
+		return Err(SignalProcessError::InvalidArgument(format!(
+			"第 {index} 个样本未被非零窗覆盖，无法重建"
+		)));
+	}
+
+	let samples = output[offset..end]
+		.iter()
+		.zip(window_sum[offset..end].iter())
+		.map(|(sample, weight)| sample / weight)
+		.collect();
+
+	Ok(SignalBuffer {
+		sample_rate: stft.sample_rate,
+		samples,
+	})
+}
+
+/// 计算时间 × 频率的时频图。
+///
+/// 功率归一化与 `compute_power_spectrum_with_config` 相同，相同分段参数下各帧均值即为 Welch 功率谱。
+pub fn compute_spectrogram(
+	signal: &SignalBuffer,
+	config: &StftConfig,
+	scale: SpectrogramScale,
+) -> Result<Spectrogram, SignalProcessError> {
+	let stft = compute_stft(signal, config)?;
+	let window = build_window(config.window, config.window_length);
+	let window_power = window.iter().map(|value| value * value).sum::<f32>();
+	let normalization = signal.sample_rate * window_power;
+	let half = config.fft_size / 2;
+	let center_offset = if config.center {
+		config.window_length / 2
+	} else {
+		0
+	};
+
+	let values = stft
+		.frames
+		.iter()
+		.map(|frame| {
+			frame
+				.iter()
+				.enumerate()
+				.map(|(index, bin)| {
+					let mut power = bin.norm_sqr() / normalization;
+					if index != 0 && !(config.fft_size.is_multiple_of(2) && index == half) {
+						power *= 2.0;
+					}
+					match scale {
+						SpectrogramScale::Power => power,
+						SpectrogramScale::Decibel => 10.0 * power.max(DECIBEL_FLOOR).log10(),
+					}
+				})
+				.collect()
+		})
+		.collect();
+	let times = (0..stft.frames.len())
+		.map(|frame_index| {
+			let center = (frame_index * config.hop_length + config.window_length / 2) as f32
+				- center_offset as f32;
+			center / signal.sample_rate
+		})
+		.collect();
+	let frequencies = (0..=half)
+		.map(|index| index as f32 * signal.sample_rate / config.fft_size as f32)
+		.collect();
+
+	Ok(Spectrogram {
+		times,
+		frequencies,
+		values,
+		scale,
+	})
+}
+
+fn pad_for_center(samples: &[f32], config: &StftConfig) -> Vec<f32> {
+	if !config.center {
+		return samples.to_vec();
+	}
+
+	let pad = config.window_length / 2;
+	let mut padded = vec![0.0; samples.len() + 2 * pad];
+	padded[pad..pad + samples.len()].copy_from_slice(samples);
+	padded
+}
+
+fn validate_stft_arguments(
+	signal: &SignalBuffer,
+	config: &StftConfig,
+) -> Result<(), SignalProcessError> {
+	if signal.samples.is_empty() {
+		return Err(SignalProcessError::EmptyInput);
+	}
+	if signal.sample_rate <= 0.0 {
+		return Err(SignalProcessError::InvalidArgument(
+			"采样率必须大于 0".to_string(),
+		));
+	}
+	validate_stft_config(config)
+}
+
+fn validate_stft_config(config: &StftConfig) -> Result<(), SignalProcessError> {
+	if config.window_length < 2 {
+		return Err(SignalProcessError::InvalidArgument(
+			"STFT 窗长必须大于等于 2".to_string(),
+		));
+	}
+	if config.hop_length == 0 || config.hop_length > config.window_length {
+		return Err(SignalProcessError::InvalidArgument(
+			"STFT 帧移必须位于 [1, 窗长] 内".to_string(),
+		));
+	}
+	if config.fft_size < config.window_length {
+		return Err(SignalProcessError::InvalidArgument(
+			"FFT 长度不能小于窗长".to_string(),
+		));
+	}
+	Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+	use crate::{
+		CompositeComponent, WelchSpectrumConfig, WindowKind, compute_power_spectrum_with_config,
+		generate_composite_signal,
+	};
+
+	use super::*;
+
+	fn test_signal() -> SignalBuffer {
+		generate_composite_signal(
+			256.0,
+			4.0,
+			&[
+				CompositeComponent {
+					frequency: 10.0,
+					amplitude: 1.0,
+					phase: 0.0,
+				},
+				CompositeComponent {
+					frequency: 40.0,
+					amplitude: 0.3,
+					phase: 0.5,
+				},
+			],
+		)
+		.unwrap_or_else(|error| panic!("复合信号生成失败: {error}"))
+	}
+
+	#[test]
+	fn inverse_stft_should_reconstruct_every_sample() {
+		let signal = test_signal();
+		for (window, center) in [
+			(WindowKind::Hann, true),
+			(WindowKind::Hamming, true),
+			(WindowKind::Hamming, false),
+		] {
+			let config = StftConfig {
+				window,
+				window_length: 64,
+				hop_length: 16,
+				fft_size: 128,
+				center,
+			};
+
+			let stft =
+				compute_stft(&signal, &config).unwrap_or_else(|error| panic!("STFT 失败: {error}"));
+			let restored =
+				compute_inverse_stft(&stft).unwrap_or_else(|error| panic!("逆 STFT 失败: {error}"));
+
+			assert_eq!(restored.samples.len(), signal.samples.len());
+			for (left, right) in restored.samples.iter().zip(signal.samples.iter()) {
+				assert!((left - right).abs() < 1e-4);
+			}
+		}
+	}
+
+	#[test]
+	fn inverse_stft_should_reject_samples_zeroed_by_window() {
+		let config = StftConfig {
+			window: WindowKind::Hann,
+			window_length: 64,
+			hop_length: 16,
+			fft_size: 128,
+			center: false,
+		};
+
+		let stft = compute_stft(&test_signal(), &config)
+			.unwrap_or_else(|error| panic!("STFT 失败: {error}"));
+
+		assert!(compute_inverse_stft(&stft).is_err());
+	}
+
+	#[test]
+	fn spectrogram_frame_mean_should_match_welch() {
+		let signal = test_signal();
+		let config = StftConfig {
+			window: WindowKind::Hann,
+			window_length: 128,
+			hop_length: 64,
+			fft_size: 128,
+			center: false,
+		};
+
+		let spectrogram = compute_spectrogram(&signal, &config, SpectrogramScale::Power)
+			.unwrap_or_else(|error| panic!("时频图失败: {error}"));
+		let welch = compute_power_spectrum_with_config(
+			&signal,
+			WelchSpectrumConfig {
+				segment_length: 128,
+				overlap_length: 64,
+			},
+		)
+		.unwrap_or_else(|error| panic!("Welch 功率谱失败: {error}"));
+
+		assert_eq!(spectrogram.frequencies.len(), welch.len());
+		for (index, point) in welch.iter().enumerate() {
+			let mean = spectrogram
+				.values
+				.iter()
+				.map(|frame| frame[index])
+				.sum::<f32>()
+				/ spectrogram.values.len() as f32;
+			assert!((mean - point.value).abs() <= 1e-4 * point.value.max(1e-3));
+		}
+	}
+
+	#[test]
+	fn decibel_spectrogram_should_peak_at_signal_frequency() {
+		let signal = test_signal();
+		let config = StftConfig {
+			window: WindowKind::Hamming,
+			window_length: 128,
+			hop_length: 32,
+			fft_size: 256,
+			center: true,
+		};
+
+		let spectrogram = compute_spectrogram(&signal, &config, SpectrogramScale::Decibel)
+			.unwrap_or_else(|error| panic!("时频图失败: {error}"));
+
+		assert!((spectrogram.times[0] - 0.0).abs() < 1e-6);
+		for frame in spectrogram.values.iter().skip(2).take(20) {
+			let peak = frame
+				.iter()
+				.enumerate()
+				.max_by(|left, right| left.1.total_cmp(right.1))
+				.map(|(index, _)| spectrogram.frequencies[index])
+				.unwrap_or_else(|| panic!("时频帧为空"));
+			assert!((peak - 10.0).abs() <= 1.0);
+		}
+	}
+}
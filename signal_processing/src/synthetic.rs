@@ -0,0 +1,160 @@
+pub use eeg_simulator::{EegSimulationConfig, EegSimulator, ErpComponent, NoiseColor};
+
+use crate::{error::SignalProcessError, multichannel::MultiChannelSignal, types::ChannelInfo};
+
+/// 按配置生成一段类 EEG 多通道信号，通道名为 `EEG1`、`EEG2`……
+pub fn generate_eeg_like_signal(
+	config: &EegSimulationConfig,
+	duration_secs: f32,
+) -> Result<MultiChannelSignal, SignalProcessError> {
+	if duration_secs <= 0.0 {
+		return Err(SignalProcessError::InvalidArgument(
+			"时长必须大于 0".to_string(),
+		));
+	}
+
+	let mut simulator = EegSimulator::new(config.clone())?;
+	let sample_count = (config.sample_rate * duration_secs).round() as usize;
+	let infos = (0..config.channel_count)
+		.map(|index| ChannelInfo {
+			label: format!("EEG{}", index + 1),
+			unit: "uV".to_string(),
+			sample_rate: config.sample_rate,
+		})
+		.collect();
+	MultiChannelSignal::new(infos, simulator.generate(sample_count), 0.0)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::{
+		epoch::{average_epochs, extract_epochs},
+		notch::detect_line_frequency,
+		spectrum::compute_power_spectrum_with_config,
+		types::{EpochConfig, EventMarker, SignalBuffer, SpectrumPoint, WelchSpectrumConfig},
+	};
+
+	fn quiet_config() -> EegSimulationConfig {
+		EegSimulationConfig {
+			alpha_amplitude: 0.0,
+			spike_amplitude: 0.0,
+			blink_amplitude: 0.0,
+			line_amplitude: 0.0,
+			..EegSimulationConfig::default()
+		}
+	}
+
+	fn mean_power(spectrum: &[SpectrumPoint], low: f32, high: f32) -> f32 {
+		let selected: Vec<f32> = spectrum
+			.iter()
+			.filter(|point| point.frequency >= low && point.frequency <= high)
+			.map(|point| point.value)
+			.collect();
+		selected.iter().sum::<f32>() / selected.len() as f32
+	}
+
+	#[test]
+	fn generated_signal_should_match_simulator_frames() {
+		let config = EegSimulationConfig {
+			seed: 11,
+			..EegSimulationConfig::default()
+		};
+		let signal = generate_eeg_like_signal(&config, 2.0)
+			.unwrap_or_else(|error| panic!("生成仿真信号失败: {error}"));
+		let frames = EegSimulator::new(config)
+			.unwrap_or_else(|error| panic!("创建仿真器失败: {error}"))
+			.generate(512);
+
+		assert_eq!(
+			signal.labels().collect::<Vec<_>>(),
+			["EEG1", "EEG2", "EEG3", "EEG4"]
+		);
+		assert_eq!(signal.channel_data(), frames.as_slice());
+		assert!(
+			generate_eeg_like_signal(
+				&EegSimulationConfig {
+					line_frequency_hz: 200.0,
+					..EegSimulationConfig::default()
+				},
+				1.0
+			)
+			.is_err()
+		);
+	}
+
+	#[test]
+	fn background_should_follow_one_over_f_and_carry_line_noise() {
+		let config = EegSimulationConfig {
+			channel_count: 1,
+			line_amplitude: 5.0,
+			..quiet_config()
+		};
+		let signal = generate_eeg_like_signal(&config, 60.0)
+			.unwrap_or_else(|error| panic!("生成仿真信号失败: {error}"));
+		let buffer = SignalBuffer {
+			sample_rate: config.sample_rate,
+			samples: signal.channel_data()[0].clone(),
+		};
+
+		let spectrum = compute_power_spectrum_with_config(
+			&buffer,
+			WelchSpectrumConfig {
+				segment_length: 512,
+				overlap_length: 256,
+			},
+		)
+		.unwrap_or_else(|error| panic!("功率谱计算失败: {error}"));
+		let detection = detect_line_frequency(&buffer)
+			.unwrap_or_else(|error| panic!("工频检测失败: {error}"))
+			.unwrap_or_else(|| panic!("应检测到工频干扰"));
+
+		let ratio = mean_power(&spectrum, 2.0, 4.0) / mean_power(&spectrum, 20.0, 40.0);
+		assert!(ratio > 5.0 && ratio < 20.0, "1/f 功率比 {ratio}");
+		assert!((detection.frequency_hz - 50.0).abs() < 0.01);
+	}
+
+	#[test]
+	fn evoked_response_should_be_recovered_by_averaging() {
+		let events: Vec<EventMarker> = (0..40)
+			.map(|index| EventMarker {
+				onset_secs: 1.0 + index as f64,
+				code: 1,
+			})
+			.collect();
+		let config = EegSimulationConfig {
+			channel_count: 2,
+			background_amplitude: 2.0,
+			event_onsets_secs: events.iter().map(|event| event.onset_secs).collect(),
+			..quiet_config()
+		};
+		let signal = generate_eeg_like_signal(&config, 42.0)
+			.unwrap_or_else(|error| panic!("生成仿真信号失败: {error}"));
+
+		let epochs = extract_epochs(
+			&signal,
+			&events,
+			&EpochConfig {
+				reject_peak_to_peak: None,
+				..EpochConfig::default()
+			},
+		)
+		.unwrap_or_else(|error| panic!("分段失败: {error}"));
+		let evoked =
+			average_epochs(&epochs, &[1]).unwrap_or_else(|error| panic!("平均失败: {error}"));
+
+		let value_at = |channel: usize, time: f32| {
+			let index = evoked
+				.times_secs
+				.iter()
+				.position(|sample_time| (sample_time - time).abs() < 0.5 / config.sample_rate)
+				.unwrap_or_else(|| panic!("找不到时刻 {time}"));
+			evoked.mean[channel][index]
+		};
+		assert_eq!(evoked.epoch_count, 40);
+		for channel in 0..2 {
+			assert!((value_at(channel, 0.17) + 5.0).abs() < 1.0);
+			assert!((value_at(channel, 0.35) - 8.0).abs() < 1.0);
+		}
+	}
+}
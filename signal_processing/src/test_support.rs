@@ -0,0 +1,12 @@
+//! 单元测试共用的确定性信号。
+
+/// 确定性的均匀白噪声，取值 [-0.5, 0.5)。
+pub(crate) fn white_noise(seed: u32, count: usize) -> Vec<f32> {
+	let mut state = seed;
+	(0..count)
+		.map(|_| {
+			state = state.wrapping_mul(1_664_525).wrapping_add(1_013_904_223);
+			(state >> 8) as f32 / (1u32 << 24) as f32 - 0.5
+		})
+		.collect()
+}
@@ -1,4 +1,5 @@
-use rustfft::num_complex::Complex32;
+pub use rustfft::num_complex::Complex32;
+use serde::{Deserialize, Serialize};
 
 /// 单通道信号缓冲区。
 #[derive(Debug, Clone)]
@@ -7,6 +8,16 @@ pub struct SignalBuffer {
 	pub samples: Vec<f32>,
 }
 
+/// 多通道信号中单个通道的元数据。
+#[derive(Debug, Clone, PartialEq)]
+pub struct ChannelInfo {
+	pub label: String,
+
+	/// 物理单位，例如 uV。
+	pub unit: String,
+	pub sample_rate: f32,
+}
+
 /// 正弦波生成配置。
 #[derive(Debug, Clone, Copy)]
 pub struct SineWaveConfig {
@@ -32,6 +43,13 @@ pub struct FftOutput {
 	pub bins: Vec<Complex32>,
 }
 
+/// 解析信号 x + j·H{x}，实部为原信号，虚部为其 Hilbert 变换。
+#[derive(Debug, Clone)]
+pub struct AnalyticSignal {
+	pub sample_rate: f32,
+	pub values: Vec<Complex32>,
+}
+
 /// 频谱点。
 #[derive(Debug, Clone, Copy)]
 pub struct SpectrumPoint {
@@ -39,6 +57,21 @@ pub struct SpectrumPoint {
 	pub value: f32,
 }
 
+/// 滤波器频率响应中的一个频点。
+#[derive(Debug, Clone, Copy)]
+pub struct FrequencyResponsePoint {
+	pub frequency: f32,
+
+	/// 幅度响应 (dB)。
+	pub magnitude_db: f32,
+
+	/// 沿频率轴展开后的相位 (rad)。
+	pub phase_rad: f32,
+
+	/// 群延迟 (样本数)。
+	pub group_delay_samples: f32,
+}
+
 /// Welch 功率谱估计配置。
 #[derive(Debug, Clone, Copy)]
 pub struct WelchSpectrumConfig {
@@ -49,6 +82,128 @@ pub struct WelchSpectrumConfig {
 	pub overlap_length: usize,
 }
 
+/// 短时傅里叶变换配置。
+#[derive(Debug, Clone, Copy)]
+pub struct StftConfig {
+	pub window: WindowKind,
+
+	/// 每帧加窗的样本长度。
+	pub window_length: usize,
+
+	/// 相邻帧起点之间的样本数。
+	pub hop_length: usize,
+
+	/// FFT 长度，不小于窗长，多出部分补零。
+	pub fft_size: usize,
+
+	/// 是否在信号两侧各补半个窗长的零，使帧中心与采样时刻对齐。
+	pub center: bool,
+}
+
+/// 短时傅里叶变换结果。
+#[derive(Debug, Clone)]
+pub struct StftOutput {
+	pub sample_rate: f32,
+	pub config: StftConfig,
+
+	/// 原始信号长度，逆变换据此截断。
+	pub signal_length: usize,
+
+	/// 按 [帧][频点] 排列的单边频谱，频点数为 fft_size / 2 + 1。
+	pub frames: Vec<Vec<Complex32>>,
+}
+
+/// 时频图数值刻度。
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SpectrogramScale {
+	/// 单边功率谱密度。
+	Power,
+	/// 10·log10 功率谱密度。
+	Decibel,
+}
+
+/// 时间 × 频率的时频图。
+#[derive(Debug, Clone)]
+pub struct Spectrogram {
+	/// 各帧中心时刻 (秒)。
+	pub times: Vec<f32>,
+	pub frequencies: Vec<f32>,
+
+	/// 按 [帧][频点] 排列的数值。
+	pub values: Vec<Vec<f32>>,
+	pub scale: SpectrogramScale,
+}
+
+/// Morlet 小波的周期数 n，决定时间分辨率 σt = n / (2πf) 与频率分辨率的折中。
+#[derive(Debug, Clone, PartialEq)]
+pub enum MorletCycles {
+	/// 所有频率使用相同周期数。
+	Constant(f32),
+	/// 周期数随频率序号从 low 线性增加到 high。
+	Linear { low: f32, high: f32 },
+	/// 逐频率指定，长度须与频率列表一致。
+	PerFrequency(Vec<f32>),
+}
+
+/// Morlet 连续小波变换配置。
+#[derive(Debug, Clone)]
+pub struct MorletConfig {
+	/// 分析频率 (Hz)。
+	pub frequencies: Vec<f32>,
+	pub cycles: MorletCycles,
+}
+
+impl Default for MorletConfig {
+	fn default() -> Self {
+		Self {
+			frequencies: (2..=40).map(|frequency| frequency as f32).collect(),
+			cycles: MorletCycles::Linear {
+				low: 3.0,
+				high: 10.0,
+			},
+		}
+	}
+}
+
+/// 小波变换系数，幅值为 a 的正弦在其频率处的系数模约为 a。
+#[derive(Debug, Clone)]
+pub struct WaveletTransform {
+	/// 各样本的时刻 (秒)。
+	pub times: Vec<f32>,
+	pub frequencies: Vec<f32>,
+
+	/// 按 [时刻][频率] 排列的复系数。
+	pub coefficients: Vec<Vec<Complex32>>,
+}
+
+/// 时频功率的基线归一化方式。
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BaselineMode {
+	/// 10·log10(P / 基线均值)。
+	Decibel,
+	/// 100·(P - 基线均值) / 基线均值。
+	PercentChange,
+	/// (P - 基线均值) / 基线标准差。
+	ZScore,
+}
+
+/// 时频功率图。
+#[derive(Debug, Clone)]
+pub struct TimeFrequencyPower {
+	/// 各样本的时刻 (秒)，分段平均时相对事件时刻。
+	pub times: Vec<f32>,
+	pub frequencies: Vec<f32>,
+
+	/// 按 [时刻][频率] 排列的功率或归一化后的数值。
+	pub values: Vec<Vec<f32>>,
+
+	/// 参与平均的分段数，单段信号为 1。
+	pub epoch_count: usize,
+
+	/// 已应用的基线归一化方式。
+	pub baseline: Option<BaselineMode>,
+}
+
 /// 滤波器类别。
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum FilterKind {
@@ -58,6 +213,21 @@ pub enum FilterKind {
 	BandStop,
 }
 
+/// 窗函数类别。
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum WindowKind {
+	Rectangular,
+	Hann,
+	Hamming,
+	Blackman,
+	/// Kaiser 窗，`beta` 越大旁瓣越低、主瓣越宽。
+	Kaiser {
+		beta: f32,
+	},
+	/// 平顶窗，幅度测量误差最小。
+	FlatTop,
+}
+
 /// FIR 设计配置。
 #[derive(Debug, Clone)]
 pub struct FirDesignConfig {
@@ -65,6 +235,48 @@ pub struct FirDesignConfig {
 	pub filter_kind: FilterKind,
 	pub cutoff_hz: Vec<f32>,
 	pub tap_count: usize,
+	pub window: WindowKind,
+}
+
+/// 按指标设计 Kaiser 窗 FIR 的配置。
+#[derive(Debug, Clone)]
+pub struct KaiserFirSpec {
+	pub sample_rate: f32,
+	pub filter_kind: FilterKind,
+	pub cutoff_hz: Vec<f32>,
+
+	/// 通带与阻带允许的最大波纹，以阻带衰减 dB 表示。
+	pub ripple_db: f32,
+
+	/// 过渡带宽度 (Hz)。
+	pub transition_width_hz: f32,
+}
+
+/// Kaiser 窗估计结果。
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct KaiserParameters {
+	pub tap_count: usize,
+	pub beta: f32,
+}
+
+/// 等波纹 FIR 设计中的一个频带。
+#[derive(Debug, Clone, Copy)]
+pub struct RemezBand {
+	pub start_hz: f32,
+	pub end_hz: f32,
+	pub desired_gain: f32,
+	pub weight: f32,
+}
+
+/// 等波纹（Parks-McClellan）FIR 设计配置。
+#[derive(Debug, Clone)]
+pub struct RemezDesignConfig {
+	pub sample_rate: f32,
+	pub tap_count: usize,
+
+	/// 按频率升序排列且互不重叠的频带，频带之间的空隙为不关心的过渡带。
+	pub bands: Vec<RemezBand>,
+	pub max_iterations: usize,
 }
 
 /// IIR 设计配置。
@@ -82,3 +294,592 @@ pub struct IirCoefficients {
 	pub b: [f32; 3],
 	pub a: [f32; 3],
 }
+
+/// 二阶节级联（SOS）形式的 IIR 系数。
+#[derive(Debug, Clone)]
+pub struct SosCoefficients {
+	/// 按处理顺序排列的二阶节。
+	pub sections: Vec<IirCoefficients>,
+}
+
+/// 高阶 IIR 的模拟原型。
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum IirPrototype {
+	/// 通带最平坦的 Butterworth 原型。
+	Butterworth,
+	/// 通带等波纹的 Chebyshev I 型原型。
+	ChebyshevI { passband_ripple_db: f32 },
+	/// 阻带等波纹的 Chebyshev II 型原型，截止频率为阻带边缘。
+	ChebyshevII { stopband_attenuation_db: f32 },
+	/// 通带与阻带均等波纹的椭圆原型。
+	Elliptic {
+		passband_ripple_db: f32,
+		stopband_attenuation_db: f32,
+	},
+}
+
+/// 高阶 IIR（SOS 级联）设计配置。
+#[derive(Debug, Clone, Copy)]
+pub struct SosDesignConfig {
+	pub sample_rate: f32,
+	pub filter_kind: FilterKind,
+	pub cutoff_hz: [f32; 2],
+	/// 原型阶数，带通与带阻的实际阶数为其 2 倍。
+	pub order: usize,
+	pub prototype: IirPrototype,
+}
+
+/// 频段功率积分方法。
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IntegrationMethod {
+	Trapezoid,
+	/// 复合 Simpson 公式，区间数为奇数时最后一个区间退化为梯形。
+	Simpson,
+}
+
+/// 命名频段，例如 alpha 8–13 Hz。
+#[derive(Debug, Clone, PartialEq)]
+pub struct FrequencyBand {
+	pub name: String,
+	pub low_hz: f32,
+	pub high_hz: f32,
+}
+
+/// 频段功率计算配置。
+#[derive(Debug, Clone)]
+pub struct BandPowerConfig {
+	pub welch: WelchSpectrumConfig,
+	pub bands: Vec<FrequencyBand>,
+	pub integration: IntegrationMethod,
+
+	/// 计算总功率与相对功率的频率范围 (Hz)。
+	pub total_range_hz: [f32; 2],
+
+	/// 谱边缘频率对应的累计功率比例，常用 0.95。
+	pub spectral_edge_ratio: f32,
+
+	/// 搜索 alpha 峰值频率的范围 (Hz)。
+	pub alpha_range_hz: [f32; 2],
+}
+
+impl Default for BandPowerConfig {
+	fn default() -> Self {
+		let band = |name: &str, low_hz: f32, high_hz: f32| FrequencyBand {
+			name: name.to_string(),
+			low_hz,
+			high_hz,
+		};
+		Self {
+			welch: WelchSpectrumConfig {
+				segment_length: 256,
+				overlap_length: 128,
+			},
+			bands: vec![
+				band("delta", 0.5, 4.0),
+				band("theta", 4.0, 8.0),
+				band("alpha", 8.0, 13.0),
+				band("beta", 13.0, 30.0),
+				band("gamma", 30.0, 45.0),
+			],
+			integration: IntegrationMethod::Simpson,
+			total_range_hz: [0.5, 45.0],
+			spectral_edge_ratio: 0.95,
+			alpha_range_hz: [7.0, 14.0],
+		}
+	}
+}
+
+/// 单个频段的功率。
+#[derive(Debug, Clone, PartialEq)]
+pub struct BandPowerValue {
+	pub name: String,
+	pub low_hz: f32,
+	pub high_hz: f32,
+
+	/// 绝对功率 (信号单位²)。
+	pub absolute_power: f32,
+
+	/// 占总功率的比例，范围 [0, 1]。
+	pub relative_power: f32,
+}
+
+/// 单通道的频段功率特征。
+#[derive(Debug, Clone, PartialEq)]
+pub struct BandPowerFeatures {
+	pub bands: Vec<BandPowerValue>,
+	pub total_power: f32,
+
+	/// 累计功率达到 `spectral_edge_ratio` 时的频率 (Hz)。
+	pub spectral_edge_frequency: f32,
+
+	/// alpha 范围内的谱峰频率 (Hz)，范围内没有局部峰值时为 None。
+	pub peak_alpha_frequency: Option<f32>,
+}
+
+/// 伪迹类别。
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ArtifactKind {
+	/// 峰峰值过小，电极脱落或放大器饱和后的平直段。
+	Flat,
+	/// 连续多个样本停在通道的极值上。
+	Clipping,
+	/// 峰峰值超过阈值。
+	AmplitudeOutlier,
+	/// 高频段功率占比过高，通常来自肌电。
+	MuscleNoise,
+}
+
+/// 单个通道上的一段伪迹。
+#[derive(Debug, Clone, PartialEq)]
+pub struct ArtifactInterval {
+	pub channel_index: usize,
+	pub channel_label: String,
+	pub kind: ArtifactKind,
+
+	/// 起止时间 (秒)，已加上信号的起始时间。
+	pub start_secs: f64,
+	pub end_secs: f64,
+
+	/// 区间内触发判定的最极端指标值，单位随类别而定。
+	pub value: f32,
+}
+
+/// 坏导原因。
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BadChannelReason {
+	LowVariance,
+	HighVariance,
+	/// 与同采样率其他通道的相关性过低。
+	LowCorrelation,
+	/// 含伪迹的分段比例过高。
+	ExcessiveArtifacts,
+}
+
+/// 坏导判定结果。
+#[derive(Debug, Clone, PartialEq)]
+pub struct BadChannel {
+	pub channel_index: usize,
+	pub channel_label: String,
+	pub reason: BadChannelReason,
+
+	/// 触发判定的指标值，例如稳健 z 分数或相关系数。
+	pub value: f32,
+}
+
+/// 伪迹检测配置，幅值阈值使用通道的物理单位。
+#[derive(Debug, Clone, Copy)]
+pub struct ArtifactConfig {
+	/// 扫描分段的时长 (秒)。
+	pub epoch_secs: f32,
+
+	/// 峰峰值低于该值视为平直。
+	pub flat_peak_to_peak: f32,
+
+	/// 峰峰值高于该值视为幅值异常。
+	pub max_peak_to_peak: f32,
+
+	/// 判为削顶所需的连续极值样本数。
+	pub clipping_run_length: usize,
+
+	/// 肌电频段 (Hz)。
+	pub muscle_band_hz: [f32; 2],
+
+	/// 肌电频段功率占总功率的比例阈值。
+	pub muscle_power_ratio: f32,
+
+	/// 通道对数方差的稳健 z 分数阈值。
+	pub variance_z_threshold: f32,
+
+	/// 与其他通道相关系数绝对值的中位数低于该值视为坏导。
+	pub min_channel_correlation: f32,
+
+	/// 含伪迹分段比例超过该值视为坏导。
+	pub max_artifact_fraction: f32,
+}
+
+impl Default for ArtifactConfig {
+	fn default() -> Self {
+		Self {
+			epoch_secs: 1.0,
+			flat_peak_to_peak: 1.0,
+			max_peak_to_peak: 200.0,
+			clipping_run_length: 5,
+			muscle_band_hz: [20.0, 45.0],
+			muscle_power_ratio: 0.6,
+			variance_z_threshold: 5.0,
+			min_channel_correlation: 0.2,
+			max_artifact_fraction: 0.5,
+		}
+	}
+}
+
+/// 伪迹扫描结果。
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ArtifactReport {
+	/// 按通道、时间排序的伪迹区间，相邻同类分段已合并。
+	pub intervals: Vec<ArtifactInterval>,
+	pub bad_channels: Vec<BadChannel>,
+}
+
+/// FastICA 的非线性函数 G 的导数 g。
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IcaNonlinearity {
+	/// g(u) = tanh(u)，通用选择。
+	LogCosh,
+	/// g(u) = u·exp(-u²/2)，适合超高斯源或需要稳健性时。
+	Exp,
+	/// g(u) = u³，即峭度准则。
+	Cube,
+}
+
+/// FastICA 求解多个分量的方式。
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IcaAlgorithm {
+	/// 逐个估计分量，每次与已得分量正交化。
+	Deflation,
+	/// 同时更新全部分量后做对称正交化。
+	Symmetric,
+}
+
+/// FastICA 配置。
+#[derive(Debug, Clone, Copy)]
+pub struct IcaConfig {
+	/// 保留的分量数，None 表示与通道数相同。
+	pub component_count: Option<usize>,
+	pub algorithm: IcaAlgorithm,
+	pub nonlinearity: IcaNonlinearity,
+	pub max_iterations: usize,
+
+	/// 相邻两次迭代分量方向变化的收敛阈值。
+	pub tolerance: f32,
+
+	/// 初始解混矩阵的随机种子，相同种子得到相同结果。
+	pub seed: u64,
+}
+
+impl Default for IcaConfig {
+	fn default() -> Self {
+		Self {
+			component_count: None,
+			algorithm: IcaAlgorithm::Symmetric,
+			nonlinearity: IcaNonlinearity::LogCosh,
+			max_iterations: 200,
+			tolerance: 1e-4,
+			seed: 0,
+		}
+	}
+}
+
+/// FastICA 分解结果，矩阵均按行存储。
+#[derive(Debug, Clone)]
+pub struct IcaDecomposition {
+	/// 各通道均值，重建时加回。
+	pub channel_means: Vec<f32>,
+
+	/// 解混矩阵 [分量][通道]，sources = unmixing · (x - mean)。
+	pub unmixing: Vec<Vec<f32>>,
+
+	/// 混合矩阵 [通道][分量]，每列是一个分量在各通道上的投影。
+	pub mixing: Vec<Vec<f32>>,
+
+	/// 分量时间序列 [分量][样本]。
+	pub sources: Vec<Vec<f32>>,
+	pub iterations: usize,
+}
+
+/// 线性组合导联中的一项。
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MontageTerm {
+	pub channel: String,
+	pub weight: f32,
+}
+
+/// 导联的派生规则，JSON 中以 `kind` 字段区分。
+///
+/// `channels` 为空时表示使用全部输入通道。
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum MontageDerivation {
+	/// 双极导联 positive - negative。
+	Bipolar {
+		positive: String,
+		negative: String,
+		#[serde(default)]
+		label: Option<String>,
+	},
+	/// 每个通道减去若干参考通道的平均，例如双侧乳突参考。
+	Reference {
+		references: Vec<String>,
+		#[serde(default)]
+		channels: Vec<String>,
+		#[serde(default)]
+		exclude: Vec<String>,
+	},
+	/// 每个通道减去所选通道的平均。
+	CommonAverage {
+		#[serde(default)]
+		channels: Vec<String>,
+		#[serde(default)]
+		exclude: Vec<String>,
+	},
+	/// 表面 Laplacian (Hjorth)：通道减去相邻电极的平均。
+	Laplacian {
+		channel: String,
+		neighbors: Vec<String>,
+		#[serde(default)]
+		label: Option<String>,
+	},
+	/// 任意加权组合。
+	Weighted {
+		label: String,
+		terms: Vec<MontageTerm>,
+	},
+}
+
+/// 导联定义，可以从 JSON 加载。
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MontageDefinition {
+	pub name: String,
+	pub derivations: Vec<MontageDerivation>,
+}
+
+/// 刺激事件，例如 P300 范式中的一次闪烁。
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct EventMarker {
+	/// 事件发生时刻 (秒)，与多通道信号的起始时间处于同一时间轴。
+	pub onset_secs: f64,
+
+	/// 事件类型，例如目标 / 非目标刺激。
+	pub code: u32,
+}
+
+/// 分段配置，时间均相对事件发生时刻。
+#[derive(Debug, Clone, Copy)]
+pub struct EpochConfig {
+	/// 事件前的时长 (秒)。
+	pub pre_secs: f32,
+
+	/// 事件后的时长 (秒)。
+	pub post_secs: f32,
+
+	/// 基线区间 (秒)，例如 [-0.2, 0.0]；None 表示不做基线校正。
+	pub baseline_secs: Option<[f32; 2]>,
+
+	/// 基线校正后任一通道峰峰值超过该值时剔除该分段。
+	pub reject_peak_to_peak: Option<f32>,
+
+	/// 任一通道峰峰值低于该值时剔除该分段。
+	pub flat_peak_to_peak: Option<f32>,
+}
+
+impl Default for EpochConfig {
+	fn default() -> Self {
+		Self {
+			pre_secs: 0.2,
+			post_secs: 0.8,
+			baseline_secs: Some([-0.2, 0.0]),
+			reject_peak_to_peak: Some(100.0),
+			flat_peak_to_peak: None,
+		}
+	}
+}
+
+/// 分段被剔除的原因。
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum EpochRejectReason {
+	/// 分段窗口超出信号范围。
+	OutOfBounds,
+	/// 峰峰值过大，value 为该通道的峰峰值。
+	PeakToPeak { channel_index: usize, value: f32 },
+	/// 峰峰值过小，value 为该通道的峰峰值。
+	Flat { channel_index: usize, value: f32 },
+}
+
+/// 保留下来的分段。
+#[derive(Debug, Clone, PartialEq)]
+pub struct Epoch {
+	pub event: EventMarker,
+
+	/// 基线校正后的数据 [通道][样本]。
+	pub data: Vec<Vec<f32>>,
+}
+
+/// 被剔除的分段。
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RejectedEpoch {
+	pub event: EventMarker,
+	pub reason: EpochRejectReason,
+}
+
+/// 一组事件的分段结果。
+#[derive(Debug, Clone, PartialEq)]
+pub struct EpochSet {
+	pub channel_labels: Vec<String>,
+	pub sample_rate: f32,
+
+	/// 各样本相对事件的时刻 (秒)。
+	pub times_secs: Vec<f32>,
+	pub epochs: Vec<Epoch>,
+	pub rejected: Vec<RejectedEpoch>,
+}
+
+/// 诱发响应：同一条件下分段的平均值与标准误，或两个条件的差异波。
+#[derive(Debug, Clone, PartialEq)]
+pub struct EvokedResponse {
+	/// 条件名称，平均时为事件类型，差异波为 `a-b`。
+	pub condition: String,
+	pub channel_labels: Vec<String>,
+	pub times_secs: Vec<f32>,
+
+	/// 参与平均的分段数，差异波取两者中较小的一个。
+	pub epoch_count: usize,
+
+	/// 平均值 [通道][样本]。
+	pub mean: Vec<Vec<f32>>,
+
+	/// 均值的标准误 [通道][样本]，只有一个分段时为 0。
+	pub standard_error: Vec<Vec<f32>>,
+}
+
+/// 工频陷波配置，在基频及其谐波处各放置一个二阶陷波节。
+#[derive(Debug, Clone, Copy)]
+pub struct NotchConfig {
+	pub sample_rate: f32,
+
+	/// 工频基频 (Hz)，通常为 50 或 60。
+	pub line_frequency_hz: f32,
+
+	/// 包括基频在内的谐波个数，超过奈奎斯特频率的谐波会被忽略。
+	pub harmonic_count: usize,
+
+	/// 品质因数 Q = 中心频率 / -3 dB 带宽，越大陷波越窄。
+	pub quality_factor: f32,
+}
+
+/// 工频检测结果。
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct LineNoiseDetection {
+	/// 检测到的工频 (Hz)。
+	pub frequency_hz: f32,
+
+	/// 工频处功率谱峰值与邻近频段中位数之比。
+	pub peak_ratio: f32,
+}
+
+/// 基于 Welch 分段互谱的连接性指标。
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConnectivityMetric {
+	/// 幅度平方相干 |Sxy|² / (Sxx·Syy)。
+	Coherence,
+	/// 虚部相干 Im(Sxy) / √(Sxx·Syy)，对零延迟的容积传导不敏感。
+	ImaginaryCoherence,
+	/// 各分段互谱相位的一致性 |mean(Sxy / |Sxy|)|。
+	PhaseLockingValue,
+	/// 相位超前或滞后的一致性 |mean(sign(Im Sxy))|。
+	PhaseLagIndex,
+}
+
+/// Welch 互谱密度矩阵。
+#[derive(Debug, Clone)]
+pub struct CrossSpectralDensity {
+	pub channel_labels: Vec<String>,
+	pub frequencies: Vec<f32>,
+
+	/// 按 [通道][通道][频率] 排列，values[i][j] = E[Xi·conj(Xj)]，对角线为单边功率谱密度。
+	pub values: Vec<Vec<Vec<Complex32>>>,
+}
+
+/// 通道 × 通道 × 频率的连接性结果。
+#[derive(Debug, Clone)]
+pub struct ConnectivityMatrix {
+	pub channel_labels: Vec<String>,
+	pub frequencies: Vec<f32>,
+
+	/// 按 [通道][通道][频率] 排列；Granger 因果为 [源通道][目标通道][频率]。
+	pub values: Vec<Vec<Vec<f32>>>,
+}
+
+/// 双变量谱 Granger 因果配置。
+#[derive(Debug, Clone, Copy)]
+pub struct GrangerConfig {
+	/// 多变量自回归模型阶数。
+	pub model_order: usize,
+
+	/// 从 0 到奈奎斯特频率均匀取的频点数。
+	pub frequency_count: usize,
+}
+
+impl Default for GrangerConfig {
+	fn default() -> Self {
+		Self {
+			model_order: 10,
+			frequency_count: 129,
+		}
+	}
+}
+
+/// 单通道特征提取配置。
+#[derive(Debug, Clone, Copy)]
+pub struct FeatureConfig {
+	/// 样本熵与近似熵的嵌入维数 m。
+	pub embedding_dimension: usize,
+
+	/// 样本熵与近似熵的容差 r 与信号标准差之比。
+	pub tolerance_ratio: f32,
+
+	/// 排列熵的阶数与延迟。
+	pub permutation_order: usize,
+	pub permutation_delay: usize,
+
+	/// Higuchi 分形维数的最大尺度 k。
+	pub higuchi_max_k: usize,
+
+	/// 谱熵的 Welch 分段长度，超过信号长度时取信号长度，重叠一半。
+	pub spectral_segment_length: usize,
+}
+
+impl Default for FeatureConfig {
+	fn default() -> Self {
+		Self {
+			embedding_dimension: 2,
+			tolerance_ratio: 0.2,
+			permutation_order: 3,
+			permutation_delay: 1,
+			higuchi_max_k: 10,
+			spectral_segment_length: 256,
+		}
+	}
+}
+
+/// 单通道的描述统计与非线性特征。
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SignalFeatures {
+	pub rms: f32,
+
+	/// 偏度 m3 / m2^1.5。
+	pub skewness: f32,
+
+	/// 超额峰度 m4 / m2² - 3，正态分布为 0。
+	pub kurtosis: f32,
+
+	/// 去均值后每个样本间隔的过零次数。
+	pub zero_crossing_rate: f32,
+
+	/// 相邻样本差的绝对值之和。
+	pub line_length: f32,
+	pub hjorth_activity: f32,
+	pub hjorth_mobility: f32,
+	pub hjorth_complexity: f32,
+
+	/// 没有长度为 m + 1 的匹配模板时为无穷大。
+	pub sample_entropy: f32,
+	pub approximate_entropy: f32,
+
+	/// 以 ln(阶数!) 归一化到 [0, 1]。
+	pub permutation_entropy: f32,
+
+	/// 以 ln(频点数) 归一化到 [0, 1]。
+	pub spectral_entropy: f32,
+	pub higuchi_fractal_dimension: f32,
+	pub katz_fractal_dimension: f32,
+}
@@ -0,0 +1,412 @@
+use std::{f32::consts::TAU, sync::Arc};
+
+use rustfft::{Fft, FftPlanner};
+
+use crate::{
+	error::SignalProcessError,
+	types::{
+		BaselineMode, Complex32, EpochSet, MorletConfig, MorletCycles, SignalBuffer,
+		TimeFrequencyPower, WaveletTransform,
+	},
+};
+
+/// 小波截断在 ±5σt 处，截断处高斯包络约为峰值的 4e-6。
+const WAVELET_HALF_WIDTH_SIGMAS: f32 = 5.0;
+
+/// 用复 Morlet 小波做连续小波变换，卷积通过 FFT 完成。
+///
+/// 信号两端按零延拓，距端点不足约 2.5σt 的系数会受边缘效应影响。
+pub fn compute_morlet_transform(
+	signal: &SignalBuffer,
+	config: &MorletConfig,
+) -> Result<WaveletTransform, SignalProcessError> {
+	if signal.samples.is_empty() {
+		return Err(SignalProcessError::EmptyInput);
+	}
+
+	let bank = MorletBank::new(signal.sample_rate, signal.samples.len(), config)?;
+	let rows = bank.transform(&signal.samples);
+	Ok(WaveletTransform {
+		times: sample_times(signal.sample_rate, signal.samples.len()),
+		frequencies: config.frequencies.clone(),
+		coefficients: transpose(&rows),
+	})
+}
+
+/// 单段信号的小波功率 |W|²。
+pub fn compute_wavelet_power(
+	signal: &SignalBuffer,
+	config: &MorletConfig,
+) -> Result<TimeFrequencyPower, SignalProcessError> {
+	let transform = compute_morlet_transform(signal, config)?;
+	Ok(TimeFrequencyPower {
+		times: transform.times,
+		frequencies: transform.frequencies,
+		values: transform
+			.coefficients
+			.iter()
+			.map(|row| row.iter().map(Complex32::norm_sqr).collect())
+			.collect(),
+		epoch_count: 1,
+		baseline: None,
+	})
+}
+
+/// 对所有分段的同一通道分别计算小波功率后取平均，时间轴相对事件时刻。
+///
+/// 先平均功率再做基线归一化，得到包含非锁相成分的事件相关谱扰动。
+pub fn compute_epoch_wavelet_power(
+	epoch_set: &EpochSet,
+	channel_index: usize,
+	config: &MorletConfig,
+) -> Result<TimeFrequencyPower, SignalProcessError> {
+	if epoch_set.epochs.is_empty() || epoch_set.times_secs.is_empty() {
+		return Err(SignalProcessError::EmptyInput);
+	}
+	if channel_index >= epoch_set.channel_labels.len() {
+		return Err(SignalProcessError::InvalidArgument(format!(
+			"通道索引 {channel_index} 超出通道数 {}",
+			epoch_set.channel_labels.len()
+		)));
+	}
+
+	let bank = MorletBank::new(epoch_set.sample_rate, epoch_set.times_secs.len(), config)?;
+	let mut average = vec![vec![0.0; epoch_set.times_secs.len()]; config.frequencies.len()];
+	for epoch in &epoch_set.epochs {
+		for (sum_row, row) in average
+			.iter_mut()
+			.zip(bank.transform(&epoch.data[channel_index]))
+		{
+			for (sum, value) in sum_row.iter_mut().zip(row.iter()) {
+				*sum += value.norm_sqr();
+			}
+		}
+	}
+	let scale = 1.0 / epoch_set.epochs.len() as f32;
+	for value in average.iter_mut().flatten() {
+		*value *= scale;
+	}
+
+	Ok(TimeFrequencyPower {
+		times: epoch_set.times_secs.clone(),
+		frequencies: config.frequencies.clone(),
+		values: transpose(&average),
+		epoch_count: epoch_set.epochs.len(),
+		baseline: None,
+	})
+}
+
+/// 按基线区间 (秒) 内各频率的功率均值与标准差做归一化。
+pub fn apply_baseline(
+	power: &TimeFrequencyPower,
+	baseline_secs: [f32; 2],
+	mode: BaselineMode,
+) -> Result<TimeFrequencyPower, SignalProcessError> {
+	if let Some(applied) = power.baseline {
+		return Err(SignalProcessError::InvalidArgument(format!(
+			"时频图已做过 {applied:?} 基线归一化"
+		)));
+	}
+	let [start, end] = baseline_secs;
+	let baseline_rows = power
+		.times
+		.iter()
+		.zip(power.values.iter())
+		.filter(|(time, _)| **time >= start && **time <= end)
+		.map(|(_, row)| row)
+		.collect::<Vec<_>>();
+	if baseline_rows.is_empty() {
+		return Err(SignalProcessError::InvalidArgument(format!(
+			"基线区间 [{start}, {end}] 内没有样本"
+		)));
+	}
+
+	let count = baseline_rows.len() as f32;
+	let statistics =
+		(0..power.frequencies.len())
+			.map(|frequency_index| {
+				let mean = baseline_rows
+					.iter()
+					.map(|row| row[frequency_index])
+					.sum::<f32>() / count;
+				let variance = baseline_rows
+					.iter()
+					.map(|row| (row[frequency_index] - mean).powi(2))
+					.sum::<f32>() / count;
+				let divisor = match mode {
+					BaselineMode::Decibel | BaselineMode::PercentChange => mean,
+					BaselineMode::ZScore => variance.sqrt(),
+				};
+				if divisor > 0.0 {
+					Ok((mean, divisor))
+				} else {
+					Err(SignalProcessError::InvalidArgument(format!(
+						"{} Hz 的基线均值或标准差为 0，无法按 {mode:?} 归一化",
+						power.frequencies[frequency_index]
+					)))
+				}
+			})
+			.collect::<Result<Vec<_>, _>>()?;
+
+	let values = power
+		.values
+		.iter()
+		.map(|row| {
+			row.iter()
+				.zip(statistics.iter())
+				.map(|(value, (mean, divisor))| match mode {
+					BaselineMode::Decibel => 10.0 * (value / divisor).log10(),
+					BaselineMode::PercentChange => 100.0 * (value - mean) / divisor,
+					BaselineMode::ZScore => (value - mean) / divisor,
+				})
+				.collect()
+		})
+		.collect();
+	Ok(TimeFrequencyPower {
+		times: power.times.clone(),
+		frequencies: power.frequencies.clone(),
+		values,
+		epoch_count: power.epoch_count,
+		baseline: Some(mode),
+	})
+}
+
+/// 针对固定信号长度预先计算好频谱的一组 Morlet 小波。
+struct MorletBank {
+	signal_length: usize,
+	fft_size: usize,
+
+	/// 各频率小波的 (半长, 频谱)。
+	kernels: Vec<(usize, Vec<Complex32>)>,
+	forward: Arc<dyn Fft<f32>>,
+	inverse: Arc<dyn Fft<f32>>,
+}
+
+impl MorletBank {
+	fn new(
+		sample_rate: f32,
+		signal_length: usize,
+		config: &MorletConfig,
+	) -> Result<Self, SignalProcessError> {
+		let cycles = resolve_cycles(sample_rate, config)?;
+		let half_lengths = config
+			.frequencies
+			.iter()
+			.zip(cycles.iter())
+			.map(|(frequency, cycles)| {
+				let sigma = cycles / (TAU * frequency);
+				(WAVELET_HALF_WIDTH_SIGMAS * sigma * sample_rate).ceil() as usize
+			})
+			.collect::<Vec<_>>();
+		let longest = half_lengths.iter().copied().max().unwrap_or(0);
+		let fft_size = (signal_length + 2 * longest).next_power_of_two();
+
+		let mut planner = FftPlanner::<f32>::new();
+		let forward = planner.plan_fft_forward(fft_size);
+		let inverse = planner.plan_fft_inverse(fft_size);
+		let kernels = config
+			.frequencies
+			.iter()
+			.zip(cycles.iter())
+			.zip(half_lengths)
+			.map(|((frequency, cycles), half_length)| {
+				let sigma = cycles / (TAU * frequency);
+				let gaussian = (0..=2 * half_length)
+					.map(|index| {
+						let time = (index as f32 - half_length as f32) / sample_rate;
+						(time, (-time * time / (2.0 * sigma * sigma)).exp())
+					})
+					.collect::<Vec<_>>();
+				// 使余弦 a·cos(2πft) 的系数模为 a。
+				let scale = 2.0 / gaussian.iter().map(|(_, weight)| weight).sum::<f32>();
+				let mut spectrum = vec![Complex32::new(0.0, 0.0); fft_size];
+				for (slot, (time, weight)) in spectrum.iter_mut().zip(gaussian) {
+					*slot = Complex32::from_polar(scale * weight, TAU * frequency * time);
+				}
+				forward.process(&mut spectrum);
+				(half_length, spectrum)
+			})
+			.collect();
+
+		Ok(Self {
+			signal_length,
+			fft_size,
+			kernels,
+			forward,
+			inverse,
+		})
+	}
+
+	/// 返回按 [频率][时刻] 排列的系数。
+	fn transform(&self, samples: &[f32]) -> Vec<Vec<Complex32>> {
+		let mut signal_spectrum = vec![Complex32::new(0.0, 0.0); self.fft_size];
+		for (slot, sample) in signal_spectrum.iter_mut().zip(samples.iter()) {
+			*slot = Complex32::new(*sample, 0.0);
+		}
+		self.forward.process(&mut signal_spectrum);
+
+		let scale = 1.0 / self.fft_size as f32;
+		self.kernels
+			.iter()
+			.map(|(half_length, kernel_spectrum)| {
+				let mut product = signal_spectrum
+					.iter()
+					.zip(kernel_spectrum.iter())
+					.map(|(signal, kernel)| signal * kernel)
+					.collect::<Vec<_>>();
+				self.inverse.process(&mut product);
+				product[*half_length..*half_length + self.signal_length]
+					.iter()
+					.map(|value| value * scale)
+					.collect()
+			})
+			.collect()
+	}
+}
+
+fn resolve_cycles(sample_rate: f32, config: &MorletConfig) -> Result<Vec<f32>, SignalProcessError> {
+	if sample_rate <= 0.0 {
+		return Err(SignalProcessError::InvalidArgument(
+			"采样率必须大于 0".to_string(),
+		));
+	}
+	if config.frequencies.is_empty() {
+		return Err(SignalProcessError::InvalidArgument(
+			"小波频率列表不能为空".to_string(),
+		));
+	}
+	if let Some(frequency) = config
+		.frequencies
+		.iter()
+		.find(|frequency| **frequency <= 0.0 || **frequency >= sample_rate / 2.0)
+	{
+		return Err(SignalProcessError::InvalidArgument(format!(
+			"小波频率 {frequency} Hz 必须位于 (0, {}) Hz 内",
+			sample_rate / 2.0
+		)));
+	}
+
+	let frequency_count = config.frequencies.len();
+	let cycles = match &config.cycles {
+		MorletCycles::Constant(cycles) => vec![*cycles; frequency_count],
+		MorletCycles::Linear { low, high } => (0..frequency_count)
+			.map(|index| {
+				let fraction = index as f32 / (frequency_count - 1).max(1) as f32;
+				low + (high - low) * fraction
+			})
+			.collect(),
+		MorletCycles::PerFrequency(cycles) => {
+			if cycles.len() != frequency_count {
+				return Err(SignalProcessError::InvalidArgument(format!(
+					"周期数个数 {} 与频率个数 {frequency_count} 不一致",
+					cycles.len()
+				)));
+			}
+			cycles.clone()
+		}
+	};
+	if cycles
+		.iter()
+		.any(|cycles| !cycles.is_finite() || *cycles <= 0.0)
+	{
+		return Err(SignalProcessError::InvalidArgument(
+			"小波周期数必须为正的有限值".to_string(),
+		));
+	}
+	Ok(cycles)
+}
+
+fn sample_times(sample_rate: f32, sample_count: usize) -> Vec<f32> {
+	(0..sample_count)
+		.map(|index| index as f32 / sample_rate)
+		.collect()
+}
+
+/// 把 [频率][时刻] 转为 [时刻][频率]。
+fn transpose<T: Copy>(rows: &[Vec<T>]) -> Vec<Vec<T>> {
+	let column_count = rows.first().map_or(0, Vec::len);
+	(0..column_count)
+		.map(|column| rows.iter().map(|row| row[column]).collect())
+		.collect()
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::types::{Epoch, EventMarker};
+
+	const SAMPLE_RATE: f32 = 256.0;
+
+	#[test]
+	fn morlet_power_should_match_sine_amplitude_and_frequency() {
+		let signal = SignalBuffer {
+			sample_rate: SAMPLE_RATE,
+			samples: (0..1024)
+				.map(|index| 2.0 * (TAU * 10.0 * index as f32 / SAMPLE_RATE).cos())
+				.collect(),
+		};
+		let config = MorletConfig {
+			frequencies: vec![5.0, 10.0, 20.0],
+			cycles: MorletCycles::Constant(7.0),
+		};
+
+		let power = compute_wavelet_power(&signal, &config)
+			.unwrap_or_else(|error| panic!("小波功率计算失败: {error}"));
+
+		assert_eq!(power.times.len(), 1024);
+		for row in &power.values[256..768] {
+			assert!((row[1] - 4.0).abs() < 0.05);
+			assert!(row[0] < 0.01);
+			assert!(row[2] < 0.01);
+		}
+	}
+
+	#[test]
+	fn epoch_power_should_be_baseline_normalized() {
+		let times_secs = (0..640)
+			.map(|index| (index as f32 - 256.0) / SAMPLE_RATE)
+			.collect::<Vec<_>>();
+		let epochs = (0..3)
+			.map(|trial| Epoch {
+				event: EventMarker {
+					onset_secs: trial as f64 * 3.0,
+					code: 1,
+				},
+				data: vec![
+					times_secs
+						.iter()
+						.map(|time| {
+							let amplitude = if (0.2..0.6).contains(time) { 2.0 } else { 1.0 };
+							amplitude * (TAU * 10.0 * time + trial as f32).sin()
+						})
+						.collect(),
+				],
+			})
+			.collect();
+		let epoch_set = EpochSet {
+			channel_labels: vec!["Oz".to_string()],
+			sample_rate: SAMPLE_RATE,
+			times_secs,
+			epochs,
+			rejected: Vec::new(),
+		};
+		let config = MorletConfig {
+			frequencies: vec![10.0],
+			cycles: MorletCycles::Constant(5.0),
+		};
+
+		let power = compute_epoch_wavelet_power(&epoch_set, 0, &config)
+			.unwrap_or_else(|error| panic!("分段小波功率计算失败: {error}"));
+		let decibel = apply_baseline(&power, [-0.8, -0.2], BaselineMode::Decibel)
+			.unwrap_or_else(|error| panic!("基线归一化失败: {error}"));
+		let percent = apply_baseline(&power, [-0.8, -0.2], BaselineMode::PercentChange)
+			.unwrap_or_else(|error| panic!("基线归一化失败: {error}"));
+
+		let burst_center = 256 + (0.4 * SAMPLE_RATE) as usize;
+		assert_eq!(power.epoch_count, 3);
+		assert!((decibel.values[burst_center][0] - 10.0 * 4.0f32.log10()).abs() < 0.3);
+		assert!((percent.values[burst_center][0] - 300.0).abs() < 20.0);
+		assert!(decibel.values[256 - 64][0].abs() < 0.2);
+		assert!(apply_baseline(&decibel, [-0.8, -0.2], BaselineMode::ZScore).is_err());
+	}
+}
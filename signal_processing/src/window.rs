@@ -0,0 +1,127 @@
+use std::f64::consts::PI;
+
+use crate::types::WindowKind;
+
+/// 构造对称窗函数，适用于 FIR 设计。
+pub fn build_window(kind: WindowKind, length: usize) -> Vec<f32> {
+	match length {
+		0 => Vec::new(),
+		1 => vec![1.0],
+		_ => build_window_with_period(kind, length, length - 1),
+	}
+}
+
+/// 构造周期窗函数，适用于需要重叠相加重建的短时分析。
+pub fn build_periodic_window(kind: WindowKind, length: usize) -> Vec<f32> {
+	match length {
+		0 => Vec::new(),
+		1 => vec![1.0],
+		_ => build_window_with_period(kind, length, length),
+	}
+}
+
+fn build_window_with_period(kind: WindowKind, length: usize, period: usize) -> Vec<f32> {
+	let period = period as f64;
+	(0..length)
+		.map(|index| {
+			let phase = 2.0 * PI * index as f64 / period;
+			let value = match kind {
+				WindowKind::Rectangular => 1.0,
+				WindowKind::Hann => cosine_sum(&[0.5, 0.5], phase),
+				WindowKind::Hamming => cosine_sum(&[0.54, 0.46], phase),
+				WindowKind::Blackman => cosine_sum(&[0.42, 0.5, 0.08], phase),
+				WindowKind::FlatTop => cosine_sum(
+					&[
+						0.215_578_95,
+						0.416_631_58,
+						0.277_263_158,
+						0.083_578_947,
+						0.006_947_368,
+					],
+					phase,
+				),
+				WindowKind::Kaiser { beta } => {
+					let ratio = 2.0 * index as f64 / period - 1.0;
+					let beta = beta as f64;
+					bessel_i0(beta * (1.0 - ratio * ratio).max(0.0).sqrt()) / bessel_i0(beta)
+				}
+			};
+			value as f32
+		})
+		.collect()
+}
+
+/// 广义余弦窗 Σ (-1)^k a_k cos(kθ)。
+fn cosine_sum(coefficients: &[f64], phase: f64) -> f64 {
+	coefficients
+		.iter()
+		.enumerate()
+		.map(|(order, coefficient)| {
+			let sign = if order % 2 == 0 { 1.0 } else { -1.0 };
+			sign * coefficient * (order as f64 * phase).cos()
+		})
+		.sum()
+}
+
+/// 第一类零阶修正 Bessel 函数，按级数展开到收敛。
+pub(crate) fn bessel_i0(x: f64) -> f64 {
+	let half = x / 2.0;
+	let mut term = 1.0;
+	let mut sum = 1.0;
+	for order in 1..200 {
+		term *= (half / order as f64) * (half / order as f64);
+		sum += term;
+		if term < sum * 1e-16 {
+			break;
+		}
+	}
+	sum
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn windows_should_be_symmetric_and_peak_at_center() {
+		for kind in [
+			WindowKind::Hann,
+			WindowKind::Hamming,
+			WindowKind::Blackman,
+			WindowKind::Kaiser { beta: 8.6 },
+			WindowKind::FlatTop,
+		] {
+			let window = build_window(kind, 65);
+			for index in 0..32 {
+				assert!((window[index] - window[64 - index]).abs() < 1e-6);
+			}
+			assert!((window[32] - 1.0).abs() < 1e-3);
+		}
+	}
+
+	#[test]
+	fn kaiser_with_zero_beta_should_be_rectangular() {
+		let window = build_window(WindowKind::Kaiser { beta: 0.0 }, 16);
+
+		assert!(window.iter().all(|value| (value - 1.0).abs() < 1e-6));
+	}
+
+	#[test]
+	fn periodic_hann_should_sum_to_constant_at_half_overlap() {
+		let window = build_periodic_window(WindowKind::Hann, 64);
+
+		for index in 0..32 {
+			assert!((window[index] + window[index + 32] - 1.0).abs() < 1e-6);
+		}
+	}
+
+	#[test]
+	fn degenerate_lengths_should_not_underflow() {
+		for kind in [WindowKind::Hann, WindowKind::Kaiser { beta: 8.6 }] {
+			assert!(build_window(kind, 0).is_empty());
+			assert!(build_periodic_window(kind, 0).is_empty());
+			assert_eq!(build_window(kind, 1), [1.0]);
+			assert_eq!(build_periodic_window(kind, 1), [1.0]);
+		}
+	}
+}
@@ -5,7 +5,7 @@
 use crate::homepage::common::Functions;
 use crate::homepage::playback_plot::systems::{
 	handle_next_page, handle_open_file, handle_play_pause, handle_prev_page, handle_speed_change,
-	on_enter, on_exit, spawn_playback_control_ui, update_playback,
+	on_enter, on_exit, refresh_playback_cache, spawn_playback_control_ui, update_playback,
 	update_playback_position_display, update_playback_waveform,
 };
 use bevy::prelude::*;
@@ -26,7 +26,7 @@ impl Plugin for PlaybackPlotPlugin {
 			Update,
 			(
 				update_playback,
-				update_playback_waveform,
+				(refresh_playback_cache, update_playback_waveform).chain(),
 				update_playback_position_display,
 				handle_open_file,
 				handle_play_pause,
@@ -3,6 +3,7 @@
 //! 定义回放波形的数据结构、播放状态和配置
 
 use bevy::prelude::*;
+use edf_io::{EdfFileReader, EdfLoaderError};
 
 // ============================================================================
 // PLAYBACK DATA - 回放数据资源
@@ -10,16 +11,21 @@ use bevy::prelude::*;
 
 /// 回放数据资源
 ///
-/// 存储从 EDF 文件加载的波形数据
+/// 持有 EDF/BDF 文件的按需读取器，只缓存当前页附近的波形数据，
+/// 大文件也无需整体读入内存
 #[derive(Resource, Debug, Default)]
 pub struct PlaybackData {
 	/// 源文件路径
 	pub file_path: String,
-	/// 各通道数据
+	/// 按需读取器，未加载文件时为 None
+	pub reader: Option<EdfFileReader>,
+	/// 缓存的各通道数据，已换算到回放采样率，从 `cache_start` 开始
 	pub channels: Vec<Vec<f32>>,
+	/// 缓存起点（回放采样率下的采样点索引）
+	pub cache_start: usize,
 	/// 通道数量（从 EDF 文件头读取）
 	pub channel_count: usize,
-	/// 采样率（从 EDF 文件头读取）
+	/// 回放采样率，混合采样率文件取最高的通道采样率
 	pub sample_rate: u32,
 	/// 总数据点数
 	pub total_points: usize,
@@ -35,13 +41,89 @@ impl PlaybackData {
 	) -> Self {
 		Self {
 			file_path,
+			reader: None,
 			channels: vec![Vec::new(); channel_count],
+			cache_start: 0,
 			channel_count,
 			sample_rate,
 			total_points,
 		}
 	}
 
+	/// 打开文件并只解析文件头，波形数据在显示时按页读取
+	///
+	/// # Arguments
+	/// * `path` - EDF/BDF 文件路径
+	pub fn open(path: &str) -> Result<Self, EdfLoaderError> {
+		let reader = EdfFileReader::open(path)?;
+		// 回放时间轴使用整数采样率，混合采样率文件对齐到最高的通道采样率，不对 EEG 通道降采样
+		let sample_rate = reader.max_sample_rate().round().max(1.0) as u32;
+		let total_points = reader
+			.channel_len_at_rate(0, sample_rate as f32)
+			.unwrap_or(0);
+		Ok(Self {
+			file_path: reader.path().to_string(),
+			channels: vec![Vec::new(); reader.channel_count()],
+			cache_start: 0,
+			channel_count: reader.channel_count(),
+			sample_rate,
+			total_points,
+			reader: Some(reader),
+		})
+	}
+
+	/// 缓存是否已覆盖 `[start, end)`
+	pub fn is_cached(&self, start: usize, end: usize) -> bool {
+		let cached_len = self.channels.first().map(Vec::len).unwrap_or(0);
+		start >= self.cache_start && end <= self.cache_start + cached_len
+	}
+
+	/// 确保缓存覆盖 `[start, end)`，未覆盖时读取包含前后各 `margin` 个采样点的新页
+	///
+	/// # Arguments
+	/// * `start` - 起始采样点索引
+	/// * `end` - 结束采样点索引（不含）
+	/// * `margin` - 额外预读的采样点数，减少连续播放时的读盘次数
+	pub fn ensure_cached(
+		&mut self,
+		start: usize,
+		end: usize,
+		margin: usize,
+	) -> Result<(), EdfLoaderError> {
+		if self.is_cached(start, end) {
+			return Ok(());
+		}
+		let Some(reader) = self.reader.as_mut() else {
+			return Ok(());
+		};
+
+		let cache_start = start.saturating_sub(margin);
+		let cache_end = end.saturating_add(margin).min(self.total_points);
+		let target_rate = self.sample_rate as f32;
+		self.channels = (0..self.channel_count)
+			.map(|channel| {
+				reader.read_range_at_rate(
+					channel,
+					cache_start,
+					cache_end - cache_start,
+					target_rate,
+				)
+			})
+			.collect::<Result<Vec<_>, _>>()?;
+		self.cache_start = cache_start;
+		Ok(())
+	}
+
+	/// 获取通道在 `[start, end)` 范围内的缓存数据，超出缓存的部分被截断
+	pub fn channel_window(&self, channel: usize, start: usize, end: usize) -> &[f32] {
+		let Some(samples) = self.channels.get(channel) else {
+			return &[];
+		};
+		let from = start.saturating_sub(self.cache_start).min(samples.len());
+		let to = end.saturating_sub(self.cache_start).min(samples.len());
+		&samples[from..to.max(from)]
+	}
+
 	/// 获取通道数量
 	pub fn channel_count(&self) -> usize {
 		self.channel_count
@@ -61,7 +143,9 @@ impl PlaybackData {
 	pub fn empty() -> Self {
 		Self {
 			file_path: "-".to_string(),
+			reader: None,
 			channels: Vec::new(),
+			cache_start: 0,
 			channel_count: 0,
 			sample_rate: 1,
 			total_points: 0,
@@ -3,7 +3,6 @@
 //! 实现回放波形的生命周期、数据加载、渲染和播放控制
 
 use bevy::prelude::*;
-use edf_io::EdfLoader;
 
 use crate::file_dialog::pick_single_file;
 use crate::homepage::common::ContentAreaMarker;
@@ -21,6 +20,8 @@ const WAVEFORM_WIDTH: f32 = 800.0;
 const WAVEFORM_HEIGHT: f32 = 900.0;
 const AXIS_COLOR: [f32; 4] = [0.45, 0.45, 0.45, 1.0];
 const GRID_COLOR: [f32; 4] = [0.25, 0.25, 0.25, 1.0];
+/// 缓存当前页时前后各预读的页数
+const CACHE_MARGIN_PAGES: usize = 2;
 
 const CHANNEL_COLORS: [[f32; 4]; 8] = [
 	[0.2, 0.6, 0.9, 1.0],
@@ -255,7 +256,7 @@ pub fn handle_open_file(
 			continue;
 		};
 
-		match load_playback_data(&path) {
+		match PlaybackData::open(&path.to_string_lossy()) {
 			Ok(loaded_data) => {
 				for entity in &scene_query {
 					commands.entity(entity).despawn();
@@ -289,17 +290,9 @@ pub fn handle_open_file(
 	}
 }
 
-fn generate_waveform_points(
-	channel_data: &[f32],
-	channel_index: usize,
-	channel_count: usize,
-	window_start: usize,
-	window_end: usize,
-) -> Vec<Vec2> {
+fn generate_waveform_points(page: &[f32], channel_index: usize, channel_count: usize) -> Vec<Vec2> {
 	let y_offset = channel_center_y(channel_index, channel_count);
 	let current_channel_height = channel_height(channel_count);
-	let page =
-		&channel_data[window_start.min(channel_data.len())..window_end.min(channel_data.len())];
 
 	if page.is_empty() {
 		return vec![Vec2::new(0.0, y_offset)];
@@ -367,6 +360,24 @@ pub fn update_playback(
 	}
 }
 
+/// 按当前可见窗口从文件读取波形页，窗口仍在缓存内时不读盘
+pub fn refresh_playback_cache(mut data: ResMut<PlaybackData>, control: Res<PlaybackControl>) {
+	if data.total_points == 0 {
+		return;
+	}
+
+	let (window_start, window_end) = visible_window(&control, data.total_points);
+	if data.is_cached(window_start, window_end) {
+		return;
+	}
+	let margin = control.page_size * CACHE_MARGIN_PAGES;
+	if let Err(error) = data.ensure_cached(window_start, window_end, margin) {
+		// 读取失败后不再逐帧重试，保留已缓存的数据
+		error!("读取回放数据失败: {}", error);
+		data.reader = None;
+	}
+}
+
 pub fn update_playback_waveform(
 	mut meshes: ResMut<Assets<Mesh>>,
 	data: Res<PlaybackData>,
@@ -381,11 +392,11 @@ pub fn update_playback_waveform(
 	let channel_count = data.channel_count.max(1);
 
 	for (idx, mut mesh2d) in query.iter_mut().enumerate() {
-		let Some(channel_data) = data.channels.get(idx) else {
+		if idx >= data.channels.len() {
 			continue;
-		};
-		let points =
-			generate_waveform_points(channel_data, idx, channel_count, window_start, window_end);
+		}
+		let page = data.channel_window(idx, window_start, window_end);
+		let points = generate_waveform_points(page, idx, channel_count);
 		*mesh2d = Mesh2d(meshes.add(Mesh::from(Polyline2d::new(points))));
 	}
 }
@@ -612,19 +623,6 @@ fn default_playback_directory() -> std::path::PathBuf {
 	base_dir.join("data")
 }
 
-/// 加载 EDF/BDF 文件到回放资源
-fn load_playback_data(path: &std::path::Path) -> Result<PlaybackData, String> {
-	let file_path = path.to_string_lossy().to_string();
-	let loader = EdfLoader::from_file(&file_path).map_err(|error| error.to_string())?;
-	Ok(PlaybackData {
-		file_path: loader.path().to_string(),
-		channels: loader.channels().to_vec(),
-		channel_count: loader.channel_count(),
-		sample_rate: loader.sample_rate(),
-		total_points: loader.total_points(),
-	})
-}
-
 pub fn update_playback_position_display(
 	control: Res<PlaybackControl>,
 	data: Res<PlaybackData>,
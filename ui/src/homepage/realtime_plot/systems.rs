@@ -5,8 +5,9 @@ use crate::homepage::realtime_plot::components::{
 	ChannelSliderMarker, ControlPanelMarker, RealtimePlotContentMarker, SampleRateDropdownMarker,
 	WaveformMeshMarker,
 };
-use crate::homepage::realtime_plot::resources::{WaveformData, WaveformGenerator};
+use crate::homepage::realtime_plot::resources::WaveformData;
 use config::data_structure::Setting;
+use signal_processing::{EegSimulationConfig, EegSimulator};
 
 // ============================================================================
 // REALTIME_PLOT CONSTANTS
@@ -550,8 +551,36 @@ pub fn update_waveform_settings(
 /// 波形生成器资源，用于存储生成器状态
 #[derive(Resource, Debug, Default)]
 pub struct WaveformGeneratorState {
-	/// 波形生成器实例
-	pub generator: WaveformGenerator,
+	/// 类 EEG 仿真器，采样率或通道数变化时重建
+	pub simulator: Option<EegSimulator>,
+}
+
+impl WaveformGeneratorState {
+	/// 确保仿真器与当前设置一致
+	///
+	/// # Returns
+	/// 仿真器可用时返回其可变引用
+	pub fn sync_with(&mut self, settings: &WaveformSettings) -> Option<&mut EegSimulator> {
+		let outdated = self.simulator.as_ref().is_none_or(|simulator| {
+			simulator.channel_count() != settings.channel_count
+				|| simulator.config().sample_rate != settings.sample_rate as f32
+		});
+		if outdated {
+			let config = EegSimulationConfig {
+				sample_rate: settings.sample_rate as f32,
+				channel_count: settings.channel_count,
+				..EegSimulationConfig::default()
+			};
+			self.simulator = match EegSimulator::new(config) {
+				Ok(simulator) => Some(simulator),
+				Err(error) => {
+					warn!("创建 EEG 仿真器失败: {error}");
+					None
+				}
+			};
+		}
+		self.simulator.as_mut()
+	}
 }
 
 /// 波形生成计时器
@@ -645,16 +674,20 @@ pub fn generate_waveform_data(
 	// 更新计时器的采样率
 	timer.set_sample_rate(settings.sample_rate);
 
-	// 更新生成器的采样率
-	generator_state.generator.sample_rate = settings.sample_rate;
+	// 采样率或通道数变化时重建仿真器
+	if settings.channel_count == 0 {
+		return;
+	}
+	let Some(simulator) = generator_state.sync_with(&settings) else {
+		return;
+	};
 
 	// 检查是否应该生成数据
 	let dt = time.delta_secs();
 	let should_generate = timer.update(dt);
 	if should_generate {
-		// 为每个通道生成一个数据点
-		for ch in 0..settings.channel_count {
-			let value = generator_state.generator.generate_single();
+		// 所有通道共享同一帧，保证眨眼等伪迹在各通道同时出现
+		for (ch, value) in simulator.next_frame().into_iter().enumerate() {
 			waveform_data.push(ch, value);
 		}
 		// 每秒打印一次数据状态
@@ -666,7 +699,9 @@ pub fn generate_waveform_data(
 			{
 				info!(
 					"Waveform: {} points, last={:.2}, generator_time={:.4}",
-					point_count, last_val, generator_state.generator.time
+					point_count,
+					last_val,
+					simulator.current_time_secs()
 				);
 			}
 		}